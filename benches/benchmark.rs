@@ -0,0 +1,164 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use earclip::{
+    earclip, earcut, earcut_impl, earcut_into, earcut_points2d, earcut_with_bbox, flatten_float,
+    Store,
+};
+
+use std::fs;
+use std::hint::black_box;
+
+fn fixture(name: &str) -> Vec<Vec<Vec<f64>>> {
+    let path = format!("{}/test/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// the flattened vertices of a regular `n`-gon around the origin
+fn regular_polygon(n: usize, radius: f64) -> Vec<f64> {
+    (0..n)
+        .flat_map(|i| {
+            let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+            [angle.cos() * radius, angle.sin() * radius]
+        })
+        .collect()
+}
+
+fn bench_fixtures(c: &mut Criterion) {
+    for name in ["building", "dude", "annulus", "water", "water-huge"] {
+        let (vertices, hole_indices, dim) = flatten_float(&fixture(name));
+        c.bench_function(name, |b| {
+            b.iter(|| earcut::<f64, usize>(black_box(&vertices), &hole_indices, dim))
+        });
+    }
+}
+
+/// point arrays fed straight in versus converted into the nested `Vec` form first
+fn bench_points_input(c: &mut Criterion) {
+    let rings: Vec<Vec<[f64; 2]>> = fixture("water-huge")
+        .iter()
+        .map(|ring| ring.iter().map(|p| [p[0], p[1]]).collect())
+        .collect();
+    let slices: Vec<&[[f64; 2]]> = rings.iter().map(|ring| ring.as_slice()).collect();
+
+    c.bench_function("water-huge nested vec", |b| {
+        b.iter(|| {
+            let nested: Vec<Vec<Vec<f64>>> = slices
+                .iter()
+                .map(|ring| ring.iter().map(|p| p.to_vec()).collect())
+                .collect();
+            earclip::<f64, usize>(black_box(&nested), None, None)
+        })
+    });
+    c.bench_function("water-huge points2d", |b| {
+        b.iter(|| earcut_points2d::<f64, usize>(black_box(&slices)))
+    });
+}
+
+/// a convex ring, which skips the ear search, against the same ring with one vertex pulled in,
+/// which has to search for ears
+fn bench_convex(c: &mut Criterion) {
+    let ring = regular_polygon(1000, 1.0);
+    let mut dented = ring.clone();
+    dented[0] = 0.99;
+    c.bench_function("convex 1000-gon", |b| {
+        b.iter(|| earcut::<f64, usize>(black_box(&ring), &[], 2))
+    });
+    c.bench_function("dented 1000-gon", |b| {
+        b.iter(|| earcut::<f64, usize>(black_box(&dented), &[], 2))
+    });
+}
+
+/// many 4 to 8 vertex polygons, where the per call setup dominates
+fn bench_small_polygons(c: &mut Criterion) {
+    let polygons: Vec<Vec<f64>> = (0..10_000)
+        .map(|p| {
+            let mut ring = regular_polygon(4 + p % 5, 1.0);
+            for (i, point) in ring.as_chunks_mut::<2>().0.iter_mut().enumerate() {
+                // every other vertex pulled in makes most of them concave
+                if i % 2 == 1 {
+                    let radius = 0.4 + (p % 7) as f64 / 10.0;
+                    point.iter_mut().for_each(|c| *c *= radius);
+                }
+                point[0] += p as f64;
+            }
+            ring
+        })
+        .collect();
+
+    c.bench_function("small polygons", |b| {
+        b.iter(|| {
+            for polygon in &polygons {
+                black_box(earcut::<f64, u32>(black_box(polygon), &[], 2));
+            }
+        })
+    });
+    c.bench_function("small polygons reused store", |b| {
+        let mut store = Store::default();
+        let mut indices: Vec<u32> = Vec::new();
+        b.iter(|| {
+            for polygon in &polygons {
+                earcut_into(black_box(polygon), &[], 2, &mut indices, &mut store);
+                black_box(&indices);
+            }
+        })
+    });
+}
+
+/// dense detail in a tiny corner of a huge bounding box, which 15-bit z-order codes can't tell
+/// apart
+fn bench_wide_spread(c: &mut Criterion) {
+    let teeth = 10_000;
+    let mut ring = vec![0.0, 0.0, 1e5, 0.0, 1e5, 0.001];
+    for i in (0..=teeth).rev() {
+        let y = if i % 2 == 0 { 1.0 } else { 0.95 };
+        ring.extend([i as f64 / teeth as f64, y]);
+    }
+    let mut tested = Vec::new();
+    for bits in [15, 21] {
+        let mut indices: Vec<usize> = Vec::new();
+        let mut store = Store::default().with_morton_bits(bits);
+        let stats = earcut_impl(&ring, &[], 2, &mut indices, &mut store);
+        println!(
+            "wide spread {bits} bits: {} points tested",
+            stats.points_tested
+        );
+        tested.push(stats.points_tested);
+        c.bench_function(&format!("wide spread {bits} bits"), |b| {
+            b.iter(|| earcut_into(black_box(&ring), &[], 2, &mut indices, &mut store))
+        });
+    }
+    // the teeth are too close together for 15 bits to tell apart
+    assert!(tested[1] < tested[0]);
+}
+
+/// a bounding box known up front versus one computed from the vertices
+fn bench_known_bbox(c: &mut Criterion) {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("water-huge"));
+    let mut bbox = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    for xy in vertices.chunks_exact(dim) {
+        bbox = [
+            bbox[0].min(xy[0]),
+            bbox[1].min(xy[1]),
+            bbox[2].max(xy[0]),
+            bbox[3].max(xy[1]),
+        ];
+    }
+    c.bench_function("water-huge known bbox", |b| {
+        b.iter(|| earcut_with_bbox::<f64, usize>(black_box(&vertices), &hole_indices, dim, bbox))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_fixtures,
+    bench_points_input,
+    bench_convex,
+    bench_small_polygons,
+    bench_wide_spread,
+    bench_known_bbox
+);
+criterion_main!(benches);
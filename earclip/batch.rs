@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{Earclip, Index, Store};
+
+impl<T: Float, N: Index> Earclip<T, N> {
+    /// Triangulate many independent polygons, in parallel with the `rayon` feature
+    ///
+    /// Working memory is reused between the polygons handled by the same thread.
+    ///
+    /// ## Returns
+    /// The flattened vertices and triangle indices of each polygon, in order
+    #[cfg(feature = "rayon")]
+    pub fn triangulate_batch(&self, polygons: &[Vec<Vec<Vec<T>>>]) -> Vec<(Vec<T>, Vec<N>)>
+    where
+        T: Send + Sync,
+        N: Send + Sync,
+    {
+        polygons
+            .par_iter()
+            .map_init(Store::default, |store, polygon| {
+                self.triangulate_with_store(polygon, store)
+            })
+            .collect()
+    }
+
+    /// Triangulate many independent polygons, in parallel with the `rayon` feature
+    ///
+    /// Working memory is reused between the polygons.
+    ///
+    /// ## Returns
+    /// The flattened vertices and triangle indices of each polygon, in order
+    #[cfg(not(feature = "rayon"))]
+    pub fn triangulate_batch(&self, polygons: &[Vec<Vec<Vec<T>>>]) -> Vec<(Vec<T>, Vec<N>)> {
+        let mut store = Store::default();
+        polygons
+            .iter()
+            .map(|polygon| self.triangulate_with_store(polygon, &mut store))
+            .collect()
+    }
+}
+
+/// Triangulate many independent polygons with [`crate::earclip`]'s defaults, in parallel with
+/// the `rayon` feature, see [`Earclip::triangulate_batch`]
+#[cfg(feature = "rayon")]
+pub fn earclip_batch<T: Float + Send + Sync, N: Index + Send + Sync>(
+    polygons: &[Vec<Vec<Vec<T>>>],
+) -> Vec<(Vec<T>, Vec<N>)> {
+    Earclip::new().triangulate_batch(polygons)
+}
+
+/// Triangulate many independent polygons with [`crate::earclip`]'s defaults, in parallel with
+/// the `rayon` feature, see [`Earclip::triangulate_batch`]
+#[cfg(not(feature = "rayon"))]
+pub fn earclip_batch<T: Float, N: Index>(polygons: &[Vec<Vec<Vec<T>>>]) -> Vec<(Vec<T>, Vec<N>)> {
+    Earclip::new().triangulate_batch(polygons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earclip;
+    use alloc::vec;
+
+    /// the points of a regular `n`-gon around the origin
+    fn regular_polygon(n: usize, radius: f64) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                let angle = i as f64 / n as f64 * core::f64::consts::TAU;
+                vec![angle.cos() * radius, angle.sin() * radius]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batch_matches_serial() {
+        // stars of growing size, some with a square hole
+        let polygons: Vec<Vec<Vec<Vec<f64>>>> = (3..200)
+            .map(|n| {
+                let mut outer = regular_polygon(n * 2, 10.0);
+                outer.iter_mut().skip(1).step_by(2).for_each(|point| {
+                    point.iter_mut().for_each(|c| *c *= 0.6);
+                });
+                let hole = vec![
+                    vec![-1.0, -1.0],
+                    vec![-1.0, 1.0],
+                    vec![1.0, 1.0],
+                    vec![1.0, -1.0],
+                ];
+                if n % 2 == 0 {
+                    vec![outer, hole]
+                } else {
+                    vec![outer]
+                }
+            })
+            .collect();
+
+        let serial: Vec<(Vec<f64>, Vec<u32>)> = polygons
+            .iter()
+            .map(|polygon| earclip(polygon, None, None))
+            .collect();
+        assert_eq!(earclip_batch(&polygons), serial);
+
+        let builder = Earclip::new().modulo(3.0).offset(1);
+        let serial: Vec<(Vec<f64>, Vec<u32>)> = polygons
+            .iter()
+            .map(|polygon| earclip(polygon, Some(3.0), Some(1)))
+            .collect();
+        assert_eq!(builder.triangulate_batch(&polygons), serial);
+    }
+}
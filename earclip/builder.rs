@@ -0,0 +1,313 @@
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::{
+    debug_assert_index_fits, earcut_impl, filter_slivers, flatten, flatten_rings, index_fits,
+    remove_spikes, signed_area, split_rings, tesselate, triangle_area, validate, EarcutError,
+    Index, Store,
+};
+
+/// The winding of output triangles in y-up coordinates, where counter-clockwise triangles have
+/// a positive signed area. In y-down coordinates, as used by [`crate::is_clockwise`], the
+/// windings are the other way around.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    /// Counter-clockwise in y-up coordinates, clockwise in y-down coordinates
+    #[default]
+    CounterClockwise,
+    /// Clockwise in y-up coordinates, counter-clockwise in y-down coordinates
+    Clockwise,
+    /// The winding of the input's outer ring
+    AsInput,
+}
+
+/// A configurable polygon triangulator; [`crate::earclip`] is this with only `modulo` and
+/// `offset` set.
+///
+/// ```
+/// use earclip::{earclip, Earclip};
+///
+/// let polygon = vec![vec![vec![0.0, 0.0], vec![4.0, 0.0], vec![4.0, 4.0], vec![0.0, 4.0]]];
+/// let (vertices, indices) = Earclip::<f64, u32>::new().modulo(2.0).triangulate(&polygon);
+/// assert_eq!((vertices, indices), earclip(&polygon, Some(2.0), None));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Earclip<T: Float, N: Index> {
+    modulo: Option<T>,
+    offset: Option<N>,
+    winding: Winding,
+    min_area: Option<T>,
+    remove_spikes: bool,
+}
+
+impl<T: Float, N: Index> Default for Earclip<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, N: Index> Earclip<T, N> {
+    /// Create a triangulator without tesselation, offset or post processing
+    pub fn new() -> Self {
+        Self {
+            modulo: None,
+            offset: None,
+            winding: Winding::CounterClockwise,
+            min_area: None,
+            remove_spikes: false,
+        }
+    }
+
+    /// Tesselate the triangles along every multiple of `modulo`
+    pub fn modulo(mut self, modulo: T) -> Self {
+        self.modulo = Some(modulo);
+        self
+    }
+
+    /// Add `offset` to every output index
+    pub fn offset(mut self, offset: N) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Flip triangles as needed so all of them have the given winding.
+    ///
+    /// Defaults to [`Winding::CounterClockwise`], whatever the winding of the input rings.
+    pub fn winding(mut self, winding: Winding) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    /// Drop triangles smaller than `min_area`, see [`crate::filter_slivers`]
+    pub fn min_area(mut self, min_area: T) -> Self {
+        self.min_area = Some(min_area);
+        self
+    }
+
+    /// Remove zero-width spikes from every ring before triangulating, see [`crate::remove_spikes`].
+    ///
+    /// The output vertices are then those left in the rings, so indices no longer map 1:1 back
+    /// to the input points.
+    pub fn remove_spikes(mut self) -> Self {
+        self.remove_spikes = true;
+        self
+    }
+
+    /// Triangulate a polygon; the first ring is the outer ring, the rest are holes
+    ///
+    /// ## Returns
+    /// The flattened vertices and the triangle indices, see [`crate::earclip`]
+    pub fn triangulate(&self, polygon: &[Vec<Vec<T>>]) -> (Vec<T>, Vec<N>) {
+        self.triangulate_with_store(polygon, &mut Store::default())
+    }
+
+    /// [`Earclip::triangulate`] using `store` as working memory, to reuse its allocations
+    /// across many polygons
+    pub fn triangulate_with_store(
+        &self,
+        polygon: &[Vec<Vec<T>>],
+        store: &mut Store<T>,
+    ) -> (Vec<T>, Vec<N>) {
+        let (vertices, hole_indices, dim) = flatten::<T, N>(polygon);
+        self.triangulate_owned(vertices, &hole_indices, dim, &[], store)
+    }
+
+    /// [`Earclip::triangulate`] that hands the triangles to `inspect` before they are
+    /// tesselated, together with the vertices, hole indices and dimension they index into
+    pub(crate) fn triangulate_inspected(
+        &self,
+        polygon: &[Vec<Vec<T>>],
+        inspect: impl FnMut(&[T], &[N], usize, &[N]),
+    ) -> (Vec<T>, Vec<N>) {
+        let (vertices, hole_indices, dim) = flatten::<T, N>(polygon);
+        let mesh = self.run(
+            vertices,
+            &hole_indices,
+            dim,
+            &[],
+            &mut Store::default(),
+            false,
+            inspect,
+        );
+        mesh.expect("unchecked triangulation doesn't fail")
+    }
+
+    /// Checked version of [`Earclip::triangulate`], see [`crate::try_earclip`]
+    pub(crate) fn try_triangulate(
+        &self,
+        polygon: &[Vec<Vec<T>>],
+    ) -> Result<(Vec<T>, Vec<N>), EarcutError> {
+        if polygon.iter().flatten().any(|point| point.len() < 2) {
+            return Err(EarcutError::OddVertexCount);
+        }
+        // checked up front, as flattening already stores hole indices as `N`
+        let vertex_count: usize = polygon.iter().map(Vec::len).sum();
+        if !index_fits::<N>(vertex_count + self.offset.map_or(0, Index::into_usize)) {
+            return Err(EarcutError::IndexOverflow);
+        }
+        let (vertices, hole_indices, dim) = flatten::<T, N>(polygon);
+        validate(&vertices, &hole_indices, dim)?;
+        self.run(
+            vertices,
+            &hole_indices,
+            dim,
+            &[],
+            &mut Store::default(),
+            true,
+            |_, _, _, _| {},
+        )
+    }
+
+    /// Triangulate a polygon so that the mesh also uses the given interior points as vertices,
+    /// see [`crate::earclip_with_steiner`]
+    pub fn triangulate_with_steiner(
+        &self,
+        polygon: &[Vec<Vec<T>>],
+        steiner_points: &[Vec<T>],
+    ) -> (Vec<T>, Vec<N>) {
+        let (vertices, hole_indices, dim) = flatten::<T, N>(polygon);
+        self.triangulate_owned(
+            vertices,
+            &hole_indices,
+            dim,
+            steiner_points,
+            &mut Store::default(),
+        )
+    }
+
+    /// Triangulate a polygon given its outer ring and holes separately
+    pub fn triangulate_with_holes(
+        &self,
+        outer: &[Vec<T>],
+        holes: &[Vec<Vec<T>>],
+    ) -> (Vec<T>, Vec<N>) {
+        let rings = core::iter::once(outer).chain(holes.iter().map(Vec::as_slice));
+        let (vertices, hole_indices, dim) = flatten_rings::<T, N>(rings);
+        self.triangulate_owned(vertices, &hole_indices, dim, &[], &mut Store::default())
+    }
+
+    /// Triangulate an already flattened polygon, see [`crate::earcut`] for the layout of
+    /// `data` and `hole_indices`
+    pub fn triangulate_flat(&self, data: &[T], hole_indices: &[N], dim: usize) -> (Vec<T>, Vec<N>) {
+        self.triangulate_owned(data.to_vec(), hole_indices, dim, &[], &mut Store::default())
+    }
+
+    fn triangulate_owned(
+        &self,
+        vertices: Vec<T>,
+        hole_indices: &[N],
+        dim: usize,
+        steiner_points: &[Vec<T>],
+        store: &mut Store<T>,
+    ) -> (Vec<T>, Vec<N>) {
+        let mesh = self.run(
+            vertices,
+            hole_indices,
+            dim,
+            steiner_points,
+            store,
+            false,
+            |_, _, _, _| {},
+        );
+        mesh.expect("unchecked triangulation doesn't fail")
+    }
+
+    /// the pipeline behind every triangulation; `checked` reports indices that don't fit `N`
+    /// as an error instead of asserting in debug builds, and `inspect` sees the triangles
+    /// before tesselation
+    #[allow(clippy::too_many_arguments)]
+    fn run(
+        &self,
+        mut vertices: Vec<T>,
+        hole_indices: &[N],
+        dim: usize,
+        steiner_points: &[Vec<T>],
+        store: &mut Store<T>,
+        checked: bool,
+        mut inspect: impl FnMut(&[T], &[N], usize, &[N]),
+    ) -> Result<(Vec<T>, Vec<N>), EarcutError> {
+        let fits = |len: usize| {
+            if checked && !index_fits::<N>(len) {
+                return Err(EarcutError::IndexOverflow);
+            }
+            debug_assert_index_fits::<N>(len);
+            Ok(())
+        };
+        let mut cleaned_holes = Vec::new();
+        let hole_indices = if self.remove_spikes {
+            let mut cleaned = Vec::with_capacity(vertices.len());
+            for (r, ring) in split_rings(&vertices, hole_indices, dim).enumerate() {
+                if r > 0 {
+                    cleaned_holes.push(N::from_usize(cleaned.len() / dim));
+                }
+                cleaned.extend(remove_spikes(ring, dim));
+            }
+            vertices = cleaned;
+            &cleaned_holes
+        } else {
+            hole_indices
+        };
+        // every steiner point is a single vertex hole, added after the cleaning so it stays
+        let mut steiner_holes = Vec::new();
+        let hole_indices = if steiner_points.is_empty() {
+            hole_indices
+        } else {
+            steiner_holes.extend_from_slice(hole_indices);
+            for point in steiner_points {
+                steiner_holes.push(N::from_usize(vertices.len() / dim));
+                let len = vertices.len() + dim;
+                vertices.extend(point.iter().take(dim));
+                vertices.resize(len, T::zero());
+            }
+            &steiner_holes
+        };
+        let offset_len = self.offset.map_or(0, Index::into_usize);
+        fits(vertices.len() / dim + offset_len)?;
+        let winding = match self.winding {
+            Winding::AsInput => {
+                let outer_len = hole_indices
+                    .first()
+                    .map_or(vertices.len(), |h| h.into_usize() * dim);
+                let area = signed_area(&vertices, 0, outer_len, dim);
+                // zero area rings have no winding to follow
+                (!area.is_zero()).then_some(if area > T::zero() {
+                    Winding::CounterClockwise
+                } else {
+                    Winding::Clockwise
+                })
+            }
+            winding => Some(winding),
+        };
+        // Use earcut to build standard triangle set
+        let mut indices = Vec::new();
+        earcut_impl(&vertices, hole_indices, dim, &mut indices, store);
+        inspect(&vertices, hole_indices, dim, &indices);
+        // tesselate if necessary
+        if let Some(modulo) = self.modulo {
+            tesselate(&mut vertices, &mut indices, modulo, dim);
+            fits(vertices.len() / dim + offset_len)?;
+        }
+        if let Some(min_area) = self.min_area {
+            filter_slivers(&vertices, &mut indices, min_area, dim);
+        }
+        if let Some(winding) = winding {
+            let counter_clockwise = winding == Winding::CounterClockwise;
+            for triangle in indices.as_chunks_mut::<3>().0 {
+                let area = triangle_area(&vertices, *triangle, dim);
+                if !area.is_zero() && (area > T::zero()) != counter_clockwise {
+                    triangle.swap(1, 2);
+                }
+            }
+        }
+        // update offset
+        if let Some(offset) = self.offset {
+            let offset = offset.into_usize();
+            indices
+                .iter_mut()
+                .for_each(|i| *i = N::from_usize(i.into_usize() + offset));
+        }
+
+        Ok((vertices, indices))
+    }
+}
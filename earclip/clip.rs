@@ -0,0 +1,428 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::float::Float;
+
+use crate::point_in_polygon;
+
+/// Clip a polygon to an axis-aligned rectangle, e.g. a map tile, before triangulating it.
+///
+/// Rings are cut where they cross the rectangle and the pieces inside are joined up along its
+/// edges (Weiler–Atherton), so holes that cross the edge open up into the outer ring and a
+/// polygon that leaves and re-enters the rectangle falls apart into several polygons. Each
+/// polygon is in the nested form [`crate::earclip`] takes: an outer ring followed by its holes.
+///
+/// Only the first two coordinates are clipped. Further coordinates are interpolated where a
+/// ring crosses the rectangle, and the rectangle corners a piece wraps around copy them from the
+/// point before. Output rings are open and keep the winding of the input's outer ring, holes the
+/// opposite one.
+///
+/// ## Parameters
+/// - `polygon`: the outer ring followed by its holes, each a list of points
+/// - `min`: the lower x and y of the rectangle
+/// - `max`: the upper x and y of the rectangle
+///
+/// ## Returns
+/// The clipped polygons, none if the polygon and rectangle don't overlap
+pub fn clip_rect<T: Float>(
+    polygon: &[Vec<Vec<T>>],
+    min: [T; 2],
+    max: [T; 2],
+) -> Vec<Vec<Vec<Vec<T>>>> {
+    let Some(outer) = polygon.first() else {
+        return Vec::new();
+    };
+    // without an outer ring the holes cut from nothing
+    if outer.len() < 3 || !(min[0] < max[0] && min[1] < max[1]) {
+        return Vec::new();
+    }
+    let reversed = ring_area(outer) < T::zero();
+    let rect = Rect { min, max };
+
+    let mut chains = Vec::new();
+    let mut inside_outers = Vec::new();
+    let mut inside_holes = Vec::new();
+    let mut outside_rings = Vec::new();
+    for (r, ring) in polygon.iter().enumerate() {
+        let mut ring: Vec<Vec<T>> = ring.clone();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        let area = ring_area(&ring);
+        if ring.len() < 3 || area.is_zero() {
+            continue;
+        }
+        // wind outer rings counter-clockwise (y-up) and holes clockwise, so the polygon is always
+        // to the left of its edges
+        if (area > T::zero()) != (r == 0) {
+            ring.reverse();
+        }
+        match ring_chains(&ring, &rect) {
+            Some(ring_chains) if ring_chains.is_empty() => outside_rings.push(ring),
+            Some(ring_chains) => chains.extend(ring_chains),
+            None if r == 0 => inside_outers.push(ring),
+            None => inside_holes.push(ring),
+        }
+    }
+
+    let mut outers = join_chains(chains, &rect);
+    outers.append(&mut inside_outers);
+    if outers.is_empty() {
+        // no ring reaches into the rectangle, so it is either all inside the polygon or all out
+        let center = [
+            (min[0] + max[0]) / (T::one() + T::one()),
+            (min[1] + max[1]) / (T::one() + T::one()),
+        ];
+        let crossings = outside_rings
+            .iter()
+            .filter(|ring| point_in_polygon(&flatten_xy(ring), 2, center))
+            .count();
+        if crossings % 2 == 1 {
+            let point = |x: T, y: T| {
+                let mut point = polygon[0][0].clone();
+                point[0] = x;
+                point[1] = y;
+                point
+            };
+            outers.push(vec![
+                point(min[0], min[1]),
+                point(max[0], min[1]),
+                point(max[0], max[1]),
+                point(min[0], max[1]),
+            ]);
+        }
+    }
+
+    let flat_outers: Vec<Vec<T>> = outers.iter().map(|ring| flatten_xy(ring)).collect();
+    let mut polygons: Vec<Vec<Vec<Vec<T>>>> = outers.into_iter().map(|ring| vec![ring]).collect();
+    for hole in inside_holes {
+        let p = [hole[0][0], hole[0][1]];
+        if let Some(k) = flat_outers
+            .iter()
+            .position(|ring| point_in_polygon(ring, 2, p))
+        {
+            polygons[k].push(hole);
+        }
+    }
+    if reversed {
+        polygons
+            .iter_mut()
+            .flatten()
+            .for_each(|ring| ring.reverse());
+    }
+
+    polygons
+}
+
+/// the clipping rectangle
+struct Rect<T> {
+    min: [T; 2],
+    max: [T; 2],
+}
+
+impl<T: Float> Rect<T> {
+    fn contains(&self, p: &[T]) -> bool {
+        p[0] >= self.min[0] && p[0] <= self.max[0] && p[1] >= self.min[1] && p[1] <= self.max[1]
+    }
+
+    /// the distance counter-clockwise along the edge from the lower left corner to the nearest
+    /// point of the edge
+    fn perimeter(&self, p: &[T]) -> T {
+        let [w, h] = [self.max[0] - self.min[0], self.max[1] - self.min[1]];
+        let distances = [
+            p[1] - self.min[1],
+            self.max[0] - p[0],
+            self.max[1] - p[1],
+            p[0] - self.min[0],
+        ];
+        let side = (0..4)
+            .min_by(|&a, &b| {
+                distances[a]
+                    .abs()
+                    .partial_cmp(&distances[b].abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(0);
+        match side {
+            0 => p[0] - self.min[0],
+            1 => w + p[1] - self.min[1],
+            2 => w + h + self.max[0] - p[0],
+            _ => w + h + w + self.max[1] - p[1],
+        }
+    }
+
+    /// the corners counter-clockwise from the lower left, with their perimeter positions
+    fn corners(&self) -> [([T; 2], T); 4] {
+        let [w, h] = [self.max[0] - self.min[0], self.max[1] - self.min[1]];
+        [
+            (self.min, T::zero()),
+            ([self.max[0], self.min[1]], w),
+            (self.max, w + h),
+            ([self.min[0], self.max[1]], w + h + w),
+        ]
+    }
+
+    /// the part of the segment p-q inside the rectangle as parameters along it (Liang–Barsky)
+    fn clip_segment(&self, p: &[T], q: &[T]) -> Option<(T, T)> {
+        let (dx, dy) = (q[0] - p[0], q[1] - p[1]);
+        let (mut t0, mut t1) = (T::zero(), T::one());
+        for (den, num) in [
+            (-dx, p[0] - self.min[0]),
+            (dx, self.max[0] - p[0]),
+            (-dy, p[1] - self.min[1]),
+            (dy, self.max[1] - p[1]),
+        ] {
+            if den.is_zero() {
+                if num < T::zero() {
+                    return None;
+                }
+            } else if den < T::zero() {
+                t0 = t0.max(num / den);
+            } else {
+                t1 = t1.min(num / den);
+            }
+        }
+        (t0 <= t1).then_some((t0, t1))
+    }
+
+    /// the point at `t` along p-q, snapped onto the rectangle
+    fn point_at(&self, p: &[T], q: &[T], t: T) -> Vec<T> {
+        let mut point: Vec<T> = p.iter().zip(q).map(|(&a, &b)| a + (b - a) * t).collect();
+        point[0] = point[0].max(self.min[0]).min(self.max[0]);
+        point[1] = point[1].max(self.min[1]).min(self.max[1]);
+        point
+    }
+}
+
+/// Cut a ring into the stretches inside the rectangle, each running from where it enters to
+/// where it leaves. `None` if the ring is inside the rectangle as a whole.
+fn ring_chains<T: Float>(ring: &[Vec<T>], rect: &Rect<T>) -> Option<Vec<Vec<Vec<T>>>> {
+    let start = ring.iter().position(|p| !rect.contains(p))?;
+    let n = ring.len();
+    let mut chains = Vec::new();
+    let mut chain: Option<Vec<Vec<T>>> = None;
+    for i in start..start + n {
+        let (p, q) = (&ring[i % n], &ring[(i + 1) % n]);
+        let Some((t0, t1)) = rect.clip_segment(p, q) else {
+            continue;
+        };
+        let (p_in, q_in) = (rect.contains(p), rect.contains(q));
+        // a segment only grazing the rectangle from outside adds nothing
+        if !p_in && !q_in && t0 >= t1 {
+            continue;
+        }
+        let current = chain.get_or_insert_with(Vec::new);
+        if !p_in {
+            current.push(rect.point_at(p, q, t0));
+        }
+        if q_in {
+            current.push(q.clone());
+        } else {
+            current.push(rect.point_at(p, q, t1));
+            chains.extend(chain.take());
+        }
+    }
+
+    Some(chains)
+}
+
+/// Join the chains into rings by following the rectangle counter-clockwise from where each
+/// chain leaves it to where the next one enters.
+fn join_chains<T: Float>(chains: Vec<Vec<Vec<T>>>, rect: &Rect<T>) -> Vec<Vec<Vec<T>>> {
+    let [w, h] = [rect.max[0] - rect.min[0], rect.max[1] - rect.min[1]];
+    let length = w + h + w + h;
+    let ahead = |from: T, to: T| {
+        let d = to - from;
+        if d < T::zero() {
+            d + length
+        } else {
+            d
+        }
+    };
+    let entries: Vec<T> = chains.iter().map(|c| rect.perimeter(&c[0])).collect();
+    let mut used = vec![false; chains.len()];
+    let mut rings = Vec::new();
+    for first in 0..chains.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring: Vec<Vec<T>> = Vec::new();
+        let mut c = first;
+        loop {
+            used[c] = true;
+            ring.extend_from_slice(&chains[c]);
+            let exit = rect.perimeter(&chains[c][chains[c].len() - 1]);
+            let next = (0..chains.len())
+                .filter(|&j| !used[j] || j == first)
+                .min_by(|&a, &b| {
+                    ahead(exit, entries[a])
+                        .partial_cmp(&ahead(exit, entries[b]))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap_or(first);
+            let gap = ahead(exit, entries[next]);
+            let mut corners: Vec<([T; 2], T)> = rect
+                .corners()
+                .into_iter()
+                .map(|(corner, at)| (corner, ahead(exit, at)))
+                .filter(|&(_, d)| d > T::zero() && d < gap)
+                .collect();
+            corners.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+            for (corner, _) in corners {
+                let mut point = ring[ring.len() - 1].clone();
+                point[0] = corner[0];
+                point[1] = corner[1];
+                ring.push(point);
+            }
+            if next == first {
+                break;
+            }
+            c = next;
+        }
+        if !ring_area(&ring).is_zero() {
+            rings.push(ring);
+        }
+    }
+
+    rings
+}
+
+/// twice the signed area of a ring, positive if counter-clockwise in y-up coordinates
+fn ring_area<T: Float>(ring: &[Vec<T>]) -> T {
+    let Some(last) = ring.last() else {
+        return T::zero();
+    };
+    let mut prev = last;
+    let mut sum = T::zero();
+    for p in ring {
+        sum = sum + prev[0] * p[1] - p[0] * prev[1];
+        prev = p;
+    }
+    sum
+}
+
+/// the x and y of a ring's points, flattened
+fn flatten_xy<T: Float>(ring: &[Vec<T>]) -> Vec<T> {
+    ring.iter().flat_map(|p| [p[0], p[1]]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{earclip, triangulated_area};
+
+    fn polygon(rings: &[&[[f64; 2]]]) -> Vec<Vec<Vec<f64>>> {
+        rings
+            .iter()
+            .map(|ring| ring.iter().map(|p| p.to_vec()).collect())
+            .collect()
+    }
+
+    fn area(polygons: &[Vec<Vec<Vec<f64>>>]) -> f64 {
+        polygons
+            .iter()
+            .map(|polygon| {
+                let (vertices, indices) = earclip::<f64, usize>(polygon, None, None);
+                triangulated_area(&vertices, &indices, 2)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn l_shape() {
+        let l = polygon(&[&[
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 2.0],
+            [2.0, 2.0],
+            [2.0, 10.0],
+            [0.0, 10.0],
+        ]]);
+        let clipped = clip_rect(&l, [1.0, 1.0], [11.0, 5.0]);
+        assert_eq!(
+            clipped,
+            vec![polygon(&[&[
+                [10.0, 1.0],
+                [10.0, 2.0],
+                [2.0, 2.0],
+                [2.0, 5.0],
+                [1.0, 5.0],
+                [1.0, 1.0],
+            ]])]
+        );
+        assert_eq!(area(&clipped), 12.0);
+
+        // the winding of the input is kept
+        let mut reversed = l.clone();
+        reversed[0].reverse();
+        let clipped = clip_rect(&reversed, [1.0, 1.0], [11.0, 5.0]);
+        assert!(ring_area(&clipped[0][0]) < 0.0);
+        assert_eq!(area(&clipped), 12.0);
+
+        // a rectangle all inside, and one all outside
+        assert_eq!(area(&clip_rect(&l, [0.5, 0.5], [1.5, 9.5])), 9.0);
+        assert!(clip_rect(&l, [3.0, 3.0], [9.0, 9.0]).is_empty());
+    }
+
+    #[test]
+    fn disjoint_pieces() {
+        let u = polygon(&[&[
+            [0.0, 0.0],
+            [6.0, 0.0],
+            [6.0, 6.0],
+            [4.0, 6.0],
+            [4.0, 2.0],
+            [2.0, 2.0],
+            [2.0, 6.0],
+            [0.0, 6.0],
+        ]]);
+        let clipped = clip_rect(&u, [-1.0, 3.0], [7.0, 5.0]);
+        assert_eq!(clipped.len(), 2);
+        assert!(clipped.iter().all(|p| p.len() == 1 && p[0].len() == 4));
+        assert_eq!(area(&clipped), 8.0);
+    }
+
+    #[test]
+    fn holes() {
+        let square = polygon(&[
+            &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            &[[4.0, 4.0], [4.0, 6.0], [6.0, 6.0], [6.0, 4.0]],
+        ]);
+        // the hole stays a hole
+        let clipped = clip_rect(&square, [1.0, 1.0], [9.0, 9.0]);
+        assert_eq!(clipped.len(), 1);
+        assert_eq!(clipped[0].len(), 2);
+        assert_eq!(area(&clipped), 60.0);
+        // the hole is cut open into the outer ring
+        let clipped = clip_rect(&square, [5.0, -5.0], [20.0, 20.0]);
+        assert_eq!(clipped.len(), 1);
+        assert_eq!(clipped[0].len(), 1);
+        assert_eq!(area(&clipped), 48.0);
+        // inside the hole there is nothing, around it the rectangle is covered
+        assert!(clip_rect(&square, [4.5, 4.5], [5.5, 5.5]).is_empty());
+        assert_eq!(area(&clip_rect(&square, [7.0, 1.0], [9.0, 3.0])), 4.0);
+    }
+
+    #[test]
+    fn empty_outer_ring() {
+        let hole: &[[f64; 2]] = &[[2., 2.], [2., 8.], [8., 8.], [8., 2.]];
+        let clipped = clip_rect(&polygon(&[&[], hole]), [0., 0.], [10., 10.]);
+        assert!(clipped.is_empty());
+        let clipped = clip_rect(&polygon(&[&[], hole]), [4., 4.], [6., 6.]);
+        assert!(clipped.is_empty());
+    }
+
+    #[test]
+    fn interpolates_extra_coordinates() {
+        let ramp = vec![vec![
+            vec![0.0, 0.0, 0.0],
+            vec![4.0, 0.0, 4.0],
+            vec![4.0, 4.0, 4.0],
+            vec![0.0, 4.0, 0.0],
+        ]];
+        let clipped = clip_rect(&ramp, [-1.0, -1.0], [2.0, 5.0]);
+        assert!(clipped[0][0].contains(&vec![2.0, 0.0, 2.0]));
+        assert!(clipped[0][0].contains(&vec![2.0, 4.0, 2.0]));
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::{flatten_dim, ring_area, split_rings};
+
+/// A rough estimate of how expensive a polygon is to triangulate, see [`estimate_cost`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cost {
+    /// The number of vertices across all rings
+    pub vertices: usize,
+    /// The number of holes
+    pub holes: usize,
+    /// The number of vertices that are reflex once the holes are bridged into the outer ring
+    pub reflex: usize,
+}
+impl Cost {
+    /// A unitless score to compare polygons by, growing with the vertex count times the
+    /// number of reflex vertices and holes each ear test and hole bridge has to check against
+    pub fn score(&self) -> usize {
+        self.vertices.saturating_mul(1 + self.reflex + self.holes)
+    }
+}
+
+/// Cheaply estimate the work needed to triangulate a polygon without triangulating it.
+///
+/// Convex polygons with few vertices are fast, while many reflex vertices or holes slow the
+/// ear search down. Only the first two coordinates of each point are considered.
+///
+/// ## Parameters
+/// - `polygon`: the outer ring followed by its holes, as passed to [`crate::earclip`]
+pub fn estimate_cost<T: Float>(polygon: &[Vec<Vec<T>>]) -> Cost {
+    let mut cost = Cost {
+        holes: polygon.len().saturating_sub(1),
+        ..Cost::default()
+    };
+    let (data, hole_indices) = flatten_dim::<T, usize>(polygon, 2);
+    for (r, ring) in split_rings(&data, &hole_indices, 2).enumerate() {
+        let area = ring_area(ring, 2);
+        let ring = ring.as_chunks::<2>().0;
+        cost.vertices += ring.len();
+        if ring.len() < 3 || area.is_zero() {
+            continue;
+        }
+        for (i, b) in ring.iter().enumerate() {
+            let a = &ring[(i + ring.len() - 1) % ring.len()];
+            let c = &ring[(i + 1) % ring.len()];
+            let turn = (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0]);
+            if turn.is_zero() {
+                continue;
+            }
+            // holes are walked the other way around once bridged, so their convex corners
+            // turn into reflex vertices of the outer ring
+            let convex = (turn > T::zero()) == (area > T::zero());
+            if convex == (r > 0) {
+                cost.reflex += 1;
+            }
+        }
+    }
+
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn star_costs_more_than_square() {
+        // a square with its edge midpoints, so both polygons have 8 vertices
+        let square = vec![vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![2.0, 0.0],
+            vec![2.0, 1.0],
+            vec![2.0, 2.0],
+            vec![1.0, 2.0],
+            vec![0.0, 2.0],
+            vec![0.0, 1.0],
+        ]];
+        let star = vec![vec![
+            vec![1.0, -2.0],
+            vec![1.2, 0.8],
+            vec![4.0, 1.0],
+            vec![1.2, 1.2],
+            vec![1.0, 4.0],
+            vec![0.8, 1.2],
+            vec![-2.0, 1.0],
+            vec![0.8, 0.8],
+        ]];
+
+        let square = estimate_cost(&square);
+        let star = estimate_cost(&star);
+        assert_eq!(
+            square,
+            Cost {
+                vertices: 8,
+                holes: 0,
+                reflex: 0
+            }
+        );
+        assert_eq!(
+            star,
+            Cost {
+                vertices: 8,
+                holes: 0,
+                reflex: 4
+            }
+        );
+        assert!(star.score() > square.score());
+    }
+
+    #[test]
+    fn hole_corners_are_reflex() {
+        let polygon = vec![
+            vec![
+                vec![0.0, 0.0],
+                vec![10.0, 0.0],
+                vec![10.0, 10.0],
+                vec![0.0, 10.0],
+            ],
+            vec![
+                vec![2.0, 2.0],
+                vec![2.0, 8.0],
+                vec![8.0, 8.0],
+                vec![8.0, 2.0],
+            ],
+        ];
+        assert_eq!(
+            estimate_cost(&polygon),
+            Cost {
+                vertices: 8,
+                holes: 1,
+                reflex: 4
+            }
+        );
+    }
+}
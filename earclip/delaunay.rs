@@ -0,0 +1,116 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::Index;
+
+/// Improve the shape of a triangulation's triangles by flipping edges until every interior
+/// edge is locally Delaunay.
+///
+/// Only edges shared by exactly two consistently wound triangles whose quad is strictly convex
+/// are flipped, so the boundary edges (including hole boundaries) stay intact, no vertices are
+/// added and the covered area doesn't change. Triangles keep their winding. Only the first two
+/// coordinates of each vertex are considered.
+///
+/// Ear clipping leaves slivers where it fans out from a hole bridge, e.g. all around an annulus;
+/// flipping is the way to get well shaped triangles there.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices to update in place
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The number of edges flipped
+pub fn delaunay_flip<T: Float, N: Index>(vertices: &[T], indices: &mut [N], dim: usize) -> usize {
+    let point = |i: usize| [vertices[i * dim], vertices[i * dim + 1]];
+    let triangles = indices.as_chunks_mut::<3>().0;
+    let mut edges: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (triangle[k].into_usize(), triangle[(k + 1) % 3].into_usize());
+            edges.entry(edge(a, b)).or_default().push(t);
+        }
+    }
+
+    let mut stack: Vec<(usize, usize)> = edges.keys().copied().collect();
+    let mut flips = 0;
+    // every flip strictly improves the triangulation, the cap only guards against rounding
+    let max_flips = edges.len() * edges.len().max(16);
+    while let Some(key) = stack.pop() {
+        if flips >= max_flips {
+            break;
+        }
+        let Some(&[t1, t2]) = edges.get(&key).map(Vec::as_slice) else {
+            continue;
+        };
+        // rotate t1 to [a, b, c] and t2 to [b, a, d]
+        let Some([a, b, c]) = rotate_to(triangles[t1], key.0, key.1)
+            .or_else(|| rotate_to(triangles[t1], key.1, key.0))
+        else {
+            continue;
+        };
+        // opposite directions mean both triangles have the same winding
+        let Some([_, _, d]) = rotate_to(triangles[t2], b, a) else {
+            continue;
+        };
+        if c == d {
+            continue;
+        }
+        let [pa, pb, pc, pd] = [point(a), point(b), point(c), point(d)];
+        let orientation = orient(pa, pb, pc);
+        // the quad a, d, b, c has to be strictly convex for the flip to stay inside it
+        let convex = orientation * orient(pa, pb, pd) < T::zero()
+            && orient(pc, pd, pa) * orient(pc, pd, pb) < T::zero();
+        if !convex || in_circle(pa, pb, pc, pd) * orientation.signum() <= T::zero() {
+            continue;
+        }
+
+        triangles[t1] = [a, d, c].map(N::from_usize);
+        triangles[t2] = [d, b, c].map(N::from_usize);
+        edges.remove(&key);
+        edges.insert(edge(c, d), alloc::vec![t1, t2]);
+        for (from, to, moved) in [(t1, t2, edge(b, c)), (t2, t1, edge(a, d))] {
+            if let Some(owners) = edges.get_mut(&moved) {
+                owners
+                    .iter_mut()
+                    .filter(|t| **t == from)
+                    .for_each(|t| *t = to);
+            }
+        }
+        stack.extend([edge(a, d), edge(d, b), edge(b, c), edge(c, a)]);
+        flips += 1;
+    }
+
+    flips
+}
+
+/// the key of an undirected edge
+fn edge(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// rotate a triangle so it starts with the directed edge from `a` to `b`, if it has one
+fn rotate_to<N: Index>(triangle: [N; 3], a: usize, b: usize) -> Option<[usize; 3]> {
+    let t = triangle.map(|i| i.into_usize());
+    (0..3)
+        .map(|k| [t[k], t[(k + 1) % 3], t[(k + 2) % 3]])
+        .find(|r| r[0] == a && r[1] == b)
+}
+
+/// twice the signed area of the triangle a, b, c
+fn orient<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2]) -> T {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// positive if d lies inside the circumcircle of the counter-clockwise triangle a, b, c
+fn in_circle<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2], d: [T; 2]) -> T {
+    let [adx, ady] = [a[0] - d[0], a[1] - d[1]];
+    let [bdx, bdy] = [b[0] - d[0], b[1] - d[1]];
+    let [cdx, cdy] = [c[0] - d[0], c[1] - d[1]];
+    let ad = adx * adx + ady * ady;
+    let bd = bdx * bdx + bdy * bdy;
+    let cd = cdx * cdx + cdy * cdy;
+    adx * (bdy * cd - bd * cdy) - ady * (bdx * cd - bd * cdx) + ad * (bdx * cdy - bdy * cdx)
+}
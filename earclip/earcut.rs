@@ -1,20 +1,2947 @@
-struct Node<T: Float> {
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Deref, DerefMut, Range};
+
+use num_traits::float::Float;
+
+/// Index of a vertex in the output triangle list
+///
+/// The index type caps the number of vertices a polygon (including any vertices added by
+/// tesselation and the output offset) can have: 256 for `u8`, 65,536 for `u16` and
+/// 4,294,967,296 for `u32`. Larger inputs are caught by a debug assertion in [`earcut`] and
+/// [`crate::earclip`], and rejected with [`EarcutError::IndexOverflow`] by the `try_` variants.
+pub trait Index: Copy {
+    /// Convert the index into a usize
+    fn into_usize(self) -> usize;
+    /// Create an index from a usize
+    fn from_usize(v: usize) -> Self;
+}
+
+impl Index for u32 {
+    fn into_usize(self) -> usize {
+        self as usize
+    }
+    fn from_usize(v: usize) -> Self {
+        v as Self
+    }
+}
+impl Index for u16 {
+    fn into_usize(self) -> usize {
+        self as usize
+    }
+    fn from_usize(v: usize) -> Self {
+        v as Self
+    }
+}
+impl Index for u8 {
+    fn into_usize(self) -> usize {
+        self as usize
+    }
+    fn from_usize(v: usize) -> Self {
+        v as Self
+    }
+}
+impl Index for usize {
+    fn into_usize(self) -> usize {
+        self
+    }
+    fn from_usize(v: usize) -> Self {
+        v as Self
+    }
+}
+
+/// Reasons a checked triangulation (`try_earcut`/`try_earclip`) can be rejected.
+///
+/// Payloads are plain indices, so the error never allocates and works without `std`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarcutError {
+    /// The vertex buffer length is not a multiple of `dim` (or `dim < 2`)
+    OddVertexCount,
+    /// A hole index points past the end of the vertex buffer or is out of order, or the index
+    /// type is too small for the number of vertices
+    IndexOverflow,
+    /// A coordinate is NaN or infinite
+    NonFiniteCoordinate,
+    /// A ring has the wrong winding for strict mode; holds the ring index (0 is the outer ring)
+    WrongWinding(usize),
+    /// The polygon is not simple (e.g. self-intersecting); holds a vertex index near the problem
+    NotSimple(usize),
+    /// Tesselation would have grown the mesh past the given number of vertices
+    VertexLimit(usize),
+    /// The caller-provided buffers of [`earcut_into_slice`] are too small
+    OutOfSpace,
+}
+
+impl fmt::Display for EarcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EarcutError::OddVertexCount => {
+                write!(f, "vertex buffer length is not a multiple of the dimension")
+            }
+            EarcutError::IndexOverflow => {
+                write!(
+                    f,
+                    "hole index is out of bounds or the index type is too small"
+                )
+            }
+            EarcutError::NonFiniteCoordinate => write!(f, "coordinate is NaN or infinite"),
+            EarcutError::WrongWinding(ring) => write!(f, "ring {ring} has the wrong winding"),
+            EarcutError::NotSimple(i) => write!(f, "polygon is not simple near vertex {i}"),
+            EarcutError::VertexLimit(max) => {
+                write!(f, "tesselation would exceed {max} vertices")
+            }
+            EarcutError::OutOfSpace => write!(f, "the node or triangle buffer is too small"),
+        }
+    }
+}
+
+impl core::error::Error for EarcutError {}
+
+/// access a node by index; out of bounds access is a bug in the linked list bookkeeping
+macro_rules! node {
+    ($nodes:expr, $index:expr) => {{
+        let index: usize = $index;
+        &$nodes[index]
+    }};
+}
+
+/// mutably access a node by index; out of bounds access is a bug in the linked list bookkeeping
+macro_rules! node_mut {
+    ($nodes:expr, $index:expr) => {{
+        let index: usize = $index;
+        &mut $nodes[index]
+    }};
+}
+
+/// Nodes help form a LinkedList and track information about the point itself and its neighbours
+///
+/// Public so callers of [`earcut_into_slice`] can provide the memory for them, e.g.
+/// `[Node::default(); 64]`, and so tools can follow the triangulation with [`earcut_observed`]
+/// or [`Store::nodes`]. The fields can only be read; node links are indices into the same
+/// node slice.
+#[derive(Debug, Clone, Copy)]
+pub struct Node<T: Float> {
     /// vertex index in coordinates array
-    i: u32,
-    /// vertex coordinate x
-    x: T,
-    /// vertex coordinate y
-    y: T,
+    i: usize,
     /// z-order curve value
-    z: i32,
-    /// previous vertex nodes in a polygon ring
-    prev: RefCell<Node>,
-    /// next vertex nodes in a polygon ring
-    next: RefCell<Node>,
-    /// previous nodes in z-order
-    prev_z: RefCell<Node>,
-    /// next nodes in z-order
-    next_z: RefCell<Node>,
+    z: i64,
+    /// vertex coordinates x and y
+    xy: [T; 2],
+    /// previous vertex node in a polygon ring
+    prev_i: usize,
+    /// next vertex node in a polygon ring
+    next_i: usize,
+    /// previous node in z-order
+    prev_z_i: Option<usize>,
+    /// next node in z-order
+    next_z_i: Option<usize>,
     /// indicates whether this is a steiner point
     steiner: bool,
+    /// an input vertex only filtered out once ears alone can't finish the ring
+    keep: bool,
+}
+
+impl<T: Float> Default for Node<T> {
+    fn default() -> Self {
+        Self::new(0, [T::zero(); 2], 0)
+    }
+}
+
+impl<T: Float> Node<T> {
+    /// create a node linked only to itself, stored at position `self_i`
+    fn new(i: usize, xy: [T; 2], self_i: usize) -> Self {
+        Self {
+            i,
+            z: 0,
+            xy,
+            prev_i: self_i,
+            next_i: self_i,
+            prev_z_i: None,
+            next_z_i: None,
+            steiner: false,
+            keep: false,
+        }
+    }
+
+    /// The index of the input vertex this node stands for
+    pub fn index(&self) -> usize {
+        self.i
+    }
+
+    /// The vertex coordinates x and y
+    pub fn xy(&self) -> [T; 2] {
+        self.xy
+    }
+
+    /// The z-order curve value, 0 unless the polygon is large enough to be indexed
+    pub fn z(&self) -> i64 {
+        self.z
+    }
+
+    /// The previous node in its ring
+    pub fn prev(&self) -> usize {
+        self.prev_i
+    }
+
+    /// The next node in its ring
+    pub fn next(&self) -> usize {
+        self.next_i
+    }
+
+    /// The previous node in z-order, if indexed
+    pub fn prev_z(&self) -> Option<usize> {
+        self.prev_z_i
+    }
+
+    /// The next node in z-order, if indexed
+    pub fn next_z(&self) -> Option<usize> {
+        self.next_z_i
+    }
+
+    /// Whether this is a steiner point, which is never filtered out
+    pub fn is_steiner(&self) -> bool {
+        self.steiner
+    }
+}
+
+/// where the linked list nodes live: a growable `Vec`, or a fixed slice for
+/// [`earcut_into_slice`]
+trait NodeArena<T: Float>: DerefMut<Target = [Node<T>]> {
+    /// append a node; a fixed arena must have been checked with [`NodeArena::make_room`]
+    fn push(&mut self, node: Node<T>);
+
+    /// whether `count` more nodes fit; a fixed arena remembers when they don't
+    fn make_room(&mut self, count: usize) -> bool;
+
+    /// called once a triangle is cut off the ring `ring_i` is still part of
+    fn ear_cut(&mut self, _ring_i: usize) {}
+}
+
+impl<T: Float> NodeArena<T> for Vec<Node<T>> {
+    fn push(&mut self, node: Node<T>) {
+        Vec::push(self, node);
+    }
+
+    fn make_room(&mut self, _count: usize) -> bool {
+        true
+    }
+}
+
+/// the nodes of a [`Store`], showing each ear cut to a callback
+struct ObservedArena<'a, T: Float, F> {
+    nodes: &'a mut Vec<Node<T>>,
+    observe: F,
+}
+
+impl<T: Float, F> Deref for ObservedArena<'_, T, F> {
+    type Target = [Node<T>];
+
+    fn deref(&self) -> &[Node<T>] {
+        self.nodes
+    }
+}
+
+impl<T: Float, F> DerefMut for ObservedArena<'_, T, F> {
+    fn deref_mut(&mut self) -> &mut [Node<T>] {
+        self.nodes
+    }
+}
+
+impl<T: Float, F: FnMut(&[Node<T>], usize)> NodeArena<T> for ObservedArena<'_, T, F> {
+    fn push(&mut self, node: Node<T>) {
+        self.nodes.push(node);
+    }
+
+    fn make_room(&mut self, _count: usize) -> bool {
+        true
+    }
+
+    fn ear_cut(&mut self, ring_i: usize) {
+        (self.observe)(self.nodes, ring_i);
+    }
+}
+
+/// a fixed slice of nodes, filled from the front
+struct SliceArena<'a, T: Float> {
+    nodes: &'a mut [Node<T>],
+    len: usize,
+    full: bool,
+}
+
+impl<T: Float> Deref for SliceArena<'_, T> {
+    type Target = [Node<T>];
+
+    fn deref(&self) -> &[Node<T>] {
+        &self.nodes[..self.len]
+    }
+}
+
+impl<T: Float> DerefMut for SliceArena<'_, T> {
+    fn deref_mut(&mut self) -> &mut [Node<T>] {
+        &mut self.nodes[..self.len]
+    }
+}
+
+impl<T: Float> NodeArena<T> for SliceArena<'_, T> {
+    fn push(&mut self, node: Node<T>) {
+        assert!(self.len < self.nodes.len(), "node arena is full");
+        self.nodes[self.len] = node;
+        self.len += 1;
+    }
+
+    fn make_room(&mut self, count: usize) -> bool {
+        let fits = self.len + count <= self.nodes.len();
+        self.full |= !fits;
+        fits
+    }
+}
+
+/// where the triangle indices go: a growable `Vec`, or a fixed slice for [`earcut_into_slice`]
+trait TriangleSink<N> {
+    /// append a vertex index
+    fn push(&mut self, index: N);
+
+    /// the number of indices pushed so far
+    fn len(&self) -> usize;
+}
+
+impl<N> TriangleSink<N> for Vec<N> {
+    fn push(&mut self, index: N) {
+        Vec::push(self, index);
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+/// a fixed slice of indices, filled from the front; indices past its end are counted but dropped
+struct SliceSink<'a, N> {
+    indices: &'a mut [N],
+    len: usize,
+}
+
+impl<N> TriangleSink<N> for SliceSink<'_, N> {
+    fn push(&mut self, index: N) {
+        if let Some(slot) = self.indices.get_mut(self.len) {
+            *slot = index;
+        }
+        self.len += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// where the halves of split rings wait to be triangulated, instead of on the call stack
+trait SplitStack {
+    /// add a waiting ring; false if there is no room for it
+    fn push(&mut self, ring_i: usize) -> bool;
+
+    /// take the ring that started waiting last
+    fn pop(&mut self) -> Option<usize>;
+}
+
+/// a split stack growing up to `limit` rings
+struct VecStack<'a> {
+    items: &'a mut Vec<usize>,
+    limit: usize,
+}
+
+impl SplitStack for VecStack<'_> {
+    fn push(&mut self, ring_i: usize) -> bool {
+        if self.items.len() >= self.limit {
+            return false;
+        }
+        self.items.push(ring_i);
+        true
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.items.pop()
+    }
+}
+
+/// how many split halves [`earcut_into_slice`] can keep waiting
+const SLICE_SPLIT_DEPTH: usize = 32;
+
+/// a split stack of fixed size, for [`earcut_into_slice`]
+struct ArrayStack {
+    items: [usize; SLICE_SPLIT_DEPTH],
+    len: usize,
+}
+
+impl SplitStack for ArrayStack {
+    fn push(&mut self, ring_i: usize) -> bool {
+        if self.len == SLICE_SPLIT_DEPTH {
+            return false;
+        }
+        self.items[self.len] = ring_i;
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.len = self.len.checked_sub(1)?;
+        Some(self.items[self.len])
+    }
+}
+
+/// How many triangles each pass of the triangulation cut, as returned by [`earcut_impl`].
+///
+/// A clean polygon is cut entirely in the first pass. The later passes only run on what is left
+/// when no more ears can be found, which usually means duplicate points or self-intersections
+/// in the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassStats {
+    /// Triangles cut from the ring as given
+    pub p0: usize,
+    /// Triangles cut after filtering out duplicate and collinear points
+    pub p1: usize,
+    /// Triangles cut while curing small local self-intersections and after it
+    pub p2: usize,
+    /// How often the rest of the ring was split in two along a diagonal, the last resort. Both
+    /// halves start over at the first pass and add to its count.
+    pub split_count: usize,
+    /// Whether a half of a split was left out because [`Store::with_split_depth`] halves were
+    /// already waiting, so the triangles are incomplete
+    pub split_limit_hit: bool,
+    /// How many points were tested for lying inside a candidate ear. The z-order index skips
+    /// far away points, and the more bits [`Store::with_morton_bits`] gives it, the fewer remain.
+    pub points_tested: usize,
+}
+
+/// the bits per axis of the z-order codes, unless [`Store::with_morton_bits`] says otherwise
+const DEFAULT_MORTON_BITS: u32 = 15;
+
+/// Reusable working memory for the triangulation
+#[derive(Debug, Clone)]
+pub struct Store<T: Float> {
+    nodes: Vec<Node<T>>,
+    /// the leftmost node of each hole, to bridge them in order
+    queue: Vec<usize>,
+    /// a vertex near the first sign that the polygon is not simple
+    issue: Option<usize>,
+    /// the triangles each pass cut in the last triangulation
+    stats: PassStats,
+    /// the bits per axis of the z-order codes
+    morton_bits: u32,
+    /// how many vertices to compare when picking an ear, 1 to cut the first valid one
+    ear_window: usize,
+    /// the halves of split rings waiting to be triangulated
+    splits: Vec<usize>,
+    /// how many halves may wait at once
+    split_depth: usize,
+    /// whether the input vertices are kept even where they are collinear
+    preserve_boundary_vertices: bool,
+}
+
+impl<T: Float> Default for Store<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Store<T> {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            queue: Vec::new(),
+            issue: None,
+            stats: PassStats::default(),
+            morton_bits: DEFAULT_MORTON_BITS,
+            ear_window: 1,
+            splits: Vec::new(),
+            split_depth: usize::MAX,
+            preserve_boundary_vertices: false,
+        }
+    }
+
+    /// Create a store with room for `capacity` nodes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+            queue: Vec::new(),
+            issue: None,
+            stats: PassStats::default(),
+            morton_bits: DEFAULT_MORTON_BITS,
+            ear_window: 1,
+            splits: Vec::new(),
+            split_depth: usize::MAX,
+            preserve_boundary_vertices: false,
+        }
+    }
+
+    /// Quantize coordinates to `bits` (clamped to `1..=21`) per axis for the z-order index
+    /// large polygons are sorted by; the default is 15, as in [`morton_code`].
+    ///
+    /// The index lets the ear search skip points far from each ear, but points closer together
+    /// than `1 / 2^bits` of the bounding box share a cell and can't be told apart. For data
+    /// spread over a huge range with dense detail in places, e.g. web-mercator meters, more bits
+    /// keep the detail in separate cells. Fewer bits only save the few more shifts the wider
+    /// codes take. The triangles are the same either way.
+    pub fn with_morton_bits(mut self, bits: u32) -> Self {
+        self.morton_bits = bits.clamp(1, 21);
+        self
+    }
+
+    /// Among the valid ears in a window of up to `window` vertices, cut the one whose triangle
+    /// has the largest smallest angle instead of the first one found; `0` and `1` keep the
+    /// default.
+    ///
+    /// The default tends to leave long slivers on finely detailed rings, which render and
+    /// interpolate poorly. Each cut then tests up to `window` ears instead of one, so the
+    /// triangulation takes roughly that many times as long; a window of 8 or so already avoids
+    /// most slivers. Rings without holes that are convex no longer take the fan shortcut.
+    ///
+    /// Only simple polygons are sure to be cut as well as by default; the passes that clean up
+    /// self-intersections start from different leftovers and may cut them differently.
+    pub fn with_best_ears(mut self, window: usize) -> Self {
+        self.ear_window = window.max(1);
+        self
+    }
+
+    /// Cap how many halves of split rings may wait to be triangulated at once; unlimited by
+    /// default.
+    ///
+    /// When no ears are left, the last pass splits the rest of a bad ring in two along a
+    /// diagonal and triangulates one half while the other waits. On pathological
+    /// self-intersecting input, halves split again and again can pile up. They wait in a list
+    /// rather than on the call stack, so even then the stack stays small, but the list grows.
+    /// With a cap, halves that don't fit are left out and [`PassStats::split_limit_hit`] is set;
+    /// the triangles cut so far are kept.
+    pub fn with_split_depth(mut self, depth: usize) -> Self {
+        self.split_depth = depth;
+        self
+    }
+
+    /// Keep every vertex of the input rings in the triangles, even those on a straight line
+    /// between their neighbours, e.g. so the edges two adjacent tiles share are cut at exactly the
+    /// same vertices on both sides.
+    ///
+    /// By default collinear vertices are dropped whenever the triangulation cleans up a ring,
+    /// which it does around hole bridges and before its later passes. With this they are only
+    /// cut around, like the single point holes of [`earcut_with_steiner`]; consecutive duplicate
+    /// points and the copies made to bridge holes can still go. Self-intersecting rings that
+    /// need the pass curing local intersections may still lose some, and may end up covered a
+    /// little less exactly than without this.
+    pub fn with_preserve_boundary_vertices(mut self, preserve: bool) -> Self {
+        self.preserve_boundary_vertices = preserve;
+        self
+    }
+
+    /// The nodes of the last triangulation, as they were left at its end, e.g. to inspect the
+    /// rings that were left over; see [`earcut_observed`] to follow them along the way
+    pub fn nodes(&self) -> &[Node<T>] {
+        &self.nodes
+    }
+
+    /// clear the store while keeping (and growing) its allocations
+    fn reset(&mut self, capacity: usize) {
+        self.nodes.clear();
+        self.nodes.reserve(capacity);
+        self.queue.clear();
+        self.splits.clear();
+        self.issue = None;
+        self.stats = PassStats::default();
+    }
+}
+
+/// Triangulate a flattened polygon.
+///
+/// ## Parameters
+/// - `data`: the polygon vertices flattened, e.g. `[x0, y0, x1, y1, ...]`
+/// - `hole_indices`: the vertex index each hole ring starts at
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The vertex indices of the triangles, three per triangle. Indices always refer to vertices of
+/// `data`; no vertices are ever created, including for steiner points and hole bridges.
+///
+/// The input is not validated; NaN or infinite coordinates give meaningless triangles. See
+/// [`validate_finite`] and [`try_earcut`] for checked input.
+pub fn earcut<T: Float, N: Index>(data: &[T], hole_indices: &[N], dim: usize) -> Vec<N> {
+    let mut triangles = Vec::new();
+    let mut store = Store::default();
+    earcut_impl(data, hole_indices, dim, &mut triangles, &mut store);
+    triangles
+}
+
+/// Triangulate a flattened polygon, returning each triangle as an array of its three vertex
+/// indices.
+///
+/// This is a thin wrapper over [`earcut`]; see it for the meaning of the parameters.
+pub fn earcut_triangles<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+) -> Vec<[N; 3]> {
+    let triangles = earcut(data, hole_indices, dim);
+    // earcut only ever pushes whole triangles
+    debug_assert!(triangles.len().is_multiple_of(3));
+    triangles.as_chunks::<3>().0.to_vec()
+}
+
+/// Triangulate a flattened polygon and iterate over its triangles as arrays of their three
+/// vertex indices.
+///
+/// The polygon is triangulated up front, but the triangles are handed out one at a time
+/// without collecting them into another buffer. See [`earcut`] for the meaning of the
+/// parameters.
+pub fn triangulate_iter<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+) -> TriangleIter<N> {
+    TriangleIter {
+        indices: earcut(data, hole_indices, dim).into_iter(),
+    }
+}
+
+/// The triangles of a triangulation, see [`triangulate_iter`]
+#[derive(Debug, Clone)]
+pub struct TriangleIter<N: Index> {
+    indices: alloc::vec::IntoIter<N>,
+}
+
+impl<N: Index> Iterator for TriangleIter<N> {
+    type Item = [N; 3];
+
+    fn next(&mut self) -> Option<[N; 3]> {
+        Some([
+            self.indices.next()?,
+            self.indices.next()?,
+            self.indices.next()?,
+        ])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.indices.len() / 3;
+        (len, Some(len))
+    }
+}
+
+impl<N: Index> ExactSizeIterator for TriangleIter<N> {}
+
+/// Triangulate a flattened polygon that ends before the end of `data`, e.g. a ring at the start
+/// of a shared coordinate buffer.
+///
+/// The polygon takes up the vertices before `end`: without holes `end` is the number of
+/// vertices of the outer ring, with holes the outer ring still ends at the first hole and the
+/// last hole ends at `end`. Anything after it is ignored. See [`earcut`] for the other
+/// parameters.
+///
+/// ## Panics
+/// If `end` reaches past the end of `data`.
+pub fn earcut_range<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    end: usize,
+) -> Vec<N> {
+    earcut(&data[..end * dim], hole_indices, dim)
+}
+
+/// Triangulate a flattened polygon whose bounding box is already known, e.g. from a spatial
+/// index, skipping the pass over the vertices that would compute it.
+///
+/// The box only has to contain the outer ring, holes lie inside it anyway. A box larger than
+/// needed is fine but makes the z-order hash coarser; one that doesn't contain every vertex
+/// gives wrong triangles, which is checked in debug builds. See [`earcut`] for the other
+/// parameters.
+///
+/// ## Parameters
+/// - `bbox`: the bounding box of the polygon, `[min_x, min_y, max_x, max_y]`
+pub fn earcut_with_bbox<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    bbox: [T; 4],
+) -> Vec<N> {
+    let mut triangles = Vec::new();
+    let mut store = Store::default();
+    earcut_bounded(
+        data,
+        hole_indices,
+        dim,
+        Some(bbox),
+        &mut triangles,
+        &mut store,
+        |_, _| {},
+    );
+    triangles
+}
+
+/// Triangulate a polygon whose rings are given as explicit vertex ranges into `data`.
+///
+/// Unlike [`earcut`]'s cumulative hole offsets, the rings may sit anywhere in the buffer, in
+/// any order and with unused vertices in between.
+///
+/// ## Parameters
+/// - `data`: the flattened vertex buffer, e.g. `[x0, y0, x1, y1, ...]`
+/// - `ring_ranges`: the vertex index range of each ring; the first is the outer ring and the
+///   rest are holes
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The vertex indices of the triangles, three per triangle, referring to vertices of `data`.
+///
+/// ## Panics
+/// If a range reaches past the end of `data`.
+pub fn earcut_ranges<T: Float, N: Index>(
+    data: &[T],
+    ring_ranges: &[Range<usize>],
+    dim: usize,
+) -> Vec<N> {
+    let Some(outer) = ring_ranges.first() else {
+        return Vec::new();
+    };
+    let contiguous = outer.start == 0 && ring_ranges.windows(2).all(|w| w[0].end == w[1].start);
+    if contiguous {
+        // already laid out the way earcut expects, so just derive the hole offsets
+        let end = ring_ranges[ring_ranges.len() - 1].end;
+        let holes: Vec<N> = ring_ranges[1..]
+            .iter()
+            .map(|r| N::from_usize(r.start))
+            .collect();
+        return earcut(&data[..end * dim], &holes, dim);
+    }
+
+    let mut vertices = Vec::new();
+    let mut holes = Vec::with_capacity(ring_ranges.len() - 1);
+    let mut origin = Vec::new();
+    for (r, range) in ring_ranges.iter().enumerate() {
+        if r > 0 {
+            holes.push(origin.len());
+        }
+        vertices.extend_from_slice(&data[range.start * dim..range.end * dim]);
+        origin.extend(range.clone());
+    }
+    let triangles: Vec<usize> = earcut(&vertices, &holes, dim);
+    triangles
+        .into_iter()
+        .map(|i| N::from_usize(origin[i]))
+        .collect()
+}
+
+/// Triangulate a flattened polygon so that the mesh also uses the given interior points.
+///
+/// Each steiner point becomes a vertex of the triangles around it, e.g. to get more evenly sized
+/// triangles. The points must lie inside the polygon and outside its holes; points anywhere else
+/// give overlapping triangles.
+///
+/// ## Parameters
+/// - `data`: the polygon vertices flattened, e.g. `[x0, y0, x1, y1, ...]`
+/// - `hole_indices`: the vertex index each hole ring starts at
+/// - `steiner_points`: the interior points to add
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The vertex indices of the triangles, three per triangle. Indices below `data.len() / dim`
+/// refer to `data`, index `data.len() / dim + k` refers to `steiner_points[k]`.
+pub fn earcut_with_steiner<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    steiner_points: &[[T; 2]],
+    dim: usize,
+) -> Vec<N> {
+    if steiner_points.is_empty() {
+        return earcut(data, hole_indices, dim);
+    }
+    // every steiner point is a single vertex hole, the extra coordinates of which are zero
+    let mut vertices = Vec::with_capacity(data.len() + steiner_points.len() * dim);
+    vertices.extend_from_slice(data);
+    let mut holes = Vec::with_capacity(hole_indices.len() + steiner_points.len());
+    holes.extend_from_slice(hole_indices);
+    for point in steiner_points {
+        holes.push(N::from_usize(vertices.len() / dim));
+        vertices.extend_from_slice(point);
+        vertices.resize(vertices.len() + dim - 2, T::zero());
+    }
+    earcut(&vertices, &holes, dim)
+}
+
+/// Checked version of [`earcut`] that validates the input instead of panicking on it.
+///
+/// Rejects vertex buffers whose length is not a multiple of `dim`, hole indices that are
+/// out of bounds or out of order, and NaN/infinite coordinates.
+pub fn try_earcut<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+) -> Result<Vec<N>, EarcutError> {
+    validate(data, hole_indices, dim)?;
+    Ok(earcut(data, hole_indices, dim))
+}
+
+/// Strict version of [`try_earcut`] that either yields a correct mesh or explains why not.
+///
+/// On top of [`try_earcut`]'s checks, the outer ring must be counter-clockwise and the holes
+/// clockwise in y-up coordinates (positive and negative [`signed_area`] respectively), and any
+/// self-intersection the algorithm runs into while bridging holes or cutting ears is reported
+/// as [`EarcutError::NotSimple`] instead of producing overlapping triangles.
+///
+/// Not every non-simple polygon is caught: intersections the ear slicing never has to work
+/// around go unnoticed.
+pub fn try_earcut_strict<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+) -> Result<Vec<N>, EarcutError> {
+    validate(data, hole_indices, dim)?;
+    let len = data.len() / dim;
+    let starts = core::iter::once(0).chain(hole_indices.iter().map(|i| i.into_usize()));
+    let ends = hole_indices
+        .iter()
+        .map(|i| i.into_usize())
+        .chain(core::iter::once(len));
+    for (ring, (start, end)) in starts.zip(ends).enumerate() {
+        let area = signed_area(data, start * dim, end * dim, dim);
+        let wound_right = if ring == 0 {
+            area > T::zero()
+        } else {
+            area < T::zero()
+        };
+        if !wound_right {
+            return Err(EarcutError::WrongWinding(ring));
+        }
+    }
+
+    let mut triangles = Vec::new();
+    let mut store = Store::default();
+    earcut_impl(data, hole_indices, dim, &mut triangles, &mut store);
+    match store.issue {
+        Some(i) => Err(EarcutError::NotSimple(i)),
+        None => Ok(triangles),
+    }
+}
+
+/// the checks shared by [`try_earcut`] and [`try_earcut_strict`]
+pub(crate) fn validate<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+) -> Result<(), EarcutError> {
+    if dim < 2 || !data.len().is_multiple_of(dim) {
+        return Err(EarcutError::OddVertexCount);
+    }
+    let vertex_count = data.len() / dim;
+    if !index_fits::<N>(vertex_count) {
+        return Err(EarcutError::IndexOverflow);
+    }
+    let mut prev_hole = 0;
+    for hole in hole_indices {
+        let hole = hole.into_usize();
+        if hole > vertex_count || hole < prev_hole {
+            return Err(EarcutError::IndexOverflow);
+        }
+        prev_hole = hole;
+    }
+    validate_finite(data).map_err(|_| EarcutError::NonFiniteCoordinate)
+}
+
+/// whether every vertex index below `len` can be represented by `N`
+pub(crate) fn index_fits<N: Index>(len: usize) -> bool {
+    len.checked_sub(1)
+        .is_none_or(|max| N::from_usize(max).into_usize() == max)
+}
+
+/// in debug builds, check that every vertex index below `len` can be represented by `N`
+pub(crate) fn debug_assert_index_fits<N: Index>(len: usize) {
+    debug_assert!(
+        index_fits::<N>(len),
+        "index type is too small for {len} vertices"
+    );
+}
+
+/// Check that every coordinate is finite.
+///
+/// A cheap pre-check for [`earcut`], which doesn't validate its input for performance: NaN
+/// compares false against everything and silently produces wrong triangles. [`try_earcut`] runs
+/// this check for you.
+///
+/// ## Returns
+/// The index into `data` of the first NaN or infinite coordinate
+pub fn validate_finite<T: Float>(data: &[T]) -> Result<(), usize> {
+    match data.iter().position(|v| !v.is_finite()) {
+        Some(i) => Err(i),
+        None => Ok(()),
+    }
+}
+
+/// Triangulate a flattened polygon into `triangles_out`, using `store` as working memory.
+///
+/// The new triangles are **appended** to `triangles_out`; existing contents are kept as is and
+/// the new indices are not offset by them. Use [`earcut_into`] to reuse the buffer's capacity
+/// for a fresh result instead.
+///
+/// See [`earcut`] for the meaning of the parameters.
+///
+/// ## Returns
+/// How many triangles each pass cut, e.g. to find out why a triangulation looks wrong
+pub fn earcut_impl<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    triangles_out: &mut Vec<N>,
+    store: &mut Store<T>,
+) -> PassStats {
+    earcut_bounded(
+        data,
+        hole_indices,
+        dim,
+        None,
+        triangles_out,
+        store,
+        |_, _| {},
+    );
+    store.stats
+}
+
+/// [`earcut_impl`] calling `observe` after every triangle it cuts, e.g. to step through the
+/// triangulation in a debugger or visualizer.
+///
+/// `observe` gets all nodes of the store and the index of a node on the ring the triangle was
+/// cut from; follow [`Node::next`] from there to walk what is left of it. Nodes cut off stay in
+/// the slice but are no longer linked into any ring. Hole bridges and split rings add nodes on
+/// the way, so compare rings rather than slice lengths.
+///
+/// ## Returns
+/// How many triangles each pass cut, see [`earcut_impl`]
+pub fn earcut_observed<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    triangles_out: &mut Vec<N>,
+    store: &mut Store<T>,
+    observe: impl FnMut(&[Node<T>], usize),
+) -> PassStats {
+    earcut_bounded(data, hole_indices, dim, None, triangles_out, store, observe);
+    store.stats
+}
+
+/// [`earcut_impl`] with an optional known bounding box of the outer ring,
+/// `[min_x, min_y, max_x, max_y]`, that replaces the scan for it, and an observer of the ears
+/// cut, see [`earcut_observed`]
+fn earcut_bounded<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    bbox: Option<[T; 4]>,
+    triangles_out: &mut Vec<N>,
+    store: &mut Store<T>,
+    observe: impl FnMut(&[Node<T>], usize),
+) {
+    debug_assert_index_fits::<N>(data.len() / dim);
+    // every hole bridge duplicates two vertices
+    let max_nodes = data.len() / dim + 2 * hole_indices.len();
+    triangles_out.reserve(max_nodes.saturating_sub(2) * 3);
+    store.reset(max_nodes);
+    store.queue.resize(hole_indices.len(), 0);
+
+    let Some(outer_node_i) = link_polygon(
+        &mut store.nodes,
+        data,
+        hole_indices,
+        dim,
+        store.preserve_boundary_vertices,
+        &mut store.queue,
+        &mut store.issue,
+    ) else {
+        return;
+    };
+    // if the shape is not too simple, we'll use z-order curve hash later
+    let outer_len = hole_indices
+        .first()
+        .map_or(data.len(), |h| h.into_usize() * dim);
+    let indexed = (data.len() > 80 * dim).then(|| &data[..outer_len]);
+    cut_polygon(
+        &mut ObservedArena {
+            nodes: &mut store.nodes,
+            observe,
+        },
+        indexed,
+        dim,
+        bbox,
+        outer_node_i,
+        !hole_indices.is_empty(),
+        triangles_out,
+        store.morton_bits,
+        store.ear_window,
+        &mut VecStack {
+            items: &mut store.splits,
+            limit: store.split_depth,
+        },
+        &mut store.issue,
+        &mut store.stats,
+    );
+}
+
+/// build the linked list of a polygon with its holes bridged in; `None` if there is nothing to
+/// triangulate
+fn link_polygon<T: Float, N: Index, Q: Index, A: NodeArena<T>>(
+    nodes: &mut A,
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    keep: bool,
+    queue: &mut [Q],
+    issue: &mut Option<usize>,
+) -> Option<usize> {
+    if data.is_empty() {
+        return None;
+    }
+
+    let has_holes = !hole_indices.is_empty();
+    let outer_len = if has_holes {
+        hole_indices[0].into_usize() * dim
+    } else {
+        data.len()
+    };
+
+    // a zero area ring (such as a collinear one) has nothing to triangulate
+    if !has_holes && signed_area(data, 0, outer_len, dim).is_zero() {
+        return None;
+    }
+
+    let outer_node_i = linked_list(nodes, data, 0, outer_len, dim, true, keep)?;
+    let outer_node = node!(nodes, outer_node_i);
+    if outer_node.next_i == outer_node.prev_i {
+        return None;
+    }
+
+    if has_holes {
+        Some(eliminate_holes(
+            nodes,
+            data,
+            hole_indices,
+            outer_node_i,
+            dim,
+            keep,
+            queue,
+            issue,
+        ))
+    } else {
+        Some(outer_node_i)
+    }
+}
+
+/// cut a linked polygon into triangles; `indexed` is the outer ring of the input if the
+/// polygon is large enough to index its nodes along the z-order curve
+#[allow(clippy::too_many_arguments)]
+fn cut_polygon<T: Float, N: Index, A: NodeArena<T>, S: TriangleSink<N>, W: SplitStack>(
+    nodes: &mut A,
+    indexed: Option<&[T]>,
+    dim: usize,
+    bbox: Option<[T; 4]>,
+    outer_node_i: usize,
+    has_holes: bool,
+    triangles_out: &mut S,
+    morton_bits: u32,
+    ear_window: usize,
+    splits: &mut W,
+    issue: &mut Option<usize>,
+    stats: &mut PassStats,
+) {
+    let mut outer_node_i = outer_node_i;
+    if !has_holes && ear_window == 1 && is_convex(nodes, outer_node_i) {
+        // every vertex of a convex ring is an ear, so skip the search for them
+        let start = triangles_out.len();
+        let ear_i = clip_convex(nodes, outer_node_i, triangles_out);
+        stats.p0 += (triangles_out.len() - start) / 3;
+        match ear_i {
+            Some(ear_i) => outer_node_i = ear_i,
+            None => return,
+        }
+    }
+
+    let mut min_x = T::zero();
+    let mut min_y = T::zero();
+    let mut z_scale = T::zero();
+
+    // take or calculate the polygon bbox for the z-order curve hash
+    if let Some(outer) = indexed {
+        let [x0, y0, max_x, max_y] = match bbox {
+            Some(bbox) => {
+                debug_assert!(
+                    outer.chunks_exact(dim).all(|xy| xy[0] >= bbox[0]
+                        && xy[1] >= bbox[1]
+                        && xy[0] <= bbox[2]
+                        && xy[1] <= bbox[3]),
+                    "the bounding box does not contain the outer ring"
+                );
+                bbox
+            }
+            None => ring_bbox(outer, dim),
+        };
+        min_x = x0;
+        min_y = y0;
+
+        // min_x, min_y and z_scale are later used to transform coords into integers for z-order calculation
+        let cells = T::from((1_u32 << morton_bits) - 1).unwrap_or_else(T::one);
+        z_scale = cells * morton_inv_size([min_x, min_y], [max_x, max_y]);
+    }
+
+    earcut_linked(
+        nodes,
+        outer_node_i,
+        triangles_out,
+        min_x,
+        min_y,
+        z_scale,
+        ear_window,
+        splits,
+        issue,
+        stats,
+    );
+}
+
+/// check if every vertex of a ring is strictly convex
+fn is_convex<T: Float>(nodes: &[Node<T>], start_i: usize) -> bool {
+    let mut p_i = start_i;
+    loop {
+        let p = node!(nodes, p_i);
+        if area(node!(nodes, p.prev_i), p, node!(nodes, p.next_i)) >= T::zero() {
+            return false;
+        }
+        p_i = p.next_i;
+        if p_i == start_i {
+            return true;
+        }
+    }
+}
+
+/// cut off the ears of a convex ring in the same order as [`earcut_linked`] would; returns the
+/// remaining ring if rounding made one of its vertices stop being convex
+fn clip_convex<T: Float, N: Index, A: NodeArena<T>, S: TriangleSink<N>>(
+    nodes: &mut A,
+    ear_i: usize,
+    triangles: &mut S,
+) -> Option<usize> {
+    let mut ear_i = ear_i;
+    loop {
+        let ear = node!(nodes, ear_i);
+        if ear.prev_i == ear.next_i {
+            return None;
+        }
+        let (prev, next) = (node!(nodes, ear.prev_i), node!(nodes, ear.next_i));
+        if area(prev, ear, next) >= T::zero() {
+            return Some(ear_i);
+        }
+        triangles.push(N::from_usize(prev.i));
+        triangles.push(N::from_usize(ear.i));
+        triangles.push(N::from_usize(next.i));
+        let next_i = ear.next_i;
+        remove_node(nodes, ear_i);
+        nodes.ear_cut(next_i);
+        ear_i = node!(nodes, next_i).next_i;
+    }
+}
+
+/// Triangulate a flattened polygon into `triangles_out`, replacing its previous contents.
+///
+/// Clears `triangles_out` but keeps its allocation (and that of `store`), so the same buffers
+/// can be reused across many polygons without reallocating.
+///
+/// See [`earcut`] for the meaning of the parameters.
+pub fn earcut_into<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    triangles_out: &mut Vec<N>,
+    store: &mut Store<T>,
+) {
+    triangles_out.clear();
+    earcut_impl(data, hole_indices, dim, triangles_out, store);
+}
+
+/// Triangulate a flattened polygon into caller-provided buffers without allocating, e.g. on a
+/// microcontroller with a fixed scratch arena and no heap.
+///
+/// `nodes` is the working memory: it needs one node per vertex plus two per hole, and two
+/// more for every split the last-resort pass makes on bad input (see
+/// [`PassStats::split_count`]). A simple polygon takes `vertices - 2 + 2 * holes` triangles,
+/// three indices each in `out`. `out` also briefly holds one node index per hole while the
+/// holes are bridged.
+///
+/// See [`earcut`] for the other parameters.
+///
+/// ## Returns
+/// The number of indices written to the front of `out`, three per triangle
+///
+/// ## Errors
+/// [`EarcutError::OutOfSpace`] if `nodes` or `out` is too small, or if bad input needs more
+/// than 32 halves of split rings to wait at once (see [`Store::with_split_depth`]); `out` then
+/// holds as many triangles as fit.
+pub fn earcut_into_slice<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    nodes: &mut [Node<T>],
+    out: &mut [N],
+) -> Result<usize, EarcutError> {
+    debug_assert_index_fits::<N>(data.len() / dim);
+    let holes = hole_indices.len();
+    if nodes.len() < data.len() / dim + 2 * holes || out.len() < holes {
+        return Err(EarcutError::OutOfSpace);
+    }
+    let mut nodes = SliceArena {
+        nodes,
+        len: 0,
+        full: false,
+    };
+    let mut issue = None;
+    let mut stats = PassStats::default();
+    let Some(outer_node_i) = link_polygon(
+        &mut nodes,
+        data,
+        hole_indices,
+        dim,
+        false,
+        &mut out[..holes],
+        &mut issue,
+    ) else {
+        return Ok(0);
+    };
+
+    let outer_len = hole_indices
+        .first()
+        .map_or(data.len(), |h| h.into_usize() * dim);
+    let indexed = (data.len() > 80 * dim).then(|| &data[..outer_len]);
+    let mut triangles = SliceSink {
+        indices: out,
+        len: 0,
+    };
+    cut_polygon(
+        &mut nodes,
+        indexed,
+        dim,
+        None,
+        outer_node_i,
+        holes > 0,
+        &mut triangles,
+        DEFAULT_MORTON_BITS,
+        1,
+        &mut ArrayStack {
+            items: [0; SLICE_SPLIT_DEPTH],
+            len: 0,
+        },
+        &mut issue,
+        &mut stats,
+    );
+    if nodes.full || stats.split_limit_hit || triangles.len > triangles.indices.len() {
+        return Err(EarcutError::OutOfSpace);
+    }
+
+    Ok(triangles.len)
+}
+
+/// create a circular doubly linked list from polygon points in the specified winding order;
+/// with `keep`, the points can't be filtered out
+fn linked_list<T: Float, A: NodeArena<T>>(
+    nodes: &mut A,
+    data: &[T],
+    start: usize,
+    end: usize,
+    dim: usize,
+    clockwise: bool,
+    keep: bool,
+) -> Option<usize> {
+    let mut last_i: Option<usize> = None;
+    let mut push = |i: usize| {
+        let xy = [data[i], data[i + 1]];
+        // skip consecutive duplicates, which only make zero area ears
+        if last_i.is_none_or(|last_i| node!(nodes, last_i).xy != xy) {
+            let node_i = insert_node(nodes, i / dim, xy, last_i);
+            node_mut!(nodes, node_i).keep = keep;
+            last_i = Some(node_i);
+        }
+    };
+
+    if clockwise == (signed_area(data, start, end, dim) > T::zero()) {
+        (start..end).step_by(dim).for_each(&mut push);
+    } else {
+        (start..end).step_by(dim).rev().for_each(&mut push);
+    }
+
+    if let Some(li) = last_i {
+        let last = node!(nodes, li);
+        if equals(last, node!(nodes, last.next_i)) {
+            let next_i = last.next_i;
+            remove_node(nodes, li);
+            last_i = Some(next_i);
+        }
+    }
+
+    last_i
+}
+
+/// eliminate colinear or duplicate points, except those marked to keep
+fn filter_points<T: Float>(nodes: &mut [Node<T>], start_i: usize, end_i: Option<usize>) -> usize {
+    filter_points_by(nodes, start_i, end_i, |prev, p, next| {
+        !p.keep && (equals(p, next) || area(prev, p, next).is_zero())
+    })
+}
+
+/// [`filter_points`] removing the (non-steiner) points `removable` picks given their neighbours
+fn filter_points_by<T: Float>(
+    nodes: &mut [Node<T>],
+    start_i: usize,
+    end_i: Option<usize>,
+    removable: impl Fn(&Node<T>, &Node<T>, &Node<T>) -> bool,
+) -> usize {
+    let mut end_i = end_i.unwrap_or(start_i);
+    let mut p_i = start_i;
+
+    loop {
+        let mut again = false;
+        let p = *node!(nodes, p_i);
+        let p_next = node!(nodes, p.next_i);
+        if !p.steiner && removable(node!(nodes, p.prev_i), &p, p_next) {
+            remove_node(nodes, p_i);
+            p_i = p.prev_i;
+            end_i = p.prev_i;
+            if p_i == node!(nodes, p_i).next_i {
+                break;
+            }
+            again = true;
+        } else {
+            p_i = p.next_i;
+        }
+        if !again && p_i == end_i {
+            break;
+        }
+    }
+
+    end_i
+}
+
+/// triangulate a polygon (given as a linked list), running the passes of [`cut_ears`] in turn
+/// and then the halves of every split in the order the splits were made
+#[allow(clippy::too_many_arguments)]
+fn earcut_linked<T: Float, N: Index, A: NodeArena<T>, S: TriangleSink<N>, W: SplitStack>(
+    nodes: &mut A,
+    ear_i: usize,
+    triangles: &mut S,
+    min_x: T,
+    min_y: T,
+    z_scale: T,
+    ear_window: usize,
+    splits: &mut W,
+    issue: &mut Option<usize>,
+    stats: &mut PassStats,
+) {
+    let mut next = Some((ear_i, 0));
+    while let Some((ear_i, pass)) = next {
+        next = cut_ears(
+            nodes, ear_i, triangles, min_x, min_y, z_scale, ear_window, pass, splits, issue, stats,
+        )
+        .or_else(|| splits.pop().map(|ring_i| (ring_i, 0)));
+    }
+}
+
+/// main ear slicing loop which triangulates a polygon (given as a linked list); returns the
+/// ring and pass to go on with if it runs out of ears
+#[allow(clippy::too_many_arguments)]
+fn cut_ears<T: Float, N: Index, A: NodeArena<T>, S: TriangleSink<N>, W: SplitStack>(
+    nodes: &mut A,
+    ear_i: usize,
+    triangles: &mut S,
+    min_x: T,
+    min_y: T,
+    z_scale: T,
+    ear_window: usize,
+    pass: usize,
+    splits: &mut W,
+    issue: &mut Option<usize>,
+    stats: &mut PassStats,
+) -> Option<(usize, usize)> {
+    let mut ear_i = ear_i;
+
+    // interlink polygon nodes in z-order
+    if pass == 0 && !z_scale.is_zero() {
+        index_curve(nodes, ear_i, min_x, min_y, z_scale);
+    }
+
+    let mut stop_i = ear_i;
+
+    // iterate through ears, slicing them one by one
+    loop {
+        let ear = node!(nodes, ear_i);
+        if ear.prev_i == ear.next_i {
+            return None;
+        }
+        let mut prev_i = ear.prev_i;
+        let mut next_i = ear.next_i;
+
+        let is_ear = |nodes: &[Node<T>], ear_i, tested: &mut usize| {
+            if !z_scale.is_zero() {
+                is_ear_hashed(nodes, ear_i, min_x, min_y, z_scale, tested)
+            } else {
+                is_ear(nodes, ear_i, tested)
+            }
+        };
+        if is_ear(nodes, ear_i, &mut stats.points_tested) {
+            let mut ear = ear;
+            if ear_window > 1 {
+                // look ahead for a better shaped ear to cut instead
+                let mut best = (ear_i, min_angle(nodes, ear_i));
+                let mut p_i = next_i;
+                for _ in 1..ear_window {
+                    if p_i == ear_i {
+                        break;
+                    }
+                    if is_ear(nodes, p_i, &mut stats.points_tested) {
+                        let angle = min_angle(nodes, p_i);
+                        if angle > best.1 {
+                            best = (p_i, angle);
+                        }
+                    }
+                    p_i = node!(nodes, p_i).next_i;
+                }
+                ear_i = best.0;
+                ear = node!(nodes, ear_i);
+                prev_i = ear.prev_i;
+                next_i = ear.next_i;
+            }
+
+            // cut off the triangle
+            triangles.push(N::from_usize(node!(nodes, prev_i).i));
+            triangles.push(N::from_usize(ear.i));
+            triangles.push(N::from_usize(node!(nodes, next_i).i));
+            match pass {
+                0 => stats.p0 += 1,
+                1 => stats.p1 += 1,
+                _ => stats.p2 += 1,
+            }
+
+            remove_node(nodes, ear_i);
+            nodes.ear_cut(next_i);
+
+            // skipping the next vertex leads to less sliver triangles
+            ear_i = node!(nodes, next_i).next_i;
+            stop_i = ear_i;
+
+            continue;
+        }
+
+        ear_i = next_i;
+
+        // if we looped through the whole remaining polygon and can't find any more ears
+        if ear_i == stop_i {
+            return match pass {
+                // try filtering points and slicing again
+                0 => Some((filter_points(nodes, ear_i, None), 1)),
+                1 => {
+                    // if this didn't work, try curing all small self-intersections locally, now
+                    // without sparing the vertices marked to keep
+                    let filtered_i = filter_points_by(nodes, ear_i, None, |prev, p, next| {
+                        equals(p, next) || area(prev, p, next).is_zero()
+                    });
+                    let start = triangles.len();
+                    let ear_i = cure_local_intersections(nodes, filtered_i, triangles, issue);
+                    stats.p2 += (triangles.len() - start) / 3;
+                    Some((ear_i, 2))
+                }
+                _ => {
+                    // as a last resort, try splitting the remaining polygon into two, leaving
+                    // one half to wait while the other starts over
+                    let (a_i, c_i) = split_earcut(nodes, ear_i, issue)?;
+                    stats.split_count += 1;
+                    if !splits.push(c_i) {
+                        stats.split_limit_hit = true;
+                        issue.get_or_insert(node!(nodes, c_i).i);
+                    }
+                    Some((a_i, 0))
+                }
+            };
+        }
+    }
+}
+
+/// the smallest angle of the ear at a node, in radians
+fn min_angle<T: Float>(nodes: &[Node<T>], ear_i: usize) -> T {
+    let b = node!(nodes, ear_i);
+    let [a, c] = [node!(nodes, b.prev_i).xy, node!(nodes, b.next_i).xy];
+    let angle = |o: [T; 2], u: [T; 2], w: [T; 2]| {
+        let (u, w) = ([u[0] - o[0], u[1] - o[1]], [w[0] - o[0], w[1] - o[1]]);
+        let cross = u[0] * w[1] - u[1] * w[0];
+        cross.abs().atan2(u[0] * w[0] + u[1] * w[1])
+    };
+    angle(a, b.xy, c)
+        .min(angle(b.xy, c, a))
+        .min(angle(c, a, b.xy))
+}
+
+/// check whether a polygon node forms a valid ear with adjacent nodes, counting the points
+/// tested in `tested`
+fn is_ear<T: Float>(nodes: &[Node<T>], ear_i: usize, tested: &mut usize) -> bool {
+    let b = node!(nodes, ear_i);
+    let a = node!(nodes, b.prev_i);
+    let c = node!(nodes, b.next_i);
+
+    // reflex, can't be an ear
+    if area(a, b, c) >= T::zero() {
+        return false;
+    }
+
+    // now make sure we don't have other points inside the potential ear
+    let mut p_i = c.next_i;
+    while p_i != b.prev_i {
+        let p = node!(nodes, p_i);
+        *tested += 1;
+        if point_in_wound_triangle(a.xy, b.xy, c.xy, p.xy)
+            && area(node!(nodes, p.prev_i), p, node!(nodes, p.next_i)) >= T::zero()
+        {
+            return false;
+        }
+        p_i = p.next_i;
+    }
+
+    true
+}
+
+/// check whether a polygon node forms a valid ear, using the z-order index to skip far points;
+/// the points tested are counted in `tested`
+fn is_ear_hashed<T: Float>(
+    nodes: &[Node<T>],
+    ear_i: usize,
+    min_x: T,
+    min_y: T,
+    z_scale: T,
+    tested: &mut usize,
+) -> bool {
+    let b = node!(nodes, ear_i);
+    let a = node!(nodes, b.prev_i);
+    let c = node!(nodes, b.next_i);
+
+    // reflex, can't be an ear
+    if area(a, b, c) >= T::zero() {
+        return false;
+    }
+
+    // triangle bbox
+    let min_tx = a.xy[0].min(b.xy[0]).min(c.xy[0]);
+    let min_ty = a.xy[1].min(b.xy[1]).min(c.xy[1]);
+    let max_tx = a.xy[0].max(b.xy[0]).max(c.xy[0]);
+    let max_ty = a.xy[1].max(b.xy[1]).max(c.xy[1]);
+
+    // z-order range for the current triangle bbox;
+    let min_z = z_order([min_tx, min_ty], [min_x, min_y], z_scale);
+    let max_z = z_order([max_tx, max_ty], [min_x, min_y], z_scale);
+
+    let mut blocks = |p_i: usize| {
+        if p_i == b.prev_i || p_i == b.next_i {
+            return false;
+        }
+        let p = node!(nodes, p_i);
+        *tested += 1;
+        point_in_wound_triangle(a.xy, b.xy, c.xy, p.xy)
+            && area(node!(nodes, p.prev_i), p, node!(nodes, p.next_i)) >= T::zero()
+    };
+
+    let mut p = b.prev_z_i;
+    let mut n = b.next_z_i;
+
+    // look for points inside the triangle in both directions
+    while let (Some(p_i), Some(n_i)) = (p, n) {
+        if node!(nodes, p_i).z < min_z || node!(nodes, n_i).z > max_z {
+            break;
+        }
+        if blocks(p_i) {
+            return false;
+        }
+        p = node!(nodes, p_i).prev_z_i;
+
+        if blocks(n_i) {
+            return false;
+        }
+        n = node!(nodes, n_i).next_z_i;
+    }
+
+    // look for remaining points in decreasing z-order
+    while let Some(p_i) = p {
+        if node!(nodes, p_i).z < min_z {
+            break;
+        }
+        if blocks(p_i) {
+            return false;
+        }
+        p = node!(nodes, p_i).prev_z_i;
+    }
+
+    // look for remaining points in increasing z-order
+    while let Some(n_i) = n {
+        if node!(nodes, n_i).z > max_z {
+            break;
+        }
+        if blocks(n_i) {
+            return false;
+        }
+        n = node!(nodes, n_i).next_z_i;
+    }
+
+    true
+}
+
+/// go through all polygon nodes and cure small local self-intersections
+fn cure_local_intersections<T: Float, N: Index, A: NodeArena<T>, S: TriangleSink<N>>(
+    nodes: &mut A,
+    start_i: usize,
+    triangles: &mut S,
+    issue: &mut Option<usize>,
+) -> usize {
+    let mut start_i = start_i;
+    let mut p_i = start_i;
+    loop {
+        let p = *node!(nodes, p_i);
+        let p_next = *node!(nodes, p.next_i);
+        let a_i = p.prev_i;
+        let b_i = p_next.next_i;
+        let a = node!(nodes, a_i);
+        let b = node!(nodes, b_i);
+
+        if !equals(a, b)
+            && intersects(a, &p, &p_next, b)
+            && locally_inside(nodes, a_i, b_i)
+            && locally_inside(nodes, b_i, a_i)
+        {
+            issue.get_or_insert(p.i);
+            triangles.push(N::from_usize(a.i));
+            triangles.push(N::from_usize(p.i));
+            triangles.push(N::from_usize(b.i));
+
+            // remove two nodes involved
+            remove_node(nodes, p_i);
+            remove_node(nodes, p.next_i);
+            nodes.ear_cut(b_i);
+
+            p_i = b_i;
+            start_i = b_i;
+        }
+        p_i = node!(nodes, p_i).next_i;
+        if p_i == start_i {
+            break;
+        }
+    }
+
+    p_i
+}
+
+/// try splitting polygon into two along a diagonal, returning both halves to triangulate
+/// independently
+fn split_earcut<T: Float, A: NodeArena<T>>(
+    nodes: &mut A,
+    start_i: usize,
+    issue: &mut Option<usize>,
+) -> Option<(usize, usize)> {
+    // look for a valid diagonal that divides the polygon into two
+    let mut a_i = start_i;
+    loop {
+        let a = *node!(nodes, a_i);
+        let mut b_i = node!(nodes, a.next_i).next_i;
+        while b_i != a.prev_i {
+            if a.i != node!(nodes, b_i).i && is_valid_diagonal(nodes, a_i, b_i) {
+                // split the polygon in two by the diagonal
+                if !nodes.make_room(2) {
+                    return None;
+                }
+                let c_i = split_polygon(nodes, a_i, b_i);
+
+                // filter colinear points around the cuts
+                let a_next_i = node!(nodes, a_i).next_i;
+                let a_i = filter_points(nodes, a_i, Some(a_next_i));
+                let c_next_i = node!(nodes, c_i).next_i;
+                let c_i = filter_points(nodes, c_i, Some(c_next_i));
+                return Some((a_i, c_i));
+            }
+            b_i = node!(nodes, b_i).next_i;
+        }
+        a_i = a.next_i;
+        if a_i == start_i {
+            break;
+        }
+    }
+    // a simple polygon can always be split by some diagonal
+    issue.get_or_insert(node!(nodes, start_i).i);
+    None
+}
+
+/// link every hole into the outer loop, producing a single-ring polygon without holes; `queue`
+/// is scratch space for one node index per hole
+#[allow(clippy::too_many_arguments)]
+fn eliminate_holes<T: Float, N: Index, Q: Index, A: NodeArena<T>>(
+    nodes: &mut A,
+    data: &[T],
+    hole_indices: &[N],
+    outer_node_i: usize,
+    dim: usize,
+    keep: bool,
+    queue: &mut [Q],
+    issue: &mut Option<usize>,
+) -> usize {
+    let len = hole_indices.len();
+    let mut queued = 0;
+    for (i, hole) in hole_indices.iter().enumerate() {
+        let start = hole.into_usize() * dim;
+        let end = if i < len - 1 {
+            hole_indices[i + 1].into_usize() * dim
+        } else {
+            data.len()
+        };
+        if let Some(list_i) = linked_list(nodes, data, start, end, dim, false, keep) {
+            // a single point, possibly repeated to close it, is a steiner point; longer rings
+            // collapsed to a point by their duplicates are degenerate holes and get filtered out
+            let list = node_mut!(nodes, list_i);
+            if list_i == list.next_i && end - start <= 2 * dim {
+                list.steiner = true;
+            }
+            queue[queued] = Q::from_usize(get_leftmost(nodes, list_i));
+            queued += 1;
+        }
+    }
+    let queue = &mut queue[..queued];
+
+    // sort by leftmost x, then y; nodes are created in hole order, so the final tie-break by
+    // index keeps holes in input order. The order is total, NaN after everything else, so the
+    // result never depends on how the sort visits the queue.
+    let xy = |q: &Q| node!(nodes, q.into_usize()).xy;
+    queue.sort_unstable_by(|a, b| {
+        let (a_xy, b_xy) = (xy(a), xy(b));
+        cmp_coordinate(a_xy[0], b_xy[0])
+            .then(cmp_coordinate(a_xy[1], b_xy[1]))
+            .then(a.into_usize().cmp(&b.into_usize()))
+    });
+
+    // process holes from left to right
+    let mut outer_node_i = outer_node_i;
+    for hole_i in queue.iter() {
+        outer_node_i = eliminate_hole(nodes, hole_i.into_usize(), outer_node_i, issue);
+        let outer_next_i = node!(nodes, outer_node_i).next_i;
+        outer_node_i = filter_points(nodes, outer_node_i, Some(outer_next_i));
+    }
+
+    outer_node_i
+}
+
+/// compare two coordinates, ordering NaN after every number
+fn cmp_coordinate<T: Float>(a: T, b: T) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// find a bridge between vertices that connects hole with an outer ring and and link it
+fn eliminate_hole<T: Float, A: NodeArena<T>>(
+    nodes: &mut A,
+    hole_i: usize,
+    outer_node_i: usize,
+    issue: &mut Option<usize>,
+) -> usize {
+    let Some(bridge_i) = find_hole_bridge(nodes, hole_i, outer_node_i) else {
+        // a hole inside the outer ring can always be bridged
+        issue.get_or_insert(node!(nodes, hole_i).i);
+        return outer_node_i;
+    };
+    if let Some((first_i, last_i)) = splice_shared_edges(nodes, bridge_i, hole_i) {
+        // filter collinear points around the splice; the input node may have been dropped
+        return filter_points(nodes, first_i, Some(last_i));
+    }
+    let bridge_reverse_i = split_polygon(nodes, bridge_i, hole_i);
+
+    // filter collinear points around the cuts
+    let bridge_next_i = node!(nodes, bridge_i).next_i;
+    let filtered_bridge_i = filter_points(nodes, bridge_i, Some(bridge_next_i));
+    let bridge_reverse_next_i = node!(nodes, bridge_reverse_i).next_i;
+    filter_points(nodes, bridge_reverse_i, Some(bridge_reverse_next_i));
+
+    // Check if input node was removed by the filtering
+    if outer_node_i == bridge_i {
+        filtered_bridge_i
+    } else {
+        outer_node_i
+    }
+}
+
+/// If a hole shares one or more edges with the outer ring next to the point where they touch,
+/// merge the rings along those edges instead of bridging them. A bridge would leave the shared
+/// edges behind as a zero-width spike, which rounding can keep from being filtered out.
+///
+/// Returns the outer nodes on both ends of the removed edges, the only outer nodes that may be
+/// left collinear with their new neighbours
+fn splice_shared_edges<T: Float>(
+    nodes: &mut [Node<T>],
+    outer_i: usize,
+    hole_i: usize,
+) -> Option<(usize, usize)> {
+    if !equals(node!(nodes, outer_i), node!(nodes, hole_i)) {
+        return None;
+    }
+    let mut hole_len = 1;
+    let mut p_i = node!(nodes, hole_i).next_i;
+    while p_i != hole_i {
+        hole_len += 1;
+        p_i = node!(nodes, p_i).next_i;
+    }
+
+    // the rings run in opposite directions, so a shared edge is followed backwards on one and
+    // forwards on the other; keep at least one vertex of the hole and the outer ring each
+    let (mut first_i, mut hole_last_i) = (outer_i, hole_i);
+    let (mut last_i, mut hole_first_i) = (outer_i, hole_i);
+    let mut shared = 0;
+    loop {
+        let (o, h) = (node!(nodes, first_i), node!(nodes, hole_last_i));
+        if shared + 2 >= hole_len || !equals(node!(nodes, o.prev_i), node!(nodes, h.next_i)) {
+            break;
+        }
+        (first_i, hole_last_i) = (o.prev_i, h.next_i);
+        shared += 1;
+    }
+    loop {
+        let (o, h) = (node!(nodes, last_i), node!(nodes, hole_first_i));
+        if shared + 2 >= hole_len || !equals(node!(nodes, o.next_i), node!(nodes, h.prev_i)) {
+            break;
+        }
+        (last_i, hole_first_i) = (o.next_i, h.prev_i);
+        shared += 1;
+    }
+    if shared == 0 || node!(nodes, last_i).next_i == first_i {
+        return None;
+    }
+
+    // the outer nodes between first and last and the hole's copies of them drop out
+    let rest_first_i = node!(nodes, hole_last_i).next_i;
+    let rest_last_i = node!(nodes, hole_first_i).prev_i;
+    node_mut!(nodes, first_i).next_i = rest_first_i;
+    node_mut!(nodes, rest_first_i).prev_i = first_i;
+    node_mut!(nodes, rest_last_i).next_i = last_i;
+    node_mut!(nodes, last_i).prev_i = rest_last_i;
+
+    Some((first_i, last_i))
+}
+
+/// David Eberly's algorithm for finding a bridge between hole and outer polygon
+fn find_hole_bridge<T: Float>(
+    nodes: &[Node<T>],
+    hole_i: usize,
+    outer_node_i: usize,
+) -> Option<usize> {
+    let [hx, hy] = node!(nodes, hole_i).xy;
+    let mut qx = T::neg_infinity();
+    let mut m_i: Option<usize> = None;
+
+    // a hole touching the outer ring at a vertex bridges right there; the ray below could miss
+    // it, as its intersection with the segment ending in that vertex is subject to rounding
+    let hole = node!(nodes, hole_i);
+    if equals(hole, node!(nodes, outer_node_i)) {
+        return Some(outer_node_i);
+    }
+
+    // find a segment intersected by a ray from the hole's leftmost point to the left;
+    // segment's endpoint with lesser x will be potential connection point
+    let mut p_i = outer_node_i;
+    loop {
+        let p = node!(nodes, p_i);
+        let p_next = node!(nodes, p.next_i);
+        if equals(hole, p_next) {
+            return Some(p.next_i);
+        }
+        if hy <= p.xy[1] && hy >= p_next.xy[1] && p_next.xy[1] != p.xy[1] {
+            let x = p.xy[0] + (hy - p.xy[1]) * (p_next.xy[0] - p.xy[0]) / (p_next.xy[1] - p.xy[1]);
+            if x <= hx && x > qx {
+                qx = x;
+                if x == hx {
+                    if hy == p.xy[1] {
+                        return Some(p_i);
+                    }
+                    if hy == p_next.xy[1] {
+                        return Some(p.next_i);
+                    }
+                }
+                m_i = Some(if p.xy[0] < p_next.xy[0] {
+                    p_i
+                } else {
+                    p.next_i
+                });
+            }
+        }
+        p_i = p.next_i;
+        if p_i == outer_node_i {
+            break;
+        }
+    }
+
+    let mut m_i = m_i?;
+
+    // hole touches outer segment; pick leftmost endpoint
+    if hx == qx {
+        return Some(m_i);
+    }
+
+    // look for points inside the triangle of hole point, segment intersection and endpoint;
+    // if there are no points found, we have a valid connection;
+    // otherwise choose the point of the minimum angle with the ray as connection point
+
+    let stop_i = m_i;
+    let [mx, my] = node!(nodes, m_i).xy;
+    let mut tan_min = T::infinity();
+
+    p_i = m_i;
+    loop {
+        let p = node!(nodes, p_i);
+        if hx >= p.xy[0]
+            && p.xy[0] >= mx
+            && hx != p.xy[0]
+            && point_in_wound_triangle(
+                [if hy < my { hx } else { qx }, hy],
+                [mx, my],
+                [if hy < my { qx } else { hx }, hy],
+                p.xy,
+            )
+        {
+            // tangential
+            let tan = (hy - p.xy[1]).abs() / (hx - p.xy[0]);
+            let m = node!(nodes, m_i);
+
+            if locally_inside(nodes, p_i, hole_i)
+                && (tan < tan_min
+                    || (tan == tan_min
+                        && (p.xy[0] > m.xy[0]
+                            || (p.xy[0] == m.xy[0] && sector_contains_sector(nodes, m_i, p_i)))))
+            {
+                m_i = p_i;
+                tan_min = tan;
+            }
+        }
+
+        p_i = p.next_i;
+        if p_i == stop_i {
+            break;
+        }
+    }
+
+    Some(m_i)
+}
+
+/// whether sector in vertex m contains sector in vertex p in the same coordinates
+fn sector_contains_sector<T: Float>(nodes: &[Node<T>], m_i: usize, p_i: usize) -> bool {
+    let m = node!(nodes, m_i);
+    let p = node!(nodes, p_i);
+    area(node!(nodes, m.prev_i), m, node!(nodes, p.prev_i)) < T::zero()
+        && area(node!(nodes, p.next_i), m, node!(nodes, m.next_i)) < T::zero()
+}
+
+/// interlink polygon nodes in z-order
+fn index_curve<T: Float>(nodes: &mut [Node<T>], start_i: usize, min_x: T, min_y: T, z_scale: T) {
+    let mut p_i = start_i;
+    loop {
+        let p = node_mut!(nodes, p_i);
+        p.z = z_order(p.xy, [min_x, min_y], z_scale);
+        p.prev_z_i = Some(p.prev_i);
+        p.next_z_i = Some(p.next_i);
+        p_i = p.next_i;
+        if p_i == start_i {
+            break;
+        }
+    }
+
+    if let Some(prev_z_i) = node!(nodes, p_i).prev_z_i {
+        node_mut!(nodes, prev_z_i).next_z_i = None;
+    }
+    node_mut!(nodes, p_i).prev_z_i = None;
+
+    sort_linked(nodes, p_i);
+}
+
+/// Simon Tatham's linked list merge sort algorithm
+/// http://www.chiark.greenend.org.uk/~sgtatham/algorithms/listsort.html
+fn sort_linked<T: Float>(nodes: &mut [Node<T>], list_i: usize) {
+    let mut list = Some(list_i);
+    let mut in_size = 1;
+
+    loop {
+        let mut p = list;
+        list = None;
+        let mut tail: Option<usize> = None;
+        let mut num_merges = 0;
+
+        while let Some(p_start) = p {
+            num_merges += 1;
+            let mut q = Some(p_start);
+            let mut p_size = 0;
+            for _ in 0..in_size {
+                p_size += 1;
+                q = q.and_then(|q_i| node!(nodes, q_i).next_z_i);
+                if q.is_none() {
+                    break;
+                }
+            }
+            let mut q_size = in_size;
+
+            while p_size > 0 || (q_size > 0 && q.is_some()) {
+                let take_p = match (p, q) {
+                    (Some(p_i), Some(q_i)) if p_size > 0 && q_size > 0 => {
+                        node!(nodes, p_i).z <= node!(nodes, q_i).z
+                    }
+                    (Some(_), _) => p_size > 0,
+                    _ => false,
+                };
+                let e = if take_p { p } else { q };
+                let Some(e_i) = e else {
+                    break;
+                };
+                if take_p {
+                    p = node!(nodes, e_i).next_z_i;
+                    p_size -= 1;
+                } else {
+                    q = node!(nodes, e_i).next_z_i;
+                    q_size -= 1;
+                }
+
+                match tail {
+                    Some(tail_i) => node_mut!(nodes, tail_i).next_z_i = Some(e_i),
+                    None => list = Some(e_i),
+                }
+                node_mut!(nodes, e_i).prev_z_i = tail;
+                tail = Some(e_i);
+            }
+
+            p = q;
+        }
+
+        if let Some(tail_i) = tail {
+            node_mut!(nodes, tail_i).next_z_i = None;
+        }
+        in_size *= 2;
+
+        if num_merges <= 1 {
+            break;
+        }
+    }
+}
+
+/// The z-order (Morton) code the triangulation sorts large polygons by.
+///
+/// Both coordinates are quantized to 15 bits, `0..=32767` across the bounding box, and their
+/// bits interleaved with x in the odd and y in the even positions. Coordinates below the box
+/// quantize to 0 and those above it to 32767, and points closer together than 1/32767 of the
+/// box's longer side may share a code.
+///
+/// ## Parameters
+/// - `xy`: the point
+/// - `min`: the lower corner of the bounding box
+/// - `inv_size`: the inverse of the bounding box's longer side, see [`morton_inv_size`]
+pub fn morton_code<T: Float>(xy: [T; 2], min: [T; 2], inv_size: T) -> i32 {
+    // coords are transformed into non-negative 15-bit integer range
+    let scale = T::from(32767.0).unwrap_or_else(T::one);
+    let x = cell(scale * (xy[0] - min[0]) * inv_size, 0x7FFF);
+    let y = cell(scale * (xy[1] - min[1]) * inv_size, 0x7FFF);
+
+    // two 15-bit coordinates interleave into 30 bits, which always fit
+    interleave(x, y) as i32
+}
+
+/// z-order code of a point with coordinates scaled by `z_scale` into up to 21 bits each
+///
+/// Scaled coordinates below 0 clamp to cell 0 and those past the 21 bit range to the last cell,
+/// where they used to wrap around in the bit mask.
+fn z_order<T: Float>(xy: [T; 2], min: [T; 2], z_scale: T) -> i64 {
+    let x = cell((xy[0] - min[0]) * z_scale, 0x1F_FFFF);
+    let y = cell((xy[1] - min[1]) * z_scale, 0x1F_FFFF);
+
+    interleave(x, y)
+}
+
+/// the z-order cell of a coordinate scaled along one axis, truncated to an integer in
+/// `0..=max_cell`
+///
+/// The scaling happens in `T` and the result converts straight to `u32`, so no wider integer or
+/// float type is involved: `f32` and `f64` give the same cell whenever the scaled coordinate is
+/// exact in both, and either way larger coordinates never get smaller cells.
+fn cell<T: Float>(scaled: T, max_cell: u32) -> u32 {
+    let max = T::from(max_cell).unwrap_or_else(T::zero);
+    // NaN becomes 0 as well
+    scaled.max(T::zero()).min(max).to_u32().unwrap_or(0)
+}
+
+/// interleave the low 21 bits of x and y, with x in the lowest bit
+fn interleave(x: u32, y: u32) -> i64 {
+    (spread_bits(x) | (spread_bits(y) << 1)) as i64
+}
+
+/// spread the low 21 bits of `v` out to the even bit positions
+fn spread_bits(v: u32) -> u64 {
+    let mut v = u64::from(v) & 0x1F_FFFF;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+/// the bounding box of a flattened ring, `[min_x, min_y, max_x, max_y]`
+fn ring_bbox<T: Float>(data: &[T], dim: usize) -> [T; 4] {
+    let mut max_x = data[0];
+    let mut max_y = data[1];
+    let mut min_x = max_x;
+    let mut min_y = max_y;
+    for xy in data[dim..].chunks_exact(dim) {
+        let (x, y) = (xy[0], xy[1]);
+        if x < min_x {
+            min_x = x;
+        }
+        if y < min_y {
+            min_y = y;
+        }
+        if x > max_x {
+            max_x = x;
+        }
+        if y > max_y {
+            max_y = y;
+        }
+    }
+    [min_x, min_y, max_x, max_y]
+}
+
+/// The `inv_size` [`morton_code`] expects for a bounding box: the inverse of its longer side,
+/// or zero for an empty box
+pub fn morton_inv_size<T: Float>(min: [T; 2], max: [T; 2]) -> T {
+    let size = (max[0] - min[0]).max(max[1] - min[1]);
+    if size.is_zero() {
+        size
+    } else {
+        T::one() / size
+    }
+}
+
+/// find the leftmost node of a polygon ring
+fn get_leftmost<T: Float>(nodes: &[Node<T>], start_i: usize) -> usize {
+    let mut p_i = start_i;
+    let mut leftmost_i = start_i;
+    loop {
+        let p = node!(nodes, p_i);
+        let leftmost = node!(nodes, leftmost_i);
+        if p.xy[0] < leftmost.xy[0] || (p.xy[0] == leftmost.xy[0] && p.xy[1] < leftmost.xy[1]) {
+            leftmost_i = p_i;
+        }
+        p_i = p.next_i;
+        if p_i == start_i {
+            break;
+        }
+    }
+
+    leftmost_i
+}
+
+/// Whether `p` lies inside the triangle `a`, `b`, `c` or on its boundary; either winding works
+pub fn point_in_triangle<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2], p: [T; 2]) -> bool {
+    point_in_wound_triangle(a, b, c, p) || point_in_wound_triangle(a, c, b, p)
+}
+
+/// Whether `p` lies inside a flattened ring or on its boundary, by even-odd ray casting.
+///
+/// The ring may be wound either way and doesn't need to repeat its first point.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+/// - `p`: the point to test
+pub fn point_in_polygon<T: Float>(ring: &[T], dim: usize, p: [T; 2]) -> bool {
+    let [px, py] = p;
+    let mut inside = false;
+    let points = || ring.chunks_exact(dim).map(|xy| [xy[0], xy[1]]);
+    for (a, b) in points().zip(points().cycle().skip(1)) {
+        let cross = (b[0] - a[0]) * (py - a[1]) - (b[1] - a[1]) * (px - a[0]);
+        if cross.is_zero()
+            && px >= a[0].min(b[0])
+            && px <= a[0].max(b[0])
+            && py >= a[1].min(b[1])
+            && py <= a[1].max(b[1])
+        {
+            return true;
+        }
+        if (a[1] > py) != (b[1] > py) && px < (b[0] - a[0]) * (py - a[1]) / (b[1] - a[1]) + a[0] {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// check if a point lies within a convex triangle wound the way the triangulation's ears are
+fn point_in_wound_triangle<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2], p: [T; 2]) -> bool {
+    if cfg!(feature = "robust") {
+        return orient2d(p, c, a) >= T::zero()
+            && orient2d(p, a, b) >= T::zero()
+            && orient2d(p, b, c) >= T::zero();
+    }
+    let [ax, ay] = a;
+    let [bx, by] = b;
+    let [cx, cy] = c;
+    let [px, py] = p;
+    (cx - px) * (ay - py) - (ax - px) * (cy - py) >= T::zero()
+        && (ax - px) * (by - py) - (bx - px) * (ay - py) >= T::zero()
+        && (bx - px) * (cy - py) - (cx - px) * (by - py) >= T::zero()
+}
+
+/// check if a diagonal between two polygon nodes is valid (lies in polygon interior)
+fn is_valid_diagonal<T: Float>(nodes: &[Node<T>], a_i: usize, b_i: usize) -> bool {
+    let a = node!(nodes, a_i);
+    let b = node!(nodes, b_i);
+    let a_prev = node!(nodes, a.prev_i);
+    let a_next = node!(nodes, a.next_i);
+    let b_prev = node!(nodes, b.prev_i);
+    let b_next = node!(nodes, b.next_i);
+
+    a_next.i != b.i
+        && a_prev.i != b.i
+        // doesn't intersect other edges
+        && !intersects_polygon(nodes, a_i, b_i)
+        // locally visible
+        && ((locally_inside(nodes, a_i, b_i)
+            && locally_inside(nodes, b_i, a_i)
+            && middle_inside(nodes, a_i, b_i)
+            // does not create opposite-facing sectors
+            && (!area(a_prev, a, b_prev).is_zero() || !area(a, b_prev, b).is_zero()))
+            // special zero-length case
+            || (equals(a, b)
+                && area(a_prev, a, a_next) > T::zero()
+                && area(b_prev, b, b_next) > T::zero()))
+}
+
+/// signed area of a triangle
+fn area<T: Float>(p: &Node<T>, q: &Node<T>, r: &Node<T>) -> T {
+    area_xy(p.xy, q.xy, r.xy)
+}
+
+/// signed area of a triangle given by its points
+fn area_xy<T: Float>(p: [T; 2], q: [T; 2], r: [T; 2]) -> T {
+    if cfg!(feature = "robust") {
+        -orient2d(p, q, r)
+    } else {
+        (q[1] - p[1]) * (r[0] - q[0]) - (q[0] - p[0]) * (r[1] - q[1])
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`, with an exact sign.
+///
+/// Positive when `c` lies left of the line from `a` to `b` in y-up coordinates, negative when
+/// it lies right of it and zero only when the points are exactly collinear. The plain formula
+/// is used whenever its rounding error can't flip the sign; only nearly collinear points fall
+/// back to exact arithmetic (Shewchuk's adaptive predicate), where the value is approximate
+/// but the sign is still exact. Coordinates are assumed not to overflow or underflow.
+///
+/// With the `robust` feature the triangulation uses this for all of its orientation tests.
+pub fn orient2d<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2]) -> T {
+    let detleft = (a[0] - c[0]) * (b[1] - c[1]);
+    let detright = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = detleft - detright;
+    let detsum = if detleft > T::zero() && detright > T::zero() {
+        detleft + detright
+    } else if detleft < T::zero() && detright < T::zero() {
+        -detleft - detright
+    } else {
+        // opposite signs (or a zero) can't cancel out
+        return det;
+    };
+    // Shewchuk's ccwerrboundA, with his epsilon being half of `T::epsilon`
+    let epsilon = T::epsilon() / (T::one() + T::one());
+    let sixteen = T::from(16.0).unwrap_or_else(T::one);
+    let three = T::one() + T::one() + T::one();
+    let err_bound = (three + sixteen * epsilon) * epsilon * detsum;
+    if det >= err_bound || -det >= err_bound {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+/// orient2d summed exactly as an expansion of the six products of the expanded determinant
+fn orient2d_exact<T: Float>(a: [T; 2], b: [T; 2], c: [T; 2]) -> T {
+    let products = [
+        (a[0], b[1]),
+        (-a[0], c[1]),
+        (-a[1], b[0]),
+        (a[1], c[0]),
+        (b[0], c[1]),
+        (-b[1], c[0]),
+    ];
+    // a nonoverlapping expansion, smallest magnitude first, without zeros
+    let mut expansion = [T::zero(); 12];
+    let mut len = 0;
+    for (x, y) in products {
+        let product = x * y;
+        // exact, as a fused multiply-add rounds only once
+        let error = x.mul_add(y, -product);
+        for term in [error, product] {
+            let mut sum = term;
+            let mut kept = 0;
+            for i in 0..len {
+                let (high, low) = two_sum(sum, expansion[i]);
+                sum = high;
+                if !low.is_zero() {
+                    expansion[kept] = low;
+                    kept += 1;
+                }
+            }
+            if !sum.is_zero() {
+                expansion[kept] = sum;
+                kept += 1;
+            }
+            len = kept;
+        }
+    }
+    let Some(&largest) = expansion[..len].last() else {
+        return T::zero();
+    };
+    let estimate = expansion[..len]
+        .iter()
+        .fold(T::zero(), |sum, &term| sum + term);
+    // the largest term alone decides the sign
+    if (estimate > T::zero()) == (largest > T::zero()) {
+        estimate
+    } else {
+        largest
+    }
+}
+
+/// `a + b` and its rounding error
+fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// check if two points are equal
+fn equals<T: Float>(p1: &Node<T>, p2: &Node<T>) -> bool {
+    p1.xy == p2.xy
+}
+
+/// check if two segments intersect
+fn intersects<T: Float>(p1: &Node<T>, q1: &Node<T>, p2: &Node<T>, q2: &Node<T>) -> bool {
+    segments_intersect(p1.xy, q1.xy, p2.xy, q2.xy)
+}
+
+/// Check if the segments `a1`-`a2` and `b1`-`b2` intersect.
+///
+/// Touching counts: segments that share an endpoint, that end on the other segment or that
+/// overlap while collinear intersect. Uses the same orientation tests as the triangulation, so
+/// with the `robust` feature the answer is exact.
+pub fn segments_intersect<T: Float>(a1: [T; 2], a2: [T; 2], b1: [T; 2], b2: [T; 2]) -> bool {
+    let o1 = sign(area_xy(a1, a2, b1));
+    let o2 = sign(area_xy(a1, a2, b2));
+    let o3 = sign(area_xy(b1, b2, a1));
+    let o4 = sign(area_xy(b1, b2, a2));
+
+    // general case
+    if o1 != o2 && o3 != o4 {
+        return true;
+    }
+
+    // a1, a2 and b1 are collinear and b1 lies on a1a2
+    if o1 == 0 && on_segment(a1, b1, a2) {
+        return true;
+    }
+    // a1, a2 and b2 are collinear and b2 lies on a1a2
+    if o2 == 0 && on_segment(a1, b2, a2) {
+        return true;
+    }
+    // b1, b2 and a1 are collinear and a1 lies on b1b2
+    if o3 == 0 && on_segment(b1, a1, b2) {
+        return true;
+    }
+    // b1, b2 and a2 are collinear and a2 lies on b1b2
+    if o4 == 0 && on_segment(b1, a2, b2) {
+        return true;
+    }
+
+    false
+}
+
+/// 0 if num is 0, 1 if num is positive, -1 if num is negative
+fn sign<T: Float>(num: T) -> i8 {
+    if num > T::zero() {
+        1
+    } else if num < T::zero() {
+        -1
+    } else {
+        0
+    }
+}
+
+/// for collinear points p, q, r, check if point q lies on segment pr
+fn on_segment<T: Float>(p: [T; 2], q: [T; 2], r: [T; 2]) -> bool {
+    q[0] <= p[0].max(r[0])
+        && q[0] >= p[0].min(r[0])
+        && q[1] <= p[1].max(r[1])
+        && q[1] >= p[1].min(r[1])
+}
+
+/// check if a polygon diagonal intersects any polygon segments
+fn intersects_polygon<T: Float>(nodes: &[Node<T>], a_i: usize, b_i: usize) -> bool {
+    let a = node!(nodes, a_i);
+    let b = node!(nodes, b_i);
+    let mut p_i = a_i;
+    loop {
+        let p = node!(nodes, p_i);
+        let p_next = node!(nodes, p.next_i);
+        if p.i != a.i
+            && p_next.i != a.i
+            && p.i != b.i
+            && p_next.i != b.i
+            && intersects(p, p_next, a, b)
+        {
+            return true;
+        }
+        p_i = p.next_i;
+        if p_i == a_i {
+            break;
+        }
+    }
+
+    false
+}
+
+/// check if a polygon diagonal is locally inside the polygon
+fn locally_inside<T: Float>(nodes: &[Node<T>], a_i: usize, b_i: usize) -> bool {
+    let a = node!(nodes, a_i);
+    let b = node!(nodes, b_i);
+    let a_prev = node!(nodes, a.prev_i);
+    let a_next = node!(nodes, a.next_i);
+    if area(a_prev, a, a_next) < T::zero() {
+        area(a, b, a_next) >= T::zero() && area(a, a_prev, b) >= T::zero()
+    } else {
+        area(a, b, a_prev) < T::zero() || area(a, a_next, b) < T::zero()
+    }
+}
+
+/// check if the middle point of a polygon diagonal is inside the polygon
+fn middle_inside<T: Float>(nodes: &[Node<T>], a_i: usize, b_i: usize) -> bool {
+    let a = node!(nodes, a_i);
+    let b = node!(nodes, b_i);
+    let two = T::one() + T::one();
+    let px = (a.xy[0] + b.xy[0]) / two;
+    let py = (a.xy[1] + b.xy[1]) / two;
+    let mut inside = false;
+    let mut p_i = a_i;
+    loop {
+        let p = node!(nodes, p_i);
+        let p_next = node!(nodes, p.next_i);
+        if (p.xy[1] > py) != (p_next.xy[1] > py)
+            && p_next.xy[1] != p.xy[1]
+            && px < (p_next.xy[0] - p.xy[0]) * (py - p.xy[1]) / (p_next.xy[1] - p.xy[1]) + p.xy[0]
+        {
+            inside = !inside;
+        }
+        p_i = p.next_i;
+        if p_i == a_i {
+            break;
+        }
+    }
+
+    inside
+}
+
+/// link two polygon vertices with a bridge; if the vertices belong to the same ring, it splits
+/// polygon into two; if one belongs to the outer ring and another to a hole, it merges it into a
+/// single ring
+fn split_polygon<T: Float, A: NodeArena<T>>(nodes: &mut A, a_i: usize, b_i: usize) -> usize {
+    let a = *node!(nodes, a_i);
+    let b = *node!(nodes, b_i);
+    let a2_i = nodes.len();
+    nodes.push(Node::new(a.i, a.xy, a2_i));
+    let b2_i = nodes.len();
+    nodes.push(Node::new(b.i, b.xy, b2_i));
+    let an_i = a.next_i;
+    let bp_i = b.prev_i;
+
+    node_mut!(nodes, a_i).next_i = b_i;
+    node_mut!(nodes, b_i).prev_i = a_i;
+
+    node_mut!(nodes, a2_i).next_i = an_i;
+    node_mut!(nodes, an_i).prev_i = a2_i;
+
+    node_mut!(nodes, b2_i).next_i = a2_i;
+    node_mut!(nodes, a2_i).prev_i = b2_i;
+
+    node_mut!(nodes, bp_i).next_i = b2_i;
+    node_mut!(nodes, b2_i).prev_i = bp_i;
+
+    b2_i
+}
+
+/// create a node and optionally link it with previous one (in a circular doubly linked list)
+fn insert_node<T: Float, A: NodeArena<T>>(
+    nodes: &mut A,
+    i: usize,
+    xy: [T; 2],
+    last_i: Option<usize>,
+) -> usize {
+    let p_i = nodes.len();
+    let mut p = Node::new(i, xy, p_i);
+
+    if let Some(last_i) = last_i {
+        let last_next_i = node!(nodes, last_i).next_i;
+        p.next_i = last_next_i;
+        p.prev_i = last_i;
+        node_mut!(nodes, last_next_i).prev_i = p_i;
+        node_mut!(nodes, last_i).next_i = p_i;
+    }
+    nodes.push(p);
+
+    p_i
+}
+
+/// unlink a node from the polygon ring and the z-order list
+fn remove_node<T: Float>(nodes: &mut [Node<T>], p_i: usize) {
+    let p = *node!(nodes, p_i);
+    node_mut!(nodes, p.next_i).prev_i = p.prev_i;
+    node_mut!(nodes, p.prev_i).next_i = p.next_i;
+
+    if let Some(prev_z_i) = p.prev_z_i {
+        node_mut!(nodes, prev_z_i).next_z_i = p.next_z_i;
+    }
+    if let Some(next_z_i) = p.next_z_i {
+        node_mut!(nodes, next_z_i).prev_z_i = p.prev_z_i;
+    }
+}
+
+/// Remove the duplicate and collinear points of a flattened ring.
+///
+/// The shape of the ring is preserved: only points that sit on the straight line between their
+/// neighbours, or that repeat the next point, are dropped, which leaves fewer vertices (and so
+/// fewer triangles) to triangulate. The remaining points keep their order and all of their
+/// coordinates. A ring without area collapses to fewer than three points.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+/// - `epsilon`: if provided, also drop points at most this far from the line between their
+///   neighbours
+pub fn simplify_ring<T: Float>(ring: &[T], dim: usize, epsilon: Option<T>) -> Vec<T> {
+    let mut nodes = Vec::with_capacity(ring.len() / dim);
+    let mut last_i = None;
+    for (i, point) in ring.chunks_exact(dim).enumerate() {
+        last_i = Some(insert_node(&mut nodes, i, [point[0], point[1]], last_i));
+    }
+    let Some(last_i) = last_i else {
+        return Vec::new();
+    };
+    let start_i = match epsilon {
+        Some(epsilon) if epsilon > T::zero() => {
+            filter_points_by(&mut nodes, last_i, None, |prev, p, next| {
+                let [dx, dy] = [next.xy[0] - prev.xy[0], next.xy[1] - prev.xy[1]];
+                let cross = area(prev, p, next);
+                equals(p, next) || cross * cross <= epsilon * epsilon * (dx * dx + dy * dy)
+            })
+        }
+        _ => filter_points(&mut nodes, last_i, None),
+    };
+
+    ring_from_nodes(&nodes, start_i, ring, dim)
+}
+
+/// Remove the spikes of a flattened ring: points where the ring runs out along a line and
+/// straight back, enclosing no area.
+///
+/// Simplified coastlines often have them, and as their two edges overlap they can keep the
+/// triangulation from finding ears until its fallback passes. A spike's tip is removed along
+/// with the duplicate point it leaves behind at its base, repeatedly, so spikes of several
+/// points go too, as do repeated points. The remaining points keep their order and all of their
+/// coordinates; unlike [`simplify_ring`], other collinear points are kept.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+pub fn remove_spikes<T: Float>(ring: &[T], dim: usize) -> Vec<T> {
+    let mut nodes = Vec::with_capacity(ring.len() / dim);
+    let mut last_i = None;
+    for (i, point) in ring.chunks_exact(dim).enumerate() {
+        last_i = Some(insert_node(&mut nodes, i, [point[0], point[1]], last_i));
+    }
+    let Some(last_i) = last_i else {
+        return Vec::new();
+    };
+    let start_i = filter_points_by(&mut nodes, last_i, None, |prev, p, next| {
+        let turns_back = (p.xy[0] - prev.xy[0]) * (next.xy[0] - p.xy[0])
+            + (p.xy[1] - prev.xy[1]) * (next.xy[1] - p.xy[1])
+            < T::zero();
+        equals(p, next) || (area(prev, p, next).is_zero() && turns_back)
+    });
+
+    ring_from_nodes(&nodes, start_i, ring, dim)
+}
+
+/// the points of `ring` still linked from `start_i`, from the first in input order on
+fn ring_from_nodes<T: Float>(nodes: &[Node<T>], start_i: usize, ring: &[T], dim: usize) -> Vec<T> {
+    let mut first_i = start_i;
+    let mut p_i = node!(nodes, start_i).next_i;
+    while p_i != start_i {
+        if node!(nodes, p_i).i < node!(nodes, first_i).i {
+            first_i = p_i;
+        }
+        p_i = node!(nodes, p_i).next_i;
+    }
+    let mut simplified = Vec::new();
+    let mut p_i = first_i;
+    loop {
+        let i = node!(nodes, p_i).i;
+        simplified.extend_from_slice(&ring[i * dim..(i + 1) * dim]);
+        p_i = node!(nodes, p_i).next_i;
+        if p_i == first_i {
+            break;
+        }
+    }
+
+    simplified
+}
+
+/// Twice the signed area of the ring stored in `data[start..end]`, see [`ring_area`] for the
+/// area of a whole ring slice.
+///
+/// ## Parameters
+/// - `data`: the flattened vertices
+/// - `start`: coordinate index the ring starts at
+/// - `end`: coordinate index the ring ends at (exclusive)
+/// - `dim`: the number of coordinates per vertex
+pub fn signed_area<T: Float>(data: &[T], start: usize, end: usize, dim: usize) -> T {
+    if end < start + dim {
+        return T::zero();
+    }
+    let mut sum = T::zero();
+    let mut j = end - dim;
+    for i in (start..end).step_by(dim) {
+        sum = sum + (data[j] - data[i]) * (data[i + 1] + data[j + 1]);
+        j = i;
+    }
+
+    sum
+}
+
+/// Whether a flattened ring is wound clockwise.
+///
+/// Clockwise is meant in a y-down (screen or tile) coordinate system, the orientation
+/// [`earcut`] itself uses for outer rings; in y-up coordinates the same ring appears
+/// counter-clockwise. Outer rings and holes should have opposite windings, but the algorithm
+/// re-orients each ring itself, so either winding is accepted. Zero-area rings are neither
+/// clockwise nor counter-clockwise.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+pub fn is_clockwise<T: Float>(ring: &[T], dim: usize) -> bool {
+    signed_area(ring, 0, ring.len(), dim) > T::zero()
+}
+
+/// Whether a flattened ring is wound counter-clockwise; see [`is_clockwise`] for the convention
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+pub fn is_counter_clockwise<T: Float>(ring: &[T], dim: usize) -> bool {
+    signed_area(ring, 0, ring.len(), dim) < T::zero()
+}
+
+/// Rewind the rings of a flattened polygon in place so the outer ring is [`is_clockwise`] and
+/// the holes are [`is_counter_clockwise`], whatever winding they came with.
+///
+/// [`earcut`] re-orients every ring by itself, so it doesn't need this. It is for data sources
+/// that wind all rings the same way, when the output has to meet [`try_earcut_strict`] or be
+/// handed to other tools that rely on the winding. Zero-area rings are left as they are.
+///
+/// ## Parameters
+/// - `data`: the polygon vertices flattened, e.g. `[x0, y0, x1, y1, ...]`
+/// - `hole_indices`: the vertex index each hole ring starts at
+/// - `dim`: the number of coordinates per vertex
+pub fn normalize_winding<T: Float, N: Index>(data: &mut [T], hole_indices: &[N], dim: usize) {
+    let starts = core::iter::once(0).chain(hole_indices.iter().map(|i| i.into_usize() * dim));
+    let ends = hole_indices
+        .iter()
+        .map(|i| i.into_usize() * dim)
+        .chain(core::iter::once(data.len()));
+    for (ring, (start, end)) in starts.zip(ends).enumerate() {
+        let area = signed_area(data, start, end, dim);
+        let wound_right = if ring == 0 {
+            area >= T::zero()
+        } else {
+            area <= T::zero()
+        };
+        if !wound_right {
+            let ring = &mut data[start..end];
+            ring.reverse();
+            // reversing the coordinates also reversed each vertex, so restore those
+            ring.chunks_exact_mut(dim).for_each(<[T]>::reverse);
+        }
+    }
+}
+
+/// The signed area of a complete flattened ring.
+///
+/// Positive for rings wound [`is_clockwise`] (counter-clockwise in y-up coordinates), negative
+/// for the opposite winding. Unlike [`signed_area`], which takes coordinate offsets and returns
+/// twice the area, this is the actual area of the ring.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+pub fn ring_area<T: Float>(ring: &[T], dim: usize) -> T {
+    signed_area(ring, 0, ring.len(), dim) / (T::one() + T::one())
+}
+
+/// Whether a flattened ring crosses or touches itself.
+///
+/// Every pair of non-adjacent edges is run through the same segment test the triangulation
+/// uses to cure local intersections, so touching edges and vertices lying on another edge
+/// count as intersections too. Consecutive duplicate points and a closing point equal to the
+/// first are ignored. Large rings are swept left to right with the Shamos-Hoey algorithm, which
+/// only compares edges that are neighbours along the sweep line, in O(n log n) comparisons.
+///
+/// ## Parameters
+/// - `ring`: the flattened ring vertices
+/// - `dim`: the number of coordinates per vertex
+pub fn has_self_intersections<T: Float>(ring: &[T], dim: usize) -> bool {
+    if dim < 2 {
+        return false;
+    }
+    let mut points: Vec<Node<T>> = Vec::with_capacity(ring.len() / dim);
+    for (i, xy) in ring.chunks_exact(dim).enumerate() {
+        let node = Node::new(i, [xy[0], xy[1]], points.len());
+        if points.last().is_none_or(|last| !equals(last, &node)) {
+            points.push(node);
+        }
+    }
+    while points.len() > 1 && equals(&points[0], &points[points.len() - 1]) {
+        points.pop();
+    }
+    let len = points.len();
+    if len < 4 {
+        return false;
+    }
+
+    // edges i and j share a vertex if they follow each other around the ring
+    let adjacent = |i: usize, j: usize| (i + 1) % len == j || (j + 1) % len == i;
+    let crosses = |i: usize, j: usize| {
+        !adjacent(i, j)
+            && intersects(
+                &points[i],
+                &points[(i + 1) % len],
+                &points[j],
+                &points[(j + 1) % len],
+            )
+    };
+
+    if len <= 64 {
+        return (0..len).any(|i| (i + 1..len).any(|j| crosses(i, j)));
+    }
+
+    // Shamos-Hoey sweep: an edge enters the active list at its left end and leaves it at its
+    // right end, the list stays ordered bottom to top, and only edges that become neighbours in
+    // it are tested; the leftmost crossing always shows up between neighbours before the sweep
+    // passes it, so this takes O(n log n) comparisons
+    let cmp_xy =
+        |a: [T; 2], b: [T; 2]| cmp_coordinate(a[0], b[0]).then_with(|| cmp_coordinate(a[1], b[1]));
+    let ends: Vec<[[T; 2]; 2]> = (0..len)
+        .map(|i| {
+            let (a, b) = (points[i].xy, points[(i + 1) % len].xy);
+            if cmp_xy(a, b) == Ordering::Greater {
+                [b, a]
+            } else {
+                [a, b]
+            }
+        })
+        .collect();
+    // which side of edge `e` the point `p` on the sweep line lies: 1 above, -1 below, 0 on it
+    let side = |e: usize, p: [T; 2]| -> i8 {
+        let [l, r] = ends[e];
+        if l[0] == r[0] {
+            if p[1] < l[1] {
+                -1
+            } else if p[1] > r[1] {
+                1
+            } else {
+                0
+            }
+        } else {
+            -sign(area_xy(l, r, p))
+        }
+    };
+    // whether edge `e` goes below edge `s` entering at its left end; through the same point the
+    // steeper edge goes above, and vertical edges are the steepest
+    let vertical = |e: usize| ends[e][0][0] == ends[e][1][0];
+    let below = |e: usize, s: usize| match side(e, ends[s][0]) {
+        0 => vertical(s) || (!vertical(e) && side(e, ends[s][1]) >= 0),
+        side => side > 0,
+    };
+
+    // events sorted along the sweep, edges entering a point before those leaving it
+    let event_point = |(i, enter): (usize, bool)| ends[i][usize::from(!enter)];
+    let mut events: Vec<(usize, bool)> = (0..len).flat_map(|i| [(i, true), (i, false)]).collect();
+    events.sort_by(|&a, &b| cmp_xy(event_point(a), event_point(b)).then(b.1.cmp(&a.1)));
+    let mut active: Vec<usize> = Vec::new();
+    for group in events.chunk_by(|&a, &b| cmp_xy(event_point(a), event_point(b)).is_eq()) {
+        let point = event_point(group[0]);
+        for &(i, _) in group.iter().filter(|(_, enter)| *enter) {
+            let k = active.partition_point(|&e| below(e, i));
+            if (k > 0 && crosses(active[k - 1], i)) || active.get(k).is_some_and(|&e| crosses(e, i))
+            {
+                return true;
+            }
+            active.insert(k, i);
+        }
+
+        // the edges through the event point all touch there, which neighbour tests alone can
+        // miss when the edges in between share vertices with their neighbours; three edges of
+        // a ring through one point always include two that don't
+        let lo = active.partition_point(|&e| side(e, point) > 0);
+        let through = active[lo..].iter().take_while(|&&e| side(e, point) == 0);
+        match through.count() {
+            0 | 1 => {}
+            2 => {
+                if crosses(active[lo], active[lo + 1]) {
+                    return true;
+                }
+            }
+            _ => return true,
+        }
+
+        for &(i, _) in group.iter().filter(|(_, enter)| !*enter) {
+            let k = active[lo..]
+                .iter()
+                .position(|&e| e == i)
+                .map(|k| lo + k)
+                .or_else(|| active.iter().position(|&e| e == i));
+            let Some(k) = k else { continue };
+            active.remove(k);
+            if k > 0 && k < active.len() && crosses(active[k - 1], active[k]) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn morton_code_matches_z_order_sort() {
+        let data = [
+            0.0, 0.0, 7.0, 1.0, 9.0, 9.0, 3.0, 6.0, 1.0, 8.0, 5.0, 4.0, 8.0, 2.0, 2.0, 3.0,
+        ];
+        let (min, max) = ([0.0, 0.0], [9.0, 9.0]);
+        let inv_size = morton_inv_size(min, max);
+        assert_eq!(inv_size, 1.0 / 9.0);
+
+        let mut nodes = Vec::new();
+        let start_i = linked_list(&mut nodes, &data, 0, data.len(), 2, true, false).unwrap();
+        index_curve(&mut nodes, start_i, min[0], min[1], 32767.0 * inv_size);
+        // walk the z-order list from its head
+        let mut p_i = start_i;
+        while let Some(prev_z_i) = nodes[p_i].prev_z_i {
+            p_i = prev_z_i;
+        }
+        let mut sorted = vec![nodes[p_i].i];
+        while let Some(next_z_i) = nodes[p_i].next_z_i {
+            p_i = next_z_i;
+            sorted.push(nodes[p_i].i);
+        }
+
+        let mut expected: Vec<usize> = (0..data.len() / 2).collect();
+        expected.sort_by_key(|&i| morton_code([data[i * 2], data[i * 2 + 1]], min, inv_size));
+        assert_eq!(sorted, expected);
+        assert_eq!(morton_code([0.0, 0.0], min, inv_size), 0);
+        assert_eq!(morton_code([9.0, 9.0], min, inv_size), 0x3FFF_FFFF);
+    }
+
+    #[test]
+    fn z_order_f32_matches_f64() {
+        // coordinates and scales exact in f32, up to the full 21 bits per axis
+        let mut seed = 7_u64;
+        let mut random = |bits: u32| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as u32 & ((1 << bits) - 1)
+        };
+        for (bits, min, z_scale) in [(21, 0.0, 1.0), (20, -4096.0, 2.0), (15, 1.5, 0.125)] {
+            let points: Vec<[u32; 2]> = (0..2000).map(|_| [random(bits), random(bits)]).collect();
+            let code_f64 = |[x, y]: [u32; 2]| {
+                let xy = [f64::from(x) / z_scale + min, f64::from(y) / z_scale + min];
+                z_order(xy, [min; 2], z_scale)
+            };
+            let code_f32 = |[x, y]: [u32; 2]| {
+                let (min, z_scale) = (min as f32, z_scale as f32);
+                let xy = [x as f32 / z_scale + min, y as f32 / z_scale + min];
+                z_order(xy, [min; 2], z_scale)
+            };
+
+            let mut by_f64: Vec<usize> = (0..points.len()).collect();
+            by_f64.sort_by_key(|&i| (code_f64(points[i]), i));
+            let mut by_f32: Vec<usize> = (0..points.len()).collect();
+            by_f32.sort_by_key(|&i| (code_f32(points[i]), i));
+            assert_eq!(by_f32, by_f64);
+            assert!(points.iter().all(|&p| code_f32(p) == code_f64(p)));
+        }
+    }
+
+    /// the z-order code as it was computed through `to_u64` and a bit mask
+    fn z_order_u64<T: Float>(xy: [T; 2], min: [T; 2], z_scale: T) -> i64 {
+        let x = ((xy[0] - min[0]) * z_scale).to_u64().unwrap_or(0);
+        let y = ((xy[1] - min[1]) * z_scale).to_u64().unwrap_or(0);
+        interleave((x & 0x1F_FFFF) as u32, (y & 0x1F_FFFF) as u32)
+    }
+
+    #[test]
+    fn z_order_matches_u64_reference() {
+        let mut seed = 11_u64;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64
+        };
+        for (min, z_scale) in [(0.0, 1.0), (-4096.0, 2.0), (1.5, 0.125), (-0.3, 12345.678)] {
+            // anywhere inside the 21 bit range
+            let span = f64::from(0x1F_FFFF) / z_scale;
+            for _ in 0..10_000 {
+                let xy = [min + random() * span, min + random() * span];
+                assert_eq!(
+                    z_order(xy, [min; 2], z_scale),
+                    z_order_u64(xy, [min; 2], z_scale)
+                );
+                let xy = xy.map(|c| c as f32);
+                let (min, z_scale) = (min as f32, z_scale as f32);
+                assert_eq!(
+                    z_order(xy, [min; 2], z_scale),
+                    z_order_u64(xy, [min; 2], z_scale)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn z_order_clamps_out_of_range() {
+        let last = 0x3FF_FFFF_FFFF;
+        // below the box both give cell 0
+        assert_eq!(z_order([-1.0, -1.0], [0.0; 2], 1.0), 0);
+        assert_eq!(z_order_u64([-1.0, -1.0], [0.0; 2], 1.0), 0);
+        // past the 21 bit range the cells clamp to the last one, where they used to wrap
+        let past = f64::from(0x20_0000);
+        assert_eq!(z_order([past, past], [0.0; 2], 1.0), last);
+        assert_eq!(z_order_u64([past, past], [0.0; 2], 1.0), 0);
+        assert_eq!(z_order([4.0e6_f32, 4.0e6], [0.0; 2], 1.0), last);
+        assert_eq!(
+            z_order([f64::INFINITY, 3.0], [0.0; 2], 1.0),
+            0x155_5555_555F
+        );
+        // each axis clamps on its own
+        assert_eq!(z_order([past, 0.0], [0.0; 2], 1.0), 0x155_5555_5555);
+        assert_eq!(z_order([0.0, past], [0.0; 2], 1.0), 0x2AA_AAAA_AAAA);
+        assert_eq!(z_order([f64::NAN, f64::NAN], [0.0; 2], 1.0), 0);
+    }
 }
@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+
+use crate::{earcut, Index};
+
+/// Largest raw magnitude for which the ear clipping orientation tests are exact
+pub const FIXED_POINT_EXACT_RANGE: i64 = 1 << 25;
+
+/// A fixed-point (or integer) coordinate backed by a raw integer.
+///
+/// Implement this for your own fixed-point or decimal type (e.g. a `rust_decimal::Decimal`
+/// rescaled to a constant number of fraction digits) by returning its mantissa. All coordinates
+/// of a polygon must share the same scale.
+pub trait FixedPoint: Copy {
+    /// The raw integer value of the coordinate
+    fn to_raw(self) -> i64;
+}
+
+impl FixedPoint for i16 {
+    fn to_raw(self) -> i64 {
+        self as i64
+    }
+}
+impl FixedPoint for i32 {
+    fn to_raw(self) -> i64 {
+        self as i64
+    }
+}
+impl FixedPoint for i64 {
+    fn to_raw(self) -> i64 {
+        self
+    }
+}
+
+/// Triangulate a flattened fixed-point polygon.
+///
+/// Every raw value must lie within ±[`FIXED_POINT_EXACT_RANGE`], which is checked in debug
+/// builds. The raw values then convert to `f64` without loss, and the orientation and
+/// point-in-triangle tests of the ear clipping are exact, as every product stays below 2^53.
+/// The hole bridge's ray intersection and the z-order hashing still round like regular `f64`
+/// triangulation.
+///
+/// See [`earcut`] for the meaning of the parameters.
+pub fn earcut_fixed<P: FixedPoint, N: Index>(data: &[P], hole_indices: &[N], dim: usize) -> Vec<N> {
+    let raw: Vec<f64> = data
+        .iter()
+        .map(|v| {
+            let raw = v.to_raw();
+            debug_assert!(
+                raw.unsigned_abs() <= FIXED_POINT_EXACT_RANGE as u64,
+                "raw coordinate {raw} outside FIXED_POINT_EXACT_RANGE"
+            );
+            raw as f64
+        })
+        .collect();
+    earcut(&raw, hole_indices, dim)
+}
+
+/// Exact area of a fixed-point triangulation, doubled so it stays an integer.
+///
+/// The result is in raw units squared; divide by two (and the scale squared) to get the area.
+pub fn fixed_double_area<P: FixedPoint, N: Index>(data: &[P], triangles: &[N], dim: usize) -> i128 {
+    let mut area = 0;
+    for &triangle in triangles.as_chunks::<3>().0 {
+        let [a, b, c] = triangle.map(|i| i.into_usize() * dim);
+        let [ax, ay, bx, by, cx, cy] =
+            [a, a + 1, b, b + 1, c, c + 1].map(|i| data[i].to_raw() as i128);
+        area += ((bx - ax) * (cy - ay) - (cx - ax) * (by - ay)).abs();
+    }
+
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a coordinate with three fraction digits
+    #[derive(Clone, Copy)]
+    struct Milli(i64);
+
+    impl FixedPoint for Milli {
+        fn to_raw(self) -> i64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn square() {
+        // 1.001 x 1.001 square
+        let data = [0, 0, 1001, 0, 1001, 1001, 0, 1001].map(Milli);
+        let triangles: Vec<u32> = earcut_fixed(&data, &[], 2);
+        assert_eq!(triangles.len(), 6);
+        assert_eq!(fixed_double_area(&data, &triangles, 2), 2 * 1001 * 1001);
+    }
+
+    #[test]
+    fn integer_square_with_hole() {
+        let data: [i32; 16] = [0, 0, 10, 0, 10, 10, 0, 10, 2, 2, 2, 8, 8, 8, 8, 2];
+        let triangles: Vec<usize> = earcut_fixed(&data, &[4], 2);
+        assert_eq!(triangles.len(), 24);
+        assert_eq!(fixed_double_area(&data, &triangles, 2), 2 * (100 - 36));
+    }
+
+    #[test]
+    fn bridged_hole_at_range_limit() {
+        // the hole's leftmost vertex sits off the outer ring's vertex rows, so the bridge is found
+        // through the ray intersection rather than a shared coordinate
+        let r = FIXED_POINT_EXACT_RANGE;
+        let data = [
+            -r,
+            -r,
+            r,
+            -r,
+            r,
+            r,
+            -r,
+            r, // outer
+            -r / 3,
+            7,
+            r / 2,
+            -r / 2 + 1,
+            r / 2,
+            r / 2 - 1, // hole
+        ]
+        .map(Milli);
+        let triangles: Vec<u32> = earcut_fixed(&data, &[4], 2);
+        assert_eq!(triangles.len(), 3 * (4 + 3));
+        let outer = 2 * (2 * r as i128) * (2 * r as i128);
+        let hole = fixed_double_area(&data[8..], &[0u32, 1, 2], 2);
+        assert_eq!(fixed_double_area(&data, &triangles, 2), outer - hole);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "outside FIXED_POINT_EXACT_RANGE")]
+    fn out_of_range() {
+        let r = FIXED_POINT_EXACT_RANGE + 1;
+        let _: Vec<u32> = earcut_fixed(&[0, 0, r, 0, r, r].map(Milli), &[], 2);
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::flatten_float;
+
+/// A flattened polygon, ready to hand to [`crate::earcut`].
+///
+/// With the `serde` feature enabled it can be (de)serialized to persist triangulation inputs.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FlatPolygon {
+    /// The flattened vertices
+    pub vertices: Vec<f64>,
+    /// The vertex index each hole ring starts at
+    pub hole_indices: Vec<usize>,
+    /// The number of coordinates per vertex
+    pub dim: usize,
+}
+impl FlatPolygon {
+    /// Flatten a nested polygon, see [`crate::flatten`]
+    pub fn from_nested(data: &[Vec<Vec<f64>>]) -> Self {
+        let (vertices, hole_indices, dim) = flatten_float(data);
+        Self {
+            vertices,
+            hole_indices,
+            dim,
+        }
+    }
+
+    /// Split the vertices back into nested rings of points
+    pub fn to_nested(&self) -> Vec<Vec<Vec<f64>>> {
+        if self.dim == 0 || self.vertices.is_empty() {
+            return Vec::new();
+        }
+        let len = self.vertices.len() / self.dim;
+        let starts = core::iter::once(0).chain(self.hole_indices.iter().copied());
+        let ends = self
+            .hole_indices
+            .iter()
+            .copied()
+            .chain(core::iter::once(len));
+        starts
+            .zip(ends)
+            .map(|(start, end)| {
+                self.vertices[start * self.dim..end * self.dim]
+                    .chunks(self.dim)
+                    .map(|point| point.to_vec())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn polygon() -> Vec<Vec<Vec<f64>>> {
+        vec![
+            vec![
+                vec![0.0, 0.0],
+                vec![10.0, 0.0],
+                vec![10.0, 10.0],
+                vec![0.0, 10.0],
+            ],
+            vec![
+                vec![2.0, 2.0],
+                vec![2.0, 8.0],
+                vec![8.0, 8.0],
+                vec![8.0, 2.0],
+            ],
+        ]
+    }
+
+    #[test]
+    fn nested_round_trip() {
+        let flat = FlatPolygon::from_nested(&polygon());
+        assert_eq!(flat.hole_indices, vec![4]);
+        assert_eq!(flat.dim, 2);
+        assert_eq!(flat.to_nested(), polygon());
+        assert!(FlatPolygon::default().to_nested().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let flat = FlatPolygon::from_nested(&polygon());
+        let json = serde_json::to_string(&flat).unwrap();
+        assert_eq!(serde_json::from_str::<FlatPolygon>(&json).unwrap(), flat);
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+use geo_types::{LineString, MultiPolygon, Polygon};
+
+use crate::{earcut, is_clockwise};
+
+/// Flatten a `geo` polygon into earcut's input: its vertices, hole indices and dimension.
+///
+/// The closing point `geo` repeats at the end of each ring is dropped, and rings are
+/// normalized to `geo`'s convention: a counter-clockwise exterior (in y-up coordinates) and
+/// clockwise interiors.
+pub fn from_geo_polygon(polygon: &Polygon<f64>) -> (Vec<f64>, Vec<usize>, usize) {
+    let mut vertices = Vec::new();
+    let mut hole_indices = Vec::with_capacity(polygon.interiors().len());
+    push_ring(&mut vertices, polygon.exterior(), true);
+    for interior in polygon.interiors() {
+        hole_indices.push(vertices.len() / 2);
+        push_ring(&mut vertices, interior, false);
+    }
+
+    (vertices, hole_indices, 2)
+}
+
+/// Triangulate every polygon of a `geo` multi polygon, treating interiors as holes.
+///
+/// ## Returns
+/// The flattened vertices and triangle indices of each polygon, in order
+pub fn triangulate_geo(multi_polygon: &MultiPolygon<f64>) -> Vec<(Vec<f64>, Vec<usize>)> {
+    multi_polygon
+        .iter()
+        .map(|polygon| {
+            let (vertices, hole_indices, dim) = from_geo_polygon(polygon);
+            let indices = earcut(&vertices, &hole_indices, dim);
+            (vertices, indices)
+        })
+        .collect()
+}
+
+/// append an open copy of the ring, wound counter-clockwise (y-up) if `exterior`
+fn push_ring(vertices: &mut Vec<f64>, ring: &LineString<f64>, exterior: bool) {
+    let start = vertices.len();
+    let mut coords = ring.0.as_slice();
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords = &coords[..coords.len() - 1];
+    }
+    for coord in coords {
+        vertices.push(coord.x);
+        vertices.push(coord.y);
+    }
+    // a positive signed area is counter-clockwise in y-up coordinates
+    if is_clockwise(&vertices[start..], 2) != exterior {
+        let ring = &mut vertices[start..];
+        ring.reverse();
+        // reversing the coordinates also swapped x and y of every point
+        for point in ring.as_chunks_mut::<2>().0 {
+            point.swap(0, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use geo_types::polygon;
+
+    fn square_with_hole() -> Polygon<f64> {
+        // the exterior is clockwise and the hole counter-clockwise, against geo's convention
+        polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 0.0, y: 10.0), (x: 10.0, y: 10.0), (x: 10.0, y: 0.0)],
+            interiors: [[(x: 2.0, y: 2.0), (x: 8.0, y: 2.0), (x: 8.0, y: 8.0), (x: 2.0, y: 8.0)]],
+        )
+    }
+
+    #[test]
+    fn flattens_and_normalizes() {
+        let (vertices, hole_indices, dim) = from_geo_polygon(&square_with_hole());
+        assert_eq!(dim, 2);
+        assert_eq!(hole_indices, vec![4]);
+        assert_eq!(
+            vertices,
+            vec![
+                10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 0.0, 0.0, // exterior
+                2.0, 8.0, 8.0, 8.0, 8.0, 2.0, 2.0, 2.0, // hole
+            ]
+        );
+    }
+
+    #[test]
+    fn triangulates_multi_polygon() {
+        let triangle = polygon![(x: 20.0, y: 0.0), (x: 30.0, y: 0.0), (x: 20.0, y: 10.0)];
+        let multi_polygon = MultiPolygon::new(vec![square_with_hole(), triangle]);
+        let results = triangulate_geo(&multi_polygon);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].1.len() / 3, 8);
+        assert_eq!(results[1].0, vec![20.0, 0.0, 30.0, 0.0, 20.0, 10.0]);
+        assert_eq!(results[1].1.len() / 3, 1);
+    }
+}
@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::{earcut_into, Index, Store, TriangleMesh};
+
+/// A 2D polygon built up one vertex at a time, e.g. from a digitizer, that can be triangulated
+/// after every step for a live preview.
+///
+/// The first ring is the outer ring and every later one is a hole. The working memory of the
+/// triangulation is kept between calls to [`IncrementalPolygon::triangulate`], so triangulating
+/// again after each vertex doesn't reallocate it.
+///
+/// ```
+/// use earclip::IncrementalPolygon;
+///
+/// let mut polygon = IncrementalPolygon::<f64, u32>::new();
+/// for point in [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]] {
+///     polygon.push_vertex(point);
+/// }
+/// polygon.begin_hole();
+/// for point in [[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]] {
+///     polygon.push_vertex(point);
+/// }
+/// assert_eq!(polygon.triangulate().area(), 12.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalPolygon<T: Float, N: Index> {
+    vertices: Vec<T>,
+    hole_indices: Vec<N>,
+    /// the vertex index the open ring starts at, if there is one
+    ring_start: Option<usize>,
+    store: Store<T>,
+}
+
+impl<T: Float, N: Index> Default for IncrementalPolygon<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, N: Index> IncrementalPolygon<T, N> {
+    /// Create an empty polygon
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            hole_indices: Vec::new(),
+            ring_start: None,
+            store: Store::default(),
+        }
+    }
+
+    /// Add a vertex to the open ring, starting a new ring if the last one was closed
+    pub fn push_vertex(&mut self, point: [T; 2]) {
+        if self.ring_start.is_none() {
+            let start = self.vertices.len() / 2;
+            if start > 0 {
+                self.hole_indices.push(N::from_usize(start));
+            }
+            self.ring_start = Some(start);
+        }
+        self.vertices.extend_from_slice(&point);
+    }
+
+    /// Close the open ring, dropping its last vertex if it repeats the first one. Open rings
+    /// are triangulated as if closed, so this only matters before starting the next ring.
+    pub fn close_ring(&mut self) {
+        let Some(start) = self.ring_start.take() else {
+            return;
+        };
+        let end = self.vertices.len();
+        if end >= (start + 2) * 2
+            && self.vertices[start * 2..start * 2 + 2] == self.vertices[end - 2..]
+        {
+            self.vertices.truncate(end - 2);
+        }
+    }
+
+    /// Close the open ring, so the next vertex starts a hole
+    pub fn begin_hole(&mut self) {
+        self.close_ring();
+    }
+
+    /// Remove all rings while keeping the allocations
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.hole_indices.clear();
+        self.ring_start = None;
+    }
+
+    /// The flattened vertices pushed so far
+    pub fn vertices(&self) -> &[T] {
+        &self.vertices
+    }
+
+    /// The vertex index each hole starts at
+    pub fn hole_indices(&self) -> &[N] {
+        &self.hole_indices
+    }
+
+    /// Triangulate the rings pushed so far, see [`crate::earcut`]
+    pub fn triangulate(&mut self) -> TriangleMesh<T, N> {
+        let mut indices = Vec::new();
+        earcut_into(
+            &self.vertices,
+            &self.hole_indices,
+            2,
+            &mut indices,
+            &mut self.store,
+        );
+        TriangleMesh {
+            vertices: self.vertices.clone(),
+            indices,
+            dim: 2,
+        }
+    }
+}
@@ -1,24 +1,1781 @@
 #![no_std]
 // #![deny(missing_docs)]
-//! The `earclip` Rust crate... TODO
+//! The `earclip` Rust crate triangulates polygons (with holes) using a modified ear slicing
+//! algorithm and can optionally tesselate the result along a modulo grid.
+//!
+//! ```
+//! use earclip::earclip;
+//!
+//! let polygon = vec![vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0, 1.0], vec![0.0, 1.0]]];
+//! let (vertices, indices) = earclip::<f64, usize>(&polygon, None, None);
+//! assert_eq!(vertices.len(), 8);
+//! assert_eq!(indices.len(), 6);
+//! ```
+//!
+//! `use earclip::prelude::*;` imports the functions and traits most callers need.
+//!
+//! ## Features
+//! - `std` (default): forwards to `num-traits/std` to use the platform's float math, and adds
+//!   the `to_svg_path` debugging helper
+//! - `serde`: derives `Serialize`/`Deserialize` for [`FlatPolygon`]
+//! - `rayon`: triangulates batches of polygons in parallel, see [`earclip_batch`]
+//! - `geo`: triangulates [`geo-types`](https://docs.rs/geo-types) polygons, as used by `geo`
+//! - `robust`: orientation tests with an exact sign, see [`orient2d`], so nearly degenerate
+//!   input can't flip them; triangulating typically takes about 1.5 times as long
+//! - `wasm`: packed buffer input and output for WebAssembly callers, see `earclip_packed`
+//! - `clip`: clips polygons to a rectangle, e.g. a tile, before triangulating with `clip_rect`
+//! - `wkt`: parses WKT `POLYGON` and `MULTIPOLYGON` strings with `parse_wkt_polygon`, e.g. for
+//!   quick tests and command line tools
+//! - `half`: converts vertex buffers to [`half`](https://docs.rs/half) precision with `to_f16`
+//!
+//! The crate itself is always `no_std` and only needs `alloc`. For bare-metal targets disable the
+//! default features (`default-features = false`) and provide a `#[global_allocator]`; float math
+//! then goes through `libm`.
 
 // https://github.com/MIERUNE/earcut-rs - not quite correct, but a good place to compare performance against
 
-/// Add two usize numbers into one
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+extern crate alloc;
+
+mod batch;
+mod builder;
+#[cfg(feature = "clip")]
+mod clip;
+mod cost;
+mod delaunay;
+mod earcut;
+mod fixed;
+mod flat;
+#[cfg(feature = "geo")]
+mod geo;
+mod incremental;
+mod mesh;
+mod normals;
+mod offset;
+#[cfg(feature = "half")]
+mod precision;
+pub mod prelude;
+mod quads;
+mod spherical;
+#[cfg(feature = "std")]
+mod svg;
+mod vec3;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weld;
+#[cfg(feature = "wkt")]
+mod wkt;
+
+pub use batch::*;
+pub use builder::*;
+#[cfg(feature = "clip")]
+pub use clip::*;
+pub use cost::*;
+pub use delaunay::*;
+pub use earcut::*;
+pub use fixed::*;
+pub use flat::*;
+#[cfg(feature = "geo")]
+pub use geo::*;
+pub use incremental::*;
+pub use mesh::*;
+pub use normals::*;
+pub use offset::*;
+#[cfg(feature = "half")]
+pub use precision::*;
+pub use quads::*;
+pub use spherical::*;
+#[cfg(feature = "std")]
+pub use svg::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+pub use weld::*;
+#[cfg(feature = "wkt")]
+pub use wkt::*;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::vec3::{cross, dot, sub};
+
+/// A point with x and y coordinates
+pub trait Point2D<T: Float> {
+    /// The x coordinate
+    fn x(&self) -> T;
+    /// The y coordinate
+    fn y(&self) -> T;
+}
+
+/// A point with x, y and z coordinates
+pub trait Point3D<T: Float> {
+    /// The x coordinate
+    fn x(&self) -> T;
+    /// The y coordinate
+    fn y(&self) -> T;
+    /// The z coordinate
+    fn z(&self) -> T;
+}
+
+impl<T: Float> Point2D<T> for [T; 2] {
+    fn x(&self) -> T {
+        self[0]
+    }
+    fn y(&self) -> T {
+        self[1]
+    }
+}
+
+impl<T: Float> Point2D<T> for (T, T) {
+    fn x(&self) -> T {
+        self.0
+    }
+    fn y(&self) -> T {
+        self.1
+    }
+}
+
+impl<T: Float> Point3D<T> for [T; 3] {
+    fn x(&self) -> T {
+        self[0]
+    }
+    fn y(&self) -> T {
+        self[1]
+    }
+    fn z(&self) -> T {
+        self[2]
+    }
+}
+
+impl<T: Float> Point3D<T> for (T, T, T) {
+    fn x(&self) -> T {
+        self.0
+    }
+    fn y(&self) -> T {
+        self.1
+    }
+    fn z(&self) -> T {
+        self.2
+    }
+}
+
+/// Convert a polygon of 2D point objects into the nested vector form `earclip` expects
+pub fn convert_2d<T: Float, P: Point2D<T>>(data: &[Vec<P>]) -> Vec<Vec<Vec<T>>> {
+    data.iter()
+        .map(|ring| ring.iter().map(|p| alloc::vec![p.x(), p.y()]).collect())
+        .collect()
+}
+
+/// Convert a polygon of 3D point objects into the nested vector form `earclip` expects
+pub fn convert_3d<T: Float, P: Point3D<T>>(data: &[Vec<P>]) -> Vec<Vec<Vec<T>>> {
+    data.iter()
+        .map(|ring| {
+            ring.iter()
+                .map(|p| alloc::vec![p.x(), p.y(), p.z()])
+                .collect()
+        })
+        .collect()
+}
+
+/// Flatten a polygon of 2D point objects straight into a vertex buffer, without the nested
+/// vector form [`convert_2d`] builds
+///
+/// ## Returns
+/// The flattened vertices, the vertex index each hole starts at, and the dimension (always 2)
+pub fn flatten_points2d<T: Float, N: Index, P: Point2D<T>>(
+    data: &[Vec<P>],
+) -> (Vec<T>, Vec<N>, usize) {
+    let (vertices, hole_indices) =
+        flatten_iter(data.iter().map(|ring| ring.iter().map(|p| [p.x(), p.y()])));
+    (vertices, hole_indices, 2)
+}
+
+/// Flatten a polygon of 3D point objects straight into a vertex buffer, without the nested
+/// vector form [`convert_3d`] builds
+///
+/// ## Returns
+/// The flattened vertices, the vertex index each hole starts at, and the dimension (always 3)
+pub fn flatten_points3d<T: Float, N: Index, P: Point3D<T>>(
+    data: &[Vec<P>],
+) -> (Vec<T>, Vec<N>, usize) {
+    let (vertices, hole_indices) = flatten_iter(
+        data.iter()
+            .map(|ring| ring.iter().map(|p| [p.x(), p.y(), p.z()])),
+    );
+    (vertices, hole_indices, 3)
+}
+
+/// An earcut polygon generator with tesselation support
+///
+/// ## Parameters
+/// - `polygon`: the polygon to triangulate; the first ring is the outer ring, the rest are holes
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+/// - `offset`: if provided, added to every output index
+///
+/// ## Returns
+/// The flattened vertices and the triangle indices
+///
+/// Without a `modulo` no vertices are added or reordered: the returned vertices are exactly the
+/// input points in input order (as produced by [`flatten`]), so every index maps 1:1 back to a
+/// point of `polygon`. Only tesselation appends new vertices, after the original ones.
+pub fn earclip<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<T>, Vec<N>) {
+    builder(modulo, offset).triangulate(polygon)
+}
+
+/// [`earclip`] with the outer ring and the holes passed separately, producing the same output
+/// as the positional form
+///
+/// ## Parameters
+/// - `outer`: the outer ring
+/// - `holes`: the holes cut out of the outer ring
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+/// - `offset`: if provided, added to every output index
+pub fn earclip_with_holes<T: Float, N: Index>(
+    outer: &[Vec<T>],
+    holes: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<T>, Vec<N>) {
+    builder(modulo, offset).triangulate_with_holes(outer, holes)
+}
+
+/// [`earclip`] with extra interior points the mesh must use as vertices, e.g. to get more evenly
+/// sized triangles or to pin the mesh to known heights.
+///
+/// A hole is a region cut out of the polygon: no triangle covers it. A steiner point cuts out
+/// nothing; the triangles around it just meet there, so the polygon stays covered in full. A
+/// hole ring of a single point acts as a steiner point too, this makes it explicit.
+///
+/// ## Parameters
+/// - `polygon`: the polygon to triangulate; the first ring is the outer ring, the rest are holes
+/// - `steiner_points`: points inside the polygon and outside its holes, with the same
+///   coordinates as the polygon's points; missing ones are zero
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+/// - `offset`: if provided, added to every output index
+///
+/// ## Returns
+/// The flattened vertices and the triangle indices. The steiner points follow the polygon's
+/// points in the vertices, in order, before any vertices tesselation adds.
+pub fn earclip_with_steiner<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    steiner_points: &[Vec<T>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<T>, Vec<N>) {
+    builder(modulo, offset).triangulate_with_steiner(polygon, steiner_points)
+}
+
+/// [`earclip`] for a polygon that is already flattened, skipping the nested `Vec` round trip
+///
+/// ## Parameters
+/// - `data`: the polygon vertices flattened, e.g. `[x0, y0, x1, y1, ...]`
+/// - `hole_indices`: the vertex index each hole ring starts at
+/// - `dim`: the number of coordinates per vertex
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+/// - `offset`: if provided, added to every output index
+pub fn earclip_flat<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<T>, Vec<N>) {
+    builder(modulo, offset).triangulate_flat(data, hole_indices, dim)
+}
+
+/// [`earclip`] returning each vertex as an `[x, y]` array instead of a flat buffer, as many
+/// graphics and math libraries expect. Coordinates past the second are dropped, see
+/// [`earclip_arrays_3d`] to keep the third.
+///
+/// See [`earclip`] for the meaning of the parameters.
+pub fn earclip_arrays<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<[T; 2]>, Vec<N>) {
+    let (vertices, indices) = earclip(polygon, modulo, offset);
+    let vertices = vertices
+        .chunks_exact(polygon_dim(polygon))
+        .map(|p| [p[0], p[1]])
+        .collect();
+    (vertices, indices)
+}
+
+/// [`earclip`] returning each vertex as an `[x, y, z]` array instead of a flat buffer, as many
+/// graphics and math libraries expect. 2D polygons get `z = 0`.
+///
+/// See [`earclip`] for the meaning of the parameters.
+pub fn earclip_arrays_3d<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> (Vec<[T; 3]>, Vec<N>) {
+    let (vertices, indices) = earclip(polygon, modulo, offset);
+    let dim = polygon_dim(polygon);
+    let vertices = vertices
+        .chunks_exact(dim)
+        .map(|p| [p[0], p[1], if dim > 2 { p[2] } else { T::zero() }])
+        .collect();
+    (vertices, indices)
+}
+
+/// the dimension [`flatten`] gives a polygon: 3 if its first point has a z, else 2
+fn polygon_dim<T>(polygon: &[Vec<Vec<T>>]) -> usize {
+    let first = polygon.first().and_then(|ring| ring.first());
+    if first.is_some_and(|point| point.len() > 2) {
+        3
+    } else {
+        2
+    }
+}
+
+/// an [`Earclip`] with the free functions' positional options
+fn builder<T: Float, N: Index>(modulo: Option<T>, offset: Option<N>) -> Earclip<T, N> {
+    let mut builder = Earclip::new();
+    if let Some(modulo) = modulo {
+        builder = builder.modulo(modulo);
+    }
+    if let Some(offset) = offset {
+        builder = builder.offset(offset);
+    }
+    builder
+}
+
+/// Checked version of [`earclip`] that returns an error instead of panicking on bad input.
+///
+/// See [`try_earcut`] for the validation performed.
+pub fn try_earclip<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    offset: Option<N>,
+) -> Result<(Vec<T>, Vec<N>), EarcutError> {
+    builder(modulo, offset).try_triangulate(polygon)
+}
+
+/// A triangulation whose [`deviation`] exceeded the caller's tolerance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation<T: Float> {
+    /// The measured deviation between the polygon and triangulation areas
+    pub deviation: T,
+    /// The tolerance that was exceeded
+    pub tolerance: T,
+}
+
+/// Like [`earclip`], but self-checks the triangulation.
+///
+/// After triangulating, the [`deviation`] of the mesh is computed and returned as a warning
+/// alongside the mesh if it exceeds `tolerance`. The check runs before tesselation, which
+/// doesn't change the covered area.
+///
+/// ## Parameters
+/// - `polygon`: the polygon to triangulate; the first ring is the outer ring, the rest are holes
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+/// - `tolerance`: the largest acceptable deviation
+pub fn earclip_checked<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+    tolerance: T,
+) -> (TriangleMesh<T, N>, Option<Deviation<T>>) {
+    let mut warning = None;
+    let (vertices, indices) = builder(modulo, None).triangulate_inspected(
+        polygon,
+        |vertices, hole_indices, dim, indices| {
+            let deviation = deviation(vertices, hole_indices, dim, indices);
+            warning = (deviation > tolerance).then_some(Deviation {
+                deviation,
+                tolerance,
+            });
+        },
+    );
+
+    (
+        TriangleMesh {
+            vertices,
+            indices,
+            dim: polygon_dim(polygon),
+        },
+        warning,
+    )
+}
+
+/// Tesselate the flattened polygon
+///
+/// ## Parameters
+/// - `vertices`: flattened vertices to append to
+/// - `indices`: triangle indices to append to
+/// - `modulo`: the grid spacing to split triangles along; non-positive or non-finite spacings
+///   do nothing
+/// - `dim`: the number of coordinates per vertex; every axis (including z for 3D data) is split
+///   and the remaining coordinates of new vertices are interpolated
+///
+/// ## Returns
+/// The number of vertices appended. New vertices always come after the original ones, so
+/// `vertices[..(vertices.len() / dim - added) * dim]` are the originals
+pub fn tesselate<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    modulo: T,
+    dim: usize,
+) -> usize {
+    tesselate_impl(vertices, indices, modulo, T::zero(), dim, None, None).unwrap_or_default()
+}
+
+/// [`tesselate`] along grid lines shifted away from the origin, at `origin + k * modulo` on
+/// every axis instead of `k * modulo`, e.g. tile boundaries at `256 * k + 128`
+///
+/// ## Returns
+/// The number of vertices appended, see [`tesselate`]
+pub fn tesselate_with_origin<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    modulo: T,
+    origin: T,
+    dim: usize,
+) -> usize {
+    tesselate_impl(vertices, indices, modulo, origin, dim, None, None).unwrap_or_default()
+}
+
+/// [`tesselate`] with a cap on the total number of vertices, to guard against a `modulo` that
+/// is tiny next to the polygon's extent.
+///
+/// Before each split, the number of vertices it could add is bounded from the triangle's
+/// extent; if that could take `vertices` past `max_vertices`, tesselation stops there. The mesh
+/// is left valid but only partly tesselated, and nothing is allocated for the skipped splits.
+///
+/// ## Returns
+/// The number of vertices appended, or [`EarcutError::VertexLimit`] if tesselation stopped
+pub fn try_tesselate<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    modulo: T,
+    dim: usize,
+    max_vertices: usize,
+) -> Result<usize, EarcutError> {
+    tesselate_impl(
+        vertices,
+        indices,
+        modulo,
+        T::zero(),
+        dim,
+        None,
+        Some(max_vertices),
+    )
+}
+
+/// Where a vertex of a tesselated mesh comes from, see [`tesselate_tracked`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VertexOrigin<T: Float> {
+    /// The vertex at this index of the input
+    Original(usize),
+    /// A new vertex on the line from vertex `a` to vertex `b`, at `a + (b - a) * t`
+    Interpolated {
+        /// The vertex the line starts at
+        a: usize,
+        /// The vertex the line ends at
+        b: usize,
+        /// How far along the line the vertex lies, from 0 to 1
+        t: T,
+    },
+}
+
+/// [`tesselate`] that records where every vertex comes from, so attributes such as texture
+/// coordinates can be interpolated onto the new vertices.
+///
+/// A vertex may be interpolated between vertices that were themselves interpolated, as every
+/// axis is split in turn; those always come first, so resolving the origins in order works.
+///
+/// ## Returns
+/// The origin of each vertex of the updated `vertices`, originals included
+pub fn tesselate_tracked<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    modulo: T,
+    dim: usize,
+) -> Vec<VertexOrigin<T>> {
+    let mut origins: Vec<VertexOrigin<T>> = (0..vertices.len() / dim)
+        .map(VertexOrigin::Original)
+        .collect();
+    tesselate_impl(
+        vertices,
+        indices,
+        modulo,
+        T::zero(),
+        dim,
+        Some(&mut origins),
+        None,
+    )
+    .unwrap_or_default();
+    origins
+}
+
+/// [`tesselate`] in batches of `batch_size` triangles, handing each tesselated batch to `emit`
+/// instead of collecting the whole result, so peak memory stays bounded for huge polygons with
+/// a fine `modulo`.
+///
+/// `emit` gets the new vertices of the batch and its triangles. Indices below the original
+/// vertex count refer to `vertices`; new vertices are numbered after those of all earlier
+/// batches, so concatenating `vertices` and every batch of new vertices gives one valid mesh.
+/// `vertices` is only used as scratch space and is left as it was.
+///
+/// ## Returns
+/// The number of vertices emitted, see [`tesselate`]
+pub fn tesselate_chunked<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &[N],
+    modulo: T,
+    dim: usize,
+    batch_size: usize,
+    mut emit: impl FnMut(&[T], &[N]),
+) -> usize {
+    let original_len = vertices.len();
+    let original_count = original_len / dim;
+    let mut batch: Vec<N> = Vec::new();
+    let mut added = 0;
+    for triangles in indices.chunks(batch_size.max(1) * 3) {
+        batch.clear();
+        batch.extend_from_slice(triangles);
+        let count = tesselate(vertices, &mut batch, modulo, dim);
+        if added > 0 {
+            for index in batch.iter_mut() {
+                let i = index.into_usize();
+                if i >= original_count {
+                    *index = N::from_usize(i + added);
+                }
+            }
+        }
+        emit(&vertices[original_len..], &batch);
+        vertices.truncate(original_len);
+        added += count;
+    }
+    added
+}
+
+/// tesselate, recording the origin of each new vertex in `origins` if given and stopping before
+/// a split that could exceed `max_vertices`
+fn tesselate_impl<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    modulo: T,
+    origin: T,
+    dim: usize,
+    mut origins: Option<&mut Vec<VertexOrigin<T>>>,
+    max_vertices: Option<usize>,
+) -> Result<usize, EarcutError> {
+    // there is no grid to split along, and stepping from line to line would never end
+    if !(modulo > T::zero() && modulo.is_finite()) {
+        return Ok(0);
+    }
+    let original_count = vertices.len() / dim;
+    // for each triangle, ensure each triangle line does not pass through iterations of the modulo for x, y, and z
+    for axis in 0..dim {
+        let mut i = 0;
+        while i < indices.len() {
+            // get indexes of each vertex
+            let a = indices[i].into_usize();
+            let b = indices[i + 1].into_usize();
+            let c = indices[i + 2].into_usize();
+            if let Some(triangle) = split_if_necessary(
+                a,
+                b,
+                c,
+                vertices,
+                indices,
+                dim,
+                axis,
+                modulo,
+                origin,
+                origins.as_deref_mut(),
+                max_vertices,
+            )? {
+                indices[i] = N::from_usize(triangle[0]);
+                indices[i + 1] = N::from_usize(triangle[1]);
+                indices[i + 2] = N::from_usize(triangle[2]);
+                // re-examine the remaining triangle in place; `i` only advances once no split
+                // is needed, so a split of the very first triangle never steps below zero
+                continue;
+            }
+            i += 3;
+        }
+    }
+
+    Ok(vertices.len() / dim - original_count)
+}
+
+/// Tesselate the flattened polygon until no triangle edge is longer than `max_len`
+///
+/// Triangles are bisected at the midpoint of their longest edge. Midpoints are shared between
+/// the triangles on either side of an edge, so no cracks open up. Degenerate triangles (zero
+/// area or edges too short to split in floating point) are left as is.
+///
+/// ## Parameters
+/// - `vertices`: flattened vertices to append to
+/// - `indices`: triangle indices to append to
+/// - `max_len`: the maximum edge length; non-positive or non-finite lengths do nothing
+/// - `dim`: the number of coordinates per vertex; lengths are measured across all of them
+///
+/// ## Returns
+/// The number of vertices appended, see [`tesselate`]
+pub fn tesselate_max_edge<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    max_len: T,
+    dim: usize,
+) -> usize {
+    if !(max_len > T::zero() && max_len.is_finite()) {
+        return 0;
+    }
+    let original_count = vertices.len() / dim;
+    let max_sq = max_len * max_len;
+    let mut midpoints: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    let mut i = 0;
+    while i < indices.len() {
+        let triangle = [indices[i], indices[i + 1], indices[i + 2]].map(|n| n.into_usize());
+        // rotate so the longest edge runs from a to b
+        let lengths =
+            [0, 1, 2].map(|e| distance_sq(vertices, triangle[e], triangle[(e + 1) % 3], dim));
+        let e = (0..3).fold(0, |m, e| if lengths[e] > lengths[m] { e } else { m });
+        let [a, b, c] = [triangle[e], triangle[(e + 1) % 3], triangle[(e + 2) % 3]];
+        let longest = lengths[e];
+        if longest <= max_sq || is_degenerate(vertices, a, b, c, longest, dim) {
+            i += 3;
+            continue;
+        }
+        let m = edge_midpoint(vertices, &mut midpoints, a, b, dim);
+        // the midpoint rounded onto an end point, so the edge can't get any shorter
+        if distance_sq(vertices, a, m, dim).is_zero() || distance_sq(vertices, m, b, dim).is_zero()
+        {
+            i += 3;
+            continue;
+        }
+        indices[i] = N::from_usize(a);
+        indices[i + 1] = N::from_usize(m);
+        indices[i + 2] = N::from_usize(c);
+        push_triangle(indices, m, b, c);
+        // re-examine the remaining half in place
+    }
+
+    vertices.len() / dim - original_count
+}
+
+/// Refine the triangles uniformly: every level splits each triangle into 4 at its edge
+/// midpoints, so `levels` turns each into 4^`levels` triangles, e.g. for displacement mapping
+///
+/// Midpoints are shared between the triangles on either side of an edge, so no cracks open up,
+/// and the smaller triangles keep the winding of the one they came from.
+///
+/// ## Parameters
+/// - `vertices`: flattened vertices to append to
+/// - `indices`: triangle indices, replaced by the refined ones
+/// - `levels`: how many times to subdivide
+/// - `dim`: the number of coordinates per vertex; all of them are interpolated
+///
+/// ## Returns
+/// The number of vertices appended, see [`tesselate`]
+pub fn subdivide<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    levels: usize,
+    dim: usize,
+) -> usize {
+    let original_count = vertices.len() / dim;
+    let mut midpoints: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    let mut refined: Vec<N> = Vec::new();
+    for _ in 0..levels {
+        // edges of one level are never edges of the next
+        midpoints.clear();
+        refined.clear();
+        refined.reserve(indices.len() * 4);
+        for triangle in indices.as_chunks::<3>().0 {
+            let [a, b, c] = triangle.map(|n| n.into_usize());
+            let ab = edge_midpoint(vertices, &mut midpoints, a, b, dim);
+            let bc = edge_midpoint(vertices, &mut midpoints, b, c, dim);
+            let ca = edge_midpoint(vertices, &mut midpoints, c, a, dim);
+            push_triangle(&mut refined, a, ab, ca);
+            push_triangle(&mut refined, ab, b, bc);
+            push_triangle(&mut refined, ca, bc, c);
+            push_triangle(&mut refined, ab, bc, ca);
+        }
+        core::mem::swap(indices, &mut refined);
+    }
+
+    vertices.len() / dim - original_count
+}
+
+/// the index of the midpoint between two vertices, appended the first time the edge is split
+fn edge_midpoint<T: Float>(
+    vertices: &mut Vec<T>,
+    midpoints: &mut BTreeMap<(usize, usize), usize>,
+    a: usize,
+    b: usize,
+    dim: usize,
+) -> usize {
+    *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+        let index = vertices.len() / dim;
+        for axis in 0..dim {
+            let (va, vb) = (vertices[a * dim + axis], vertices[b * dim + axis]);
+            vertices.push(va + (vb - va) / (T::one() + T::one()));
+        }
+        index
+    })
+}
+
+/// the squared distance between two vertices
+fn distance_sq<T: Float>(vertices: &[T], a: usize, b: usize, dim: usize) -> T {
+    (0..dim).fold(T::zero(), |sum, axis| {
+        let d = vertices[b * dim + axis] - vertices[a * dim + axis];
+        sum + d * d
+    })
+}
+
+/// whether the triangle's area is negligible next to its longest (squared) edge
+fn is_degenerate<T: Float>(
+    vertices: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    longest: T,
+    dim: usize,
+) -> bool {
+    // Lagrange's identity gives the squared parallelogram area in any dimension
+    let (mut uu, mut vv, mut uv) = (T::zero(), T::zero(), T::zero());
+    for axis in 0..dim {
+        let u = vertices[b * dim + axis] - vertices[a * dim + axis];
+        let v = vertices[c * dim + axis] - vertices[a * dim + axis];
+        uu = uu + u * u;
+        vv = vv + v * v;
+        uv = uv + u * v;
+    }
+    uu * vv - uv * uv <= T::epsilon() * longest * longest
+}
+
+/// given vertices, and an axis of said vertices:
+/// find a number "x" that is (x - origin) % modulo == 0 and between v1 and v2
+#[allow(clippy::too_many_arguments)]
+fn split_if_necessary<T: Float, N: Index>(
+    i1: usize,
+    i2: usize,
+    i3: usize,
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    dim: usize,
+    axis: usize,
+    modulo: T,
+    origin: T,
+    origins: Option<&mut Vec<VertexOrigin<T>>>,
+    max_vertices: Option<usize>,
+) -> Result<Option<[usize; 3]>, EarcutError> {
+    let v1 = vertices[i1 * dim + axis];
+    let v2 = vertices[i2 * dim + axis];
+    let v3 = vertices[i3 * dim + axis];
+    // a split adds a pair of vertices per grid line from `mod_point` up to `near`
+    let count = vertices.len() / dim;
+    let check_limit = |mod_point: T, near: T| match max_vertices {
+        Some(max_vertices) => {
+            let lines = ((near - mod_point).abs() / modulo).ceil().to_usize();
+            let bound = lines.and_then(|lines| lines.checked_add(1)?.checked_mul(2));
+            match bound.and_then(|bound| count.checked_add(bound)) {
+                Some(total) if total <= max_vertices => Ok(()),
+                _ => Err(EarcutError::VertexLimit(max_vertices)),
+            }
+        }
+        None => Ok(()),
+    };
+    // 1 is corner
+    if v1 < v2 && v1 < v3 {
+        let mod_point = v1 + modulo - mod2(v1 - origin, modulo);
+        if mod_point > v1 && mod_point <= v2 && mod_point <= v3 && v2 != mod_point {
+            check_limit(mod_point, v2.min(v3))?;
+            return Ok(Some(split_right(
+                mod_point, i1, i2, i3, v1, v2, v3, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    } else if v1 > v2 && v1 > v3 {
+        let mut m = mod2(v1 - origin, modulo);
+        if m.is_zero() {
+            m = modulo;
+        }
+        let mod_point = v1 - m;
+        if mod_point < v1 && mod_point >= v2 && mod_point >= v3 && v2 != mod_point {
+            check_limit(mod_point, v2.max(v3))?;
+            return Ok(Some(split_left(
+                mod_point, i1, i2, i3, v1, v2, v3, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    }
+    // 2 is corner
+    if v2 < v1 && v2 < v3 {
+        let mod_point = v2 + modulo - mod2(v2 - origin, modulo);
+        if mod_point > v2
+            && mod_point <= v3
+            && mod_point <= v1
+            && (v1 != mod_point || v3 != mod_point)
+        {
+            check_limit(mod_point, v3.min(v1))?;
+            return Ok(Some(split_right(
+                mod_point, i2, i3, i1, v2, v3, v1, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    } else if v2 > v1 && v2 > v3 {
+        let mut m = mod2(v2 - origin, modulo);
+        if m.is_zero() {
+            m = modulo;
+        }
+        let mod_point = v2 - m;
+        if mod_point < v2
+            && mod_point >= v3
+            && mod_point >= v1
+            && (v1 != mod_point || v3 != mod_point)
+        {
+            check_limit(mod_point, v3.max(v1))?;
+            return Ok(Some(split_left(
+                mod_point, i2, i3, i1, v2, v3, v1, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    }
+    // 3 is corner
+    if v3 < v1 && v3 < v2 {
+        let mod_point = v3 + modulo - mod2(v3 - origin, modulo);
+        if mod_point > v3
+            && mod_point <= v1
+            && mod_point <= v2
+            && (v1 != mod_point || v2 != mod_point)
+        {
+            check_limit(mod_point, v1.min(v2))?;
+            return Ok(Some(split_right(
+                mod_point, i3, i1, i2, v3, v1, v2, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    } else if v3 > v1 && v3 > v2 {
+        let mut m = mod2(v3 - origin, modulo);
+        if m.is_zero() {
+            m = modulo;
+        }
+        let mod_point = v3 - m;
+        if mod_point < v3
+            && mod_point >= v1
+            && mod_point >= v2
+            && (v1 != mod_point || v2 != mod_point)
+        {
+            check_limit(mod_point, v1.max(v2))?;
+            return Ok(Some(split_left(
+                mod_point, i3, i1, i2, v3, v1, v2, vertices, indices, dim, axis, modulo, origins,
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+/// create a vertex on the line between i1 and i2 where `axis` equals `split_point`,
+/// returning its index and recording its origin if tracked
+#[allow(clippy::too_many_arguments)]
+fn create_vertex<T: Float>(
+    split_point: T,
+    i1: usize,
+    i2: usize,
+    v1: T,
+    v2: T,
+    vertices: &mut Vec<T>,
+    dim: usize,
+    axis: usize,
+    origins: Option<&mut Vec<VertexOrigin<T>>>,
+) -> usize {
+    let index = vertices.len() / dim;
+    let travel_divisor = (v2 - v1) / (split_point - v1);
+    if let Some(origins) = origins {
+        origins.push(VertexOrigin::Interpolated {
+            a: i1,
+            b: i2,
+            t: T::one() / travel_divisor,
+        });
+    }
+    for i in 0..dim {
+        let va1 = vertices[i1 * dim + i];
+        let va2 = vertices[i2 * dim + i];
+        if i != axis {
+            vertices.push(va1 + (va2 - va1) / travel_divisor);
+        } else {
+            vertices.push(split_point);
+        }
+    }
+
+    index
+}
+
+/// i1 is always the vertex with an acute angle.
+/// split_right means we start on the left side of this "1D" observation moving right
+#[allow(clippy::too_many_arguments)]
+fn split_right<T: Float, N: Index>(
+    mod_point: T,
+    i1: usize,
+    i2: usize,
+    i3: usize,
+    v1: T,
+    v2: T,
+    v3: T,
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    dim: usize,
+    axis: usize,
+    modulo: T,
+    mut origins: Option<&mut Vec<VertexOrigin<T>>>,
+) -> [usize; 3] {
+    let mut mod_point = mod_point;
+    // first case is a standalone triangle
+    let mut i12 = create_vertex(
+        mod_point,
+        i1,
+        i2,
+        v1,
+        v2,
+        vertices,
+        dim,
+        axis,
+        origins.as_deref_mut(),
+    );
+    let mut i13 = create_vertex(
+        mod_point,
+        i1,
+        i3,
+        v1,
+        v3,
+        vertices,
+        dim,
+        axis,
+        origins.as_deref_mut(),
+    );
+    push_triangle(indices, i1, i12, i13);
+    mod_point = mod_point + modulo;
+    if v2 < v3 {
+        // create lines up to i2
+        while mod_point < v2 {
+            // next triangles are i13->i12->nexti13 and nexti13->i12->nexti12 so store in necessary order
+            let prev_i13 = i13;
+            i13 = create_vertex(
+                mod_point,
+                i1,
+                i3,
+                v1,
+                v3,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, prev_i13, i12, i13);
+            let prev_i12 = i12;
+            i12 = create_vertex(
+                mod_point,
+                i1,
+                i2,
+                v1,
+                v2,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, i13, prev_i12, i12);
+            // increment
+            mod_point = mod_point + modulo;
+        }
+        // add v2 triangle if necessary
+        push_triangle(indices, i13, i12, i2);
+        // return the remaining triangle
+        [i13, i2, i3]
+    } else {
+        // create lines up to i3
+        while mod_point < v3 {
+            // next triangles are i13->i12->nexti13 and nexti13->i12->nexti12 so store in necessary order
+            let prev_i13 = i13;
+            i13 = create_vertex(
+                mod_point,
+                i1,
+                i3,
+                v1,
+                v3,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, prev_i13, i12, i13);
+            let prev_i12 = i12;
+            i12 = create_vertex(
+                mod_point,
+                i1,
+                i2,
+                v1,
+                v2,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, i13, prev_i12, i12);
+            // increment
+            mod_point = mod_point + modulo;
+        }
+        // add v3 triangle if necessary
+        push_triangle(indices, i13, i12, i3);
+        // return the remaining triangle
+        [i3, i12, i2]
+    }
+}
+
+/// i1 is always the vertex with an acute angle. i2 is always the furthest away from i1
+/// split_left means we start on the right side of this "1D" observation moving left
+#[allow(clippy::too_many_arguments)]
+fn split_left<T: Float, N: Index>(
+    mod_point: T,
+    i1: usize,
+    i2: usize,
+    i3: usize,
+    v1: T,
+    v2: T,
+    v3: T,
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    dim: usize,
+    axis: usize,
+    modulo: T,
+    mut origins: Option<&mut Vec<VertexOrigin<T>>>,
+) -> [usize; 3] {
+    let mut mod_point = mod_point;
+    // first case is a standalone triangle
+    let mut i12 = create_vertex(
+        mod_point,
+        i1,
+        i2,
+        v1,
+        v2,
+        vertices,
+        dim,
+        axis,
+        origins.as_deref_mut(),
+    );
+    let mut i13 = create_vertex(
+        mod_point,
+        i1,
+        i3,
+        v1,
+        v3,
+        vertices,
+        dim,
+        axis,
+        origins.as_deref_mut(),
+    );
+    push_triangle(indices, i1, i12, i13);
+    mod_point = mod_point - modulo;
+    if v2 > v3 {
+        // create lines up to i2
+        while mod_point > v2 {
+            // next triangles are i13->i12->nexti13 and nexti13->i12->nexti12 so store in necessary order
+            let prev_i13 = i13;
+            i13 = create_vertex(
+                mod_point,
+                i1,
+                i3,
+                v1,
+                v3,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, prev_i13, i12, i13);
+            let prev_i12 = i12;
+            i12 = create_vertex(
+                mod_point,
+                i1,
+                i2,
+                v1,
+                v2,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, i13, prev_i12, i12);
+            // increment
+            mod_point = mod_point - modulo;
+        }
+        // add v2 triangle if necessary
+        push_triangle(indices, i13, i12, i2);
+        // return the remaining triangle
+        [i13, i2, i3]
+    } else {
+        // create lines up to i3
+        while mod_point > v3 {
+            // next triangles are i13->i12->nexti13 and nexti13->i12->nexti12 so store in necessary order
+            let prev_i13 = i13;
+            i13 = create_vertex(
+                mod_point,
+                i1,
+                i3,
+                v1,
+                v3,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, prev_i13, i12, i13);
+            let prev_i12 = i12;
+            i12 = create_vertex(
+                mod_point,
+                i1,
+                i2,
+                v1,
+                v2,
+                vertices,
+                dim,
+                axis,
+                origins.as_deref_mut(),
+            );
+            push_triangle(indices, i13, prev_i12, i12);
+            // increment
+            mod_point = mod_point - modulo;
+        }
+        // add v3 triangle if necessary
+        push_triangle(indices, i13, i12, i3);
+        // return the remaining triangle
+        [i3, i12, i2]
+    }
+}
+
+/// push a triangle's three vertex indices
+fn push_triangle<N: Index>(indices: &mut Vec<N>, a: usize, b: usize, c: usize) {
+    indices.push(N::from_usize(a));
+    indices.push(N::from_usize(b));
+    indices.push(N::from_usize(c));
+}
+
+/// x mod n (supports negative numbers)
+fn mod2<T: Float>(x: T, n: T) -> T {
+    ((x % n) + n) % n
+}
+
+/// Flattens a 2D or 3D polygon into a single vertex buffer
+///
+/// ## Parameters
+/// - `data`: the polygon rings; the first ring is the outer ring, the rest are holes
+///
+/// ## Returns
+/// The flattened vertices, the vertex index each hole starts at, and the dimension (2 or 3)
+pub fn flatten<T: Float, N: Index>(data: &[Vec<Vec<T>>]) -> (Vec<T>, Vec<N>, usize) {
+    flatten_rings(data.iter().map(Vec::as_slice))
+}
+
+/// Flattens a 2D or 3D polygon like [`flatten`], also returning the signed area of each ring
+///
+/// ## Returns
+/// The flattened vertices, the vertex index each hole starts at, the dimension (2 or 3), and the
+/// [`ring_area`] of each ring in input order
+pub fn flatten_with_areas<T: Float, N: Index>(
+    data: &[Vec<Vec<T>>],
+) -> (Vec<T>, Vec<N>, usize, Vec<T>) {
+    let (vertices, hole_indices, dim) = flatten::<T, N>(data);
+    let areas = split_rings(&vertices, &hole_indices, dim)
+        .map(|ring| ring_area(ring, dim))
+        .collect();
+    (vertices, hole_indices, dim, areas)
+}
+
+/// Flattens a 2D or 3D polygon like [`flatten`], also returning the ring each vertex came from,
+/// e.g. to carry per-ring attributes such as an elevation over to the triangulated vertices
+///
+/// ## Returns
+/// The flattened vertices, the vertex index each hole starts at, the dimension (2 or 3), and
+/// the index into `data` of each vertex's ring: 0 for the outer ring, 1 for the first hole and
+/// so on
+pub fn flatten_with_rings<T: Float, N: Index>(
+    data: &[Vec<Vec<T>>],
+) -> (Vec<T>, Vec<N>, usize, Vec<usize>) {
+    let (vertices, hole_indices, dim) = flatten::<T, N>(data);
+    (vertices, hole_indices, dim, vertex_rings(data))
+}
+
+/// the index into `data` of the ring each flattened vertex comes from
+fn vertex_rings<T>(data: &[Vec<Vec<T>>]) -> Vec<usize> {
+    data.iter()
+        .enumerate()
+        .flat_map(|(r, ring)| core::iter::repeat_n(r, ring.len()))
+        .collect()
+}
+
+/// the vertices of each ring of a flattened polygon
+fn split_rings<'a, T: Float, N: Index>(
+    vertices: &'a [T],
+    hole_indices: &'a [N],
+    dim: usize,
+) -> impl Iterator<Item = &'a [T]> {
+    let len = vertices.len() / dim;
+    let starts = core::iter::once(0).chain(hole_indices.iter().map(|h| h.into_usize()));
+    let ends = hole_indices
+        .iter()
+        .map(|h| h.into_usize())
+        .chain(core::iter::once(len));
+    starts
+        .zip(ends)
+        .map(move |(start, end)| &vertices[start * dim..end * dim])
+}
+
+/// Flattens a 2D or 3D polygon like [`flatten`], but into caller provided buffers so their
+/// capacity can be reused across calls
+///
+/// ## Parameters
+/// - `data`: the polygon rings; the first ring is the outer ring, the rest are holes
+/// - `vertices`: cleared, then filled with the flattened vertices
+/// - `hole_indices`: cleared, then filled with the vertex index each hole starts at
+///
+/// ## Returns
+/// The dimension (2 or 3)
+pub fn flatten_into<T: Float, N: Index>(
+    data: &[Vec<Vec<T>>],
+    vertices: &mut Vec<T>,
+    hole_indices: &mut Vec<N>,
+) -> usize {
+    flatten_rings_into(data.iter().map(Vec::as_slice), vertices, hole_indices)
+}
+
+/// Flattens a polygon into a single vertex buffer with a fixed stride of `dim`
+///
+/// Unlike [`flatten`], the dimension isn't inferred from the points: every point writes
+/// exactly `dim` coordinates. Points with more coordinates are truncated and points with fewer
+/// are padded with zeros, so a ragged mix of 2D and 3D points still gives a consistent buffer.
+///
+/// ## Parameters
+/// - `data`: the polygon rings; the first ring is the outer ring, the rest are holes
+/// - `dim`: the number of coordinates to write per point
+///
+/// ## Returns
+/// The flattened vertices and the vertex index each hole starts at
+pub fn flatten_dim<T: Float, N: Index>(data: &[Vec<Vec<T>>], dim: usize) -> (Vec<T>, Vec<N>) {
+    let mut vertices = Vec::with_capacity(data.iter().map(Vec::len).sum::<usize>() * dim);
+    let mut hole_indices = Vec::new();
+    let mut hole_index = 0;
+    for (i, line) in data.iter().enumerate() {
+        if i > 0 {
+            hole_indices.push(N::from_usize(hole_index));
+        }
+        for point in line {
+            vertices.extend((0..dim).map(|d| point.get(d).copied().unwrap_or_else(T::zero)));
+        }
+        hole_index += line.len();
+    }
+
+    (vertices, hole_indices)
+}
+
+/// flatten rings given one by one; the first is the outer ring, the rest are holes
+fn flatten_rings<'a, T: Float + 'a, N: Index>(
+    rings: impl IntoIterator<Item = &'a [Vec<T>]>,
+) -> (Vec<T>, Vec<N>, usize) {
+    let mut vertices = Vec::new();
+    let mut hole_indices = Vec::new();
+    let dim = flatten_rings_into(rings, &mut vertices, &mut hole_indices);
+    (vertices, hole_indices, dim)
+}
+
+/// flatten rings into the given buffers, returning the dimension
+fn flatten_rings_into<'a, T: Float + 'a, N: Index>(
+    rings: impl IntoIterator<Item = &'a [Vec<T>]>,
+    vertices: &mut Vec<T>,
+    hole_indices: &mut Vec<N>,
+) -> usize {
+    vertices.clear();
+    hole_indices.clear();
+    let mut hole_index = 0;
+    let mut dim = 2;
+
+    for (i, line) in rings.into_iter().enumerate() {
+        // the first point decides the dimension
+        if i == 0 && line.first().is_some_and(|point| point.len() > 2) {
+            dim = 3;
+        }
+        if i > 0 {
+            hole_indices.push(N::from_usize(hole_index));
+        }
+        // ragged points are padded with zero or truncated to the dimension
+        for point in line {
+            vertices.extend((0..dim).map(|d| point.get(d).copied().unwrap_or_else(T::zero)));
+        }
+        hole_index += line.len();
+    }
+
+    dim
+}
+
+/// Flattens an `f64` polygon into a single vertex buffer with `usize` hole indices.
+///
+/// This is the monomorphic entry point of the generic [`flatten`], which it delegates to, so
+/// callers don't need to spell out the float and index types.
+///
+/// ```
+/// use earclip::{flatten, flatten_float};
+///
+/// let polygon = vec![
+///     vec![vec![0.0, 0.0], vec![10.0, 0.0], vec![10.0, 10.0], vec![0.0, 10.0]],
+///     vec![vec![2.0, 2.0], vec![2.0, 4.0], vec![4.0, 4.0]],
+/// ];
+/// let generic = flatten::<f64, usize>(&polygon);
+/// assert_eq!(flatten_float(&polygon), generic);
+/// assert_eq!(generic.1, vec![4]);
+/// ```
+pub fn flatten_float(data: &[Vec<Vec<f64>>]) -> (Vec<f64>, Vec<usize>, usize) {
+    flatten(data)
+}
+
+/// Triangulate 2D rings stored as contiguous point arrays, skipping the nested `Vec` form.
+///
+/// The first ring is the outer boundary and the rest are holes, just like [`earclip`].
+///
+/// ## Returns
+/// The flattened vertices and the triangle indices
+pub fn earcut_points2d<T: Float, N: Index>(rings: &[&[[T; 2]]]) -> (Vec<T>, Vec<N>) {
+    earcut_points(rings)
+}
+
+/// Triangulate 3D rings stored as contiguous point arrays, skipping the nested `Vec` form.
+///
+/// The first ring is the outer boundary and the rest are holes, just like [`earclip`].
+///
+/// ## Returns
+/// The flattened vertices and the triangle indices
+pub fn earcut_points3d<T: Float, N: Index>(rings: &[&[[T; 3]]]) -> (Vec<T>, Vec<N>) {
+    earcut_points(rings)
+}
+
+/// flatten fixed size point rings straight into one buffer and triangulate it
+fn earcut_points<T: Float, N: Index, const D: usize>(rings: &[&[[T; D]]]) -> (Vec<T>, Vec<N>) {
+    earcut_iter(rings.iter().map(|ring| ring.iter().copied()))
+}
+
+/// Triangulate rings streamed from iterators, such as a decoder, without collecting them into
+/// nested `Vec`s first.
+///
+/// The first ring is the outer boundary and the rest are holes, just like [`earclip`]. Every
+/// point is appended to the flat vertex buffer as it is produced, which grows like any `Vec`
+/// (reserving up front whatever each ring's `size_hint` promises), so the only allocations are
+/// that buffer, the hole indices and the triangulation's own working memory.
+///
+/// ```
+/// use earclip::{earcut_iter, earcut_points2d};
+///
+/// let square = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+/// let rings = core::iter::once(square.iter().map(|&[x, y]| [x, y]));
+/// let (vertices, indices): (Vec<f64>, Vec<u32>) = earcut_iter(rings);
+/// assert_eq!((vertices, indices), earcut_points2d(&[&square]));
+/// ```
+///
+/// ## Returns
+/// The flattened vertices and the triangle indices
+pub fn earcut_iter<T: Float, N: Index, const D: usize>(
+    rings: impl IntoIterator<Item = impl IntoIterator<Item = [T; D]>>,
+) -> (Vec<T>, Vec<N>) {
+    let (vertices, hole_indices) = flatten_iter::<T, N, D>(rings);
+    let indices = earcut(&vertices, &hole_indices, D);
+
+    (vertices, indices)
+}
+
+/// flatten rings of fixed size points into one buffer and the hole indices
+fn flatten_iter<T: Float, N: Index, const D: usize>(
+    rings: impl IntoIterator<Item = impl IntoIterator<Item = [T; D]>>,
+) -> (Vec<T>, Vec<N>) {
+    let mut vertices = Vec::new();
+    let mut hole_indices = Vec::new();
+    for (i, ring) in rings.into_iter().enumerate() {
+        if i > 0 {
+            hole_indices.push(N::from_usize(vertices.len() / D));
+        }
+        let ring = ring.into_iter();
+        vertices.reserve(ring.size_hint().0 * D);
+        for point in ring {
+            vertices.extend(point);
+        }
+    }
+
+    (vertices, hole_indices)
+}
+
+/// Returns a percentage difference between the polygon area and its triangulation area;
+/// used to verify correctness of triangulation
+///
+/// ## Parameters
+/// - `data`: the flattened vertices
+/// - `hole_indices`: the vertex index each hole starts at
+/// - `dim`: the number of coordinates per vertex
+/// - `triangles`: the triangle indices produced by [`earcut`]
+pub fn deviation<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    triangles: &[N],
+) -> T {
+    triangulation_report(data, hole_indices, dim, triangles).deviation()
+}
+
+/// The raw numbers behind [`deviation`], for debugging a triangulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangulationReport<T: Float> {
+    /// The area of the outer ring minus the areas of the holes
+    pub polygon_area: T,
+    /// The summed area of all triangles
+    pub triangles_area: T,
+    /// `triangles_area - polygon_area`; positive when triangles overlap or spill outside
+    pub difference: T,
+    /// The number of triangles
+    pub triangle_count: usize,
+}
+impl<T: Float> TriangulationReport<T> {
+    /// The relative difference between the polygon and triangles areas, as returned by
+    /// [`deviation`]; zero if both are empty
+    pub fn deviation(&self) -> T {
+        if self.polygon_area.is_zero() && self.triangles_area.is_zero() {
+            T::zero()
+        } else {
+            (self.difference / self.polygon_area).abs()
+        }
+    }
+}
+
+/// Triangulate a polygon and suggest a deviation threshold to assert in its tests, e.g. when
+/// adding a new fixture.
+///
+/// This is the polygon's [`deviation`] rounded up to two significant digits, so it always
+/// holds for the triangulation at hand and is still tight enough to catch it getting worse.
+/// Polygons that triangulate exactly get zero.
+///
+/// See [`deviation`] for the parameters.
+pub fn suggest_tolerance<T: Float, N: Index>(data: &[T], hole_indices: &[N], dim: usize) -> T {
+    let triangles = earcut(data, hole_indices, dim);
+    let deviation = deviation(data, hole_indices, dim, &triangles);
+    if deviation.is_zero() || !deviation.is_finite() {
+        return deviation;
+    }
+    let step = T::from(10.0)
+        .unwrap_or_else(T::one)
+        .powi(deviation.log10().floor().to_i32().unwrap_or(0) - 1);
+    let rounded = (deviation / step).ceil() * step;
+    // the product can round just below the deviation
+    if rounded < deviation {
+        rounded + step
+    } else {
+        rounded
+    }
+}
+
+/// Measure how well a triangulation covers its polygon
+///
+/// See [`deviation`] for the parameters.
+pub fn triangulation_report<T: Float, N: Index>(
+    data: &[T],
+    hole_indices: &[N],
+    dim: usize,
+    triangles: &[N],
+) -> TriangulationReport<T> {
+    let has_holes = !hole_indices.is_empty();
+    let outer_len = if has_holes {
+        hole_indices[0].into_usize() * dim
+    } else {
+        data.len()
+    };
+    let mut polygon_area = signed_area(data, 0, outer_len, dim).abs();
+
+    if has_holes {
+        let len = hole_indices.len();
+        for i in 0..len {
+            let start = hole_indices[i].into_usize() * dim;
+            let end = if i < len - 1 {
+                hole_indices[i + 1].into_usize() * dim
+            } else {
+                data.len()
+            };
+            polygon_area = polygon_area - signed_area(data, start, end, dim).abs();
+        }
+    }
+
+    // the ring sum is twice the actual area
+    let polygon_area = polygon_area / (T::one() + T::one());
+    let triangles_area = triangulated_area(data, triangles, dim);
+    TriangulationReport {
+        polygon_area,
+        triangles_area,
+        difference: triangles_area - polygon_area,
+        triangle_count: triangles.len() / 3,
+    }
+}
+
+/// The total area covered by a triangulation: the summed absolute areas of its triangles
+///
+/// ## Parameters
+/// - `data`: the flattened vertices
+/// - `triangles`: the triangle indices
+/// - `dim`: the number of coordinates per vertex; only the first two are used
+pub fn triangulated_area<T: Float, N: Index>(data: &[T], triangles: &[N], dim: usize) -> T {
+    let sum = triangles
+        .as_chunks::<3>()
+        .0
+        .iter()
+        .fold(T::zero(), |sum, &triangle| {
+            sum + triangle_area(data, triangle, dim).abs()
+        });
+    // the cross products are twice the actual areas
+    sum / (T::one() + T::one())
+}
+
+/// The area-weighted centroid of a triangulation, e.g. to place a label: the average of the
+/// triangle centroids weighted by the triangle areas, which is the centroid of the polygon the
+/// triangles cover.
+///
+/// Unlike the average of the vertices this doesn't depend on how the points are spread along
+/// the rings. It can still lie outside a strongly concave polygon, such as a thin ring shaped
+/// one. Without any area, e.g. for a collinear polygon, the vertex average is returned instead,
+/// `[0, 0]` without vertices.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex; only the first two are used
+pub fn centroid<T: Float, N: Index>(vertices: &[T], indices: &[N], dim: usize) -> [T; 2] {
+    let three = T::from(3.0).unwrap_or_else(T::one);
+    let (mut sum, mut total) = ([T::zero(); 2], T::zero());
+    for &triangle in indices.as_chunks::<3>().0 {
+        let area = triangle_area(vertices, triangle, dim).abs();
+        let [a, b, c] = triangle.map(|i| i.into_usize() * dim);
+        for (axis, sum) in sum.iter_mut().enumerate() {
+            let center = (vertices[a + axis] + vertices[b + axis] + vertices[c + axis]) / three;
+            *sum = *sum + center * area;
+        }
+        total = total + area;
+    }
+    if total.is_zero() {
+        return vertex_average(vertices, dim);
+    }
+    sum.map(|sum| sum / total)
+}
+
+/// [`centroid`] in 3D, weighting the triangles by their area in space, e.g. for a surface
+/// tesselated in 3D or an extruded mesh; `dim` must be at least 3
+pub fn centroid_3d<T: Float, N: Index>(vertices: &[T], indices: &[N], dim: usize) -> [T; 3] {
+    let three = T::from(3.0).unwrap_or_else(T::one);
+    let (mut sum, mut total) = ([T::zero(); 3], T::zero());
+    for &triangle in indices.as_chunks::<3>().0 {
+        let [a, b, c] = triangle.map(|i| {
+            let i = i.into_usize() * dim;
+            [vertices[i], vertices[i + 1], vertices[i + 2]]
+        });
+        let normal = cross(sub(b, a), sub(c, a));
+        let area = dot(normal, normal).sqrt();
+        for (axis, sum) in sum.iter_mut().enumerate() {
+            *sum = *sum + (a[axis] + b[axis] + c[axis]) / three * area;
+        }
+        total = total + area;
+    }
+    if total.is_zero() {
+        return vertex_average(vertices, dim);
+    }
+    sum.map(|sum| sum / total)
+}
+
+/// the average of the first `D` coordinates of the vertices, zero without vertices
+fn vertex_average<T: Float, const D: usize>(vertices: &[T], dim: usize) -> [T; D] {
+    let count = vertices.len() / dim;
+    let mut sum = [T::zero(); D];
+    for vertex in vertices.chunks_exact(dim) {
+        for (sum, &v) in sum.iter_mut().zip(vertex) {
+            *sum = *sum + v;
+        }
+    }
+    let count = T::from(count.max(1)).unwrap_or_else(T::one);
+    sum.map(|sum| sum / count)
+}
+
+/// Check that a triangulation lies within its polygon, e.g. for a correctness harness.
+///
+/// Every triangle's centroid has to be inside the outer ring and outside all holes (by
+/// [`point_in_polygon`]), and the [`deviation`] of the summed triangle area from the polygon
+/// area has to be within the square root of `T::epsilon()`. Triangles without area are
+/// skipped, as they cover nothing.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `hole_indices`: the vertex index each hole starts at
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex
+pub fn validate_triangulation<T: Float, N: Index>(
+    vertices: &[T],
+    hole_indices: &[N],
+    indices: &[N],
+    dim: usize,
+) -> bool {
+    let rings: Vec<&[T]> = split_rings(vertices, hole_indices, dim).collect();
+    let three = T::one() + T::one() + T::one();
+    let inside = indices.as_chunks::<3>().0.iter().all(|&triangle| {
+        if triangle_area(vertices, triangle, dim).is_zero() {
+            return true;
+        }
+        let [a, b, c] = triangle.map(|i| i.into_usize() * dim);
+        let centroid = [
+            (vertices[a] + vertices[b] + vertices[c]) / three,
+            (vertices[a + 1] + vertices[b + 1] + vertices[c + 1]) / three,
+        ];
+        point_in_polygon(rings[0], dim, centroid)
+            && !rings[1..]
+                .iter()
+                .any(|hole| point_in_polygon(hole, dim, centroid))
+    });
+
+    inside && deviation(vertices, hole_indices, dim, indices) <= T::epsilon().sqrt()
+}
+
+/// Check that every triangle of a mesh has the same winding.
+///
+/// Ear clipping always emits triangles of a single orientation, so `false` means the mesh was
+/// corrupted or produced from bad input. Zero-area triangles have no winding and are ignored.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex
+pub fn all_same_winding<T: Float, N: Index>(vertices: &[T], indices: &[N], dim: usize) -> bool {
+    let mut positive = false;
+    let mut negative = false;
+    for &triangle in indices.as_chunks::<3>().0 {
+        let area = triangle_area(vertices, triangle, dim);
+        positive |= area > T::zero();
+        negative |= area < T::zero();
+        if positive && negative {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Remove sliver triangles whose absolute area (in x and y) is below `min_area`.
+///
+/// Only `indices` is changed: the remaining triangles keep their order and vertices are
+/// retained as is, even if no triangle references them anymore, so vertex attributes stay
+/// aligned with the input.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices to filter
+/// - `min_area`: the smallest triangle area to keep
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The number of triangles removed
+pub fn filter_slivers<T: Float, N: Index>(
+    vertices: &[T],
+    indices: &mut Vec<N>,
+    min_area: T,
+    dim: usize,
+) -> usize {
+    let double_min_area = min_area + min_area;
+    let len = indices.len();
+    let mut kept = 0;
+    for t in (0..len - len % 3).step_by(3) {
+        let triangle = [indices[t], indices[t + 1], indices[t + 2]];
+        if triangle_area(vertices, triangle, dim).abs() >= double_min_area {
+            indices.copy_within(t..t + 3, kept);
+            kept += 3;
+        }
+    }
+    indices.truncate(kept);
+
+    (len - kept) / 3
+}
+
+/// twice the signed area of a triangle given by its vertex indices
+fn triangle_area<T: Float, N: Index>(data: &[T], triangle: [N; 3], dim: usize) -> T {
+    let [a, b, c] = triangle.map(|i| i.into_usize() * dim);
+    (data[a] - data[c]) * (data[b + 1] - data[a + 1])
+        - (data[a] - data[b]) * (data[c + 1] - data[a + 1])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
-    fn it_works() {
-        let result = add(1, 2);
-        let result2 = add(1, 1);
+    fn try_earcut_rejects_odd_vertex_count() {
+        let data = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[], 2),
+            Err(EarcutError::OddVertexCount)
+        );
+    }
+
+    #[test]
+    fn try_earcut_rejects_bad_input() {
+        let data = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[5], 2),
+            Err(EarcutError::IndexOverflow)
+        );
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[], 0),
+            Err(EarcutError::OddVertexCount)
+        );
+        let data = [0.0, 0.0, 1.0, f64::NAN, 1.0, 1.0];
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[], 2),
+            Err(EarcutError::NonFiniteCoordinate)
+        );
+    }
+
+    #[test]
+    fn validate_finite_finds_first_bad_coordinate() {
+        assert_eq!(validate_finite(&[0.0, 0.0, 1.0, 0.0, 1.0, 1.0]), Ok(()));
+        assert_eq!(
+            validate_finite(&[0.0, 0.0, 1.0, f64::NAN, 1.0, 1.0]),
+            Err(3)
+        );
+        assert_eq!(
+            validate_finite(&[0.0, 0.0, 1.0, 0.0, f32::NEG_INFINITY, f32::NAN]),
+            Err(4)
+        );
 
-        assert_eq!(result, 3);
-        assert_eq!(result2, 2);
+        let data = [0.0, 0.0, 1.0, 0.0, f64::INFINITY, 1.0];
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[], 2),
+            Err(EarcutError::NonFiniteCoordinate)
+        );
+    }
+
+    #[test]
+    fn try_earcut_matches_earcut() {
+        let data = [10.0, 0.0, 0.0, 50.0, 60.0, 60.0, 70.0, 10.0];
+        assert_eq!(
+            try_earcut::<f64, usize>(&data, &[], 2),
+            Ok(earcut(&data, &[], 2))
+        );
+    }
+
+    #[test]
+    fn try_earclip_rejects_bad_input() {
+        let polygon = vec![vec![vec![0.0, 0.0], vec![1.0], vec![0.0, 1.0]]];
+        assert_eq!(
+            try_earclip::<f64, usize>(&polygon, None, None),
+            Err(EarcutError::OddVertexCount)
+        );
+        let polygon = vec![vec![
+            vec![0.0, 0.0],
+            vec![f64::INFINITY, 0.0],
+            vec![0.0, 1.0],
+        ]];
+        assert_eq!(
+            try_earclip::<f64, usize>(&polygon, None, None),
+            Err(EarcutError::NonFiniteCoordinate)
+        );
     }
 }
@@ -0,0 +1,275 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::Range;
+
+use num_traits::float::Float;
+
+use crate::{
+    builder, earcut_impl, earcut_points2d, flatten_rings_into, inner_point, point_in_polygon,
+    polygon_dim, ring_area, triangulated_area, vertex_rings, Index, Store,
+};
+
+/// A triangulated polygon: its flattened vertices, triangle indices and dimension
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TriangleMesh<T: Float, N: Index> {
+    /// The flattened vertices
+    pub vertices: Vec<T>,
+    /// The triangle indices, three per triangle
+    pub indices: Vec<N>,
+    /// The number of coordinates per vertex
+    pub dim: usize,
+}
+
+impl<T: Float, N: Index> TriangleMesh<T, N> {
+    /// The number of triangles
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// The number of vertices
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len().checked_div(self.dim).unwrap_or(0)
+    }
+
+    /// The coordinates of each triangle's three vertices
+    pub fn iter_triangles(&self) -> impl Iterator<Item = [&[T]; 3]> + '_ {
+        self.indices.as_chunks::<3>().0.iter().map(|triangle| {
+            triangle.map(|i| {
+                let start = i.into_usize() * self.dim;
+                &self.vertices[start..start + self.dim]
+            })
+        })
+    }
+
+    /// The total area covered by the triangles, see [`triangulated_area`]
+    pub fn area(&self) -> T {
+        triangulated_area(&self.vertices, &self.indices, self.dim)
+    }
+}
+
+/// Like [`crate::earclip`], but returns the mesh together with its dimension.
+///
+/// There is no `offset` here, as the mesh's indices always refer to its own vertices.
+///
+/// ## Parameters
+/// - `polygon`: the polygon to triangulate; the first ring is the outer ring, the rest are holes
+/// - `modulo`: if provided, tesselate the triangles along every multiple of `modulo`
+pub fn earclip_mesh<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+) -> TriangleMesh<T, N> {
+    let (vertices, indices) = builder(modulo, None).triangulate(polygon);
+    TriangleMesh {
+        vertices,
+        indices,
+        dim: polygon_dim(polygon),
+    }
+}
+
+/// [`earclip_mesh`] that also reports the ring of `polygon` each vertex came from, see
+/// [`crate::flatten_with_rings`].
+///
+/// ## Returns
+/// The mesh and the ring of each original vertex. The original vertices come first in the mesh,
+/// so vertex `i` came from ring `rings[i]`; vertices past the end of `rings` were added by
+/// tesselation and lie on no ring in particular.
+pub fn earclip_mesh_with_rings<T: Float, N: Index>(
+    polygon: &[Vec<Vec<T>>],
+    modulo: Option<T>,
+) -> (TriangleMesh<T, N>, Vec<usize>) {
+    (earclip_mesh(polygon, modulo), vertex_rings(polygon))
+}
+
+/// Append a mesh to shared vertex and index buffers, e.g. to pack many meshes into one draw
+/// call.
+///
+/// The mesh's indices are offset by the number of vertices already in `dst_vertices` (counted
+/// with the mesh's `dim`, which the buffers must share), so they keep pointing at the mesh's
+/// own vertices.
+///
+/// ## Returns
+/// The offset added to the mesh's indices
+pub fn append_mesh<T: Float, N: Index>(
+    dst_vertices: &mut Vec<T>,
+    dst_indices: &mut Vec<N>,
+    src_mesh: &TriangleMesh<T, N>,
+) -> usize {
+    let offset = dst_vertices.len().checked_div(src_mesh.dim).unwrap_or(0);
+    dst_vertices.extend_from_slice(&src_mesh.vertices);
+    dst_indices.extend(
+        src_mesh
+            .indices
+            .iter()
+            .map(|i| N::from_usize(i.into_usize() + offset)),
+    );
+    offset
+}
+
+/// A polygon given as its outer ring and its holes, see [`earcut_multipolygon`]
+pub type PolygonRings<T> = (Vec<Vec<T>>, Vec<Vec<Vec<T>>>);
+
+/// Triangulate several disjoint polygons into one mesh.
+///
+/// Unlike the rings of a single polygon, where every ring after the first is a hole, each
+/// element here is its own filled region given as its outer ring and its holes. The polygons
+/// are triangulated independently and their vertices concatenated, with the indices of each
+/// polygon offset to point at its own vertices. All polygons must have the same dimension.
+///
+/// ## Returns
+/// The combined mesh and, per polygon, the range of `mesh.indices` holding its triangles
+pub fn earcut_multipolygon<T: Float, N: Index>(
+    polygons: &[PolygonRings<T>],
+) -> (TriangleMesh<T, N>, Vec<Range<usize>>) {
+    let mut mesh = TriangleMesh {
+        vertices: Vec::new(),
+        indices: Vec::new(),
+        dim: 2,
+    };
+    let mut ranges = Vec::with_capacity(polygons.len());
+    let mut store = Store::default();
+    let mut vertices = Vec::new();
+    let mut hole_indices: Vec<N> = Vec::new();
+    for (p, (outer, holes)) in polygons.iter().enumerate() {
+        let rings = core::iter::once(outer.as_slice()).chain(holes.iter().map(Vec::as_slice));
+        let dim = flatten_rings_into(rings, &mut vertices, &mut hole_indices);
+        if p == 0 {
+            mesh.dim = dim;
+        }
+        debug_assert_eq!(dim, mesh.dim, "all polygons must have the same dimension");
+        let offset = mesh.vertices.len() / mesh.dim;
+        let start = mesh.indices.len();
+        earcut_impl(&vertices, &hole_indices, dim, &mut mesh.indices, &mut store);
+        for index in &mut mesh.indices[start..] {
+            *index = N::from_usize(index.into_usize() + offset);
+        }
+        mesh.vertices.extend_from_slice(&vertices);
+        ranges.push(start..mesh.indices.len());
+    }
+
+    (mesh, ranges)
+}
+
+/// Sort a list of rings with unknown nesting, e.g. from a shapefile, into polygons: which rings
+/// are outer rings, and which rings are holes of which outer ring.
+///
+/// Nesting is decided by containment alone, so the winding of the rings doesn't matter: a ring
+/// inside no other ring is an outer ring, a ring directly inside an outer ring is one of its
+/// holes, a ring inside a hole is an outer ring again (an island), and so on. A ring's parent is
+/// the smallest ring by area that contains a point inside it. Rings must not cross each other;
+/// rings without area are left out.
+///
+/// ## Returns
+/// Per outer ring, in input order, its index and the indices of its holes; ready to build the
+/// [`PolygonRings`] of [`earcut_multipolygon`]
+pub fn classify_rings<T: Float>(rings: &[Vec<[T; 2]>]) -> Vec<(usize, Vec<usize>)> {
+    let areas: Vec<T> = rings
+        .iter()
+        .map(|ring| ring_area(ring.as_flattened(), 2).abs())
+        .collect();
+    // a point inside each ring, for the rings with area
+    let mut order: Vec<(usize, [T; 2])> = rings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ring)| Some((i, inner_point(ring)?)))
+        .collect();
+
+    // a ring can only be inside rings larger than it, so parents come first from largest down
+    order.sort_by(|a, b| {
+        areas[b.0]
+            .partial_cmp(&areas[a.0])
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut parent: Vec<Option<usize>> = alloc::vec![None; rings.len()];
+    let mut depth: Vec<usize> = alloc::vec![0; rings.len()];
+    for (k, &(i, p)) in order.iter().enumerate() {
+        // the last ring containing it before it in the order is the smallest
+        parent[i] = order[..k]
+            .iter()
+            .rev()
+            .map(|&(j, _)| j)
+            .find(|&j| areas[j] > areas[i] && point_in_polygon(rings[j].as_flattened(), 2, p));
+        depth[i] = parent[i].map_or(0, |j| depth[j] + 1);
+    }
+
+    let mut polygons: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut polygon_of: Vec<usize> = alloc::vec![usize::MAX; rings.len()];
+    order.sort_by_key(|&(i, _)| i);
+    for &(i, _) in order.iter().filter(|&&(i, _)| depth[i].is_multiple_of(2)) {
+        polygon_of[i] = polygons.len();
+        polygons.push((i, Vec::new()));
+    }
+    for &(i, _) in order.iter().filter(|&&(i, _)| !depth[i].is_multiple_of(2)) {
+        if let Some(outer) = parent[i] {
+            polygons[polygon_of[outer]].1.push(i);
+        }
+    }
+
+    polygons
+}
+
+/// Extrude a 2D footprint, e.g. a building outline, into a closed prism of the given height.
+///
+/// The mesh has a bottom cap at `z = 0`, a top cap at `z = height` and a wall of two triangles
+/// per ring edge, including inner walls around holes. Every triangle is wound
+/// counter-clockwise seen from outside the prism, so normals by the right-hand rule point
+/// outward. Each footprint point becomes a bottom and a top vertex, in that order; caps and walls
+/// share them, so use flat normals (see [`crate::compute_normals`]) for hard edges.
+///
+/// ## Parameters
+/// - `footprint`: the outer ring followed by its holes, in either winding
+/// - `height`: how far to extrude along z
+pub fn extrude<T: Float, N: Index>(footprint: &[Vec<[T; 2]>], height: T) -> TriangleMesh<T, N> {
+    let rings: Vec<&[[T; 2]]> = footprint.iter().map(Vec::as_slice).collect();
+    let (flat, cap): (Vec<T>, Vec<N>) = earcut_points2d(&rings);
+    let count = flat.len() / 2;
+    let mut vertices = Vec::with_capacity(count * 6);
+    for z in [T::zero(), height] {
+        for &[x, y] in flat.as_chunks::<2>().0 {
+            vertices.extend([x, y, z]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(cap.len() * 2 + count * 6);
+    let point = |i: usize| [flat[i * 2], flat[i * 2 + 1]];
+    for triangle in cap.as_chunks::<3>().0 {
+        let [a, b, c] = triangle.map(|i| i.into_usize());
+        let [pa, pb, pc] = [point(a), point(b), point(c)];
+        let cross = (pb[0] - pa[0]) * (pc[1] - pa[1]) - (pb[1] - pa[1]) * (pc[0] - pa[0]);
+        // counter-clockwise seen from above faces up
+        let [b, c] = if cross > T::zero() { [b, c] } else { [c, b] };
+        indices.extend([a, c, b, a + count, b + count, c + count]);
+    }
+
+    let mut start = 0;
+    for (r, ring) in footprint.iter().enumerate() {
+        let len = ring.len();
+        let area = (0..len).fold(T::zero(), |sum, k| {
+            let ([x0, y0], [x1, y1]) = (ring[k], ring[(k + 1) % len]);
+            sum + x0 * y1 - x1 * y0
+        });
+        // walking outer rings counter-clockwise and holes clockwise puts the outside on the right
+        let forward = (area > T::zero()) == (r == 0);
+        for k in 0..len {
+            let (next, this) = (start + (k + 1) % len, start + k);
+            let (a, b) = if forward { (this, next) } else { (next, this) };
+            if point(a) == point(b) {
+                continue;
+            }
+            indices.extend([a, b, b + count, a, b + count, a + count]);
+        }
+        start += len;
+    }
+
+    // extruding downward turns the prism inside out
+    if height < T::zero() {
+        for triangle in indices.as_chunks_mut::<3>().0 {
+            triangle.swap(1, 2);
+        }
+    }
+
+    TriangleMesh {
+        vertices,
+        indices: indices.into_iter().map(N::from_usize).collect(),
+        dim: 3,
+    }
+}
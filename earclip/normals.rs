@@ -0,0 +1,281 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::vec3::{cross, dot, sub};
+use crate::{earclip_arrays_3d, Index};
+
+/// How [`compute_normals`] computes its normals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NormalMode {
+    /// One normal per triangle
+    Flat,
+    /// One normal per vertex, averaging the adjacent triangles weighted by their area
+    #[default]
+    Smooth,
+}
+
+/// Compute unit normals for a 3D triangle mesh.
+///
+/// With [`NormalMode::Smooth`] three values `[nx, ny, nz]` are returned per vertex, with
+/// [`NormalMode::Flat`] three per triangle. Normals follow the right-hand rule of each
+/// triangle's winding. Vertices not used by any triangle get a zero normal.
+///
+/// 2D meshes (`dim != 3`) carry no depth, so all returned normals are zero.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex
+/// - `mode`: flat (per triangle) or smooth (per vertex) normals
+pub fn compute_normals<T: Float, N: Index>(
+    vertices: &[T],
+    indices: &[N],
+    dim: usize,
+    mode: NormalMode,
+) -> Vec<T> {
+    let triangles = indices.as_chunks::<3>().0;
+    let len = match mode {
+        NormalMode::Flat => triangles.len() * 3,
+        NormalMode::Smooth if dim == 0 => 0,
+        NormalMode::Smooth => vertices.len() / dim * 3,
+    };
+    let mut normals = vec![T::zero(); len];
+    if dim != 3 {
+        return normals;
+    }
+
+    for (t, triangle) in triangles.iter().enumerate() {
+        let [a, b, c] = triangle.map(|i| i.into_usize());
+        let point = |i: usize| [vertices[i * 3], vertices[i * 3 + 1], vertices[i * 3 + 2]];
+        // the cross product's length is twice the triangle area, which weights smooth normals
+        let normal = cross(sub(point(b), point(a)), sub(point(c), point(a)));
+        match mode {
+            NormalMode::Flat => normals[t * 3..t * 3 + 3].copy_from_slice(&normalize(normal)),
+            NormalMode::Smooth => {
+                for i in [a, b, c] {
+                    for axis in 0..3 {
+                        normals[i * 3 + axis] = normals[i * 3 + axis] + normal[axis];
+                    }
+                }
+            }
+        }
+    }
+    if mode == NormalMode::Smooth {
+        for normal in normals.as_chunks_mut::<3>().0 {
+            *normal = normalize(*normal);
+        }
+    }
+
+    normals
+}
+
+/// Triangulate a polygon like [`crate::earclip`] and interleave each vertex's position with its
+/// smooth normal, `[x, y, z, nx, ny, nz, ...]`, ready to upload as a single GPU vertex buffer.
+///
+/// 2D polygons get `z = 0`. The triangles are cut by x and y and wound counter-clockwise seen
+/// from above (in y-up coordinates), so the normals of a planar polygon all equal its normal,
+/// on the side of +z. Vertices not used by any triangle, such as a closing point, get a zero
+/// normal.
+///
+/// ## Returns
+/// The interleaved vertices, six values per vertex, and the triangle indices
+pub fn earclip_with_normals_3d<T: Float, N: Index>(polygon: &[Vec<Vec<T>>]) -> (Vec<T>, Vec<N>) {
+    let (positions, indices) = earclip_arrays_3d::<T, N>(polygon, None, None);
+    let flat: Vec<T> = positions.iter().flatten().copied().collect();
+    let normals = compute_normals(&flat, &indices, 3, NormalMode::Smooth);
+    let vertices = positions
+        .iter()
+        .zip(normals.as_chunks::<3>().0)
+        .flat_map(|(p, n)| [p[0], p[1], p[2], n[0], n[1], n[2]])
+        .collect();
+    (vertices, indices)
+}
+
+/// The plane [`planar_uvs`] projects onto
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Plane {
+    /// Drop z: u follows x and v follows y
+    #[default]
+    XY,
+    /// Drop y: u follows x and v follows z
+    XZ,
+    /// Drop x: u follows y and v follows z
+    YZ,
+    /// The plane of the polygon itself, from its normal. u runs horizontally and v up the
+    /// steepest slope (along z for a wall), so a flat polygon maps like [`Plane::XY`]
+    BestFit,
+}
+
+/// Generate texture coordinates by projecting vertices onto a plane, e.g. for the roofs and
+/// walls of extruded building footprints.
+///
+/// Two values `[u, v]` are returned per vertex, stretched so the vertices' bounding box in the
+/// plane maps onto `[0, 1]` on both axes. An axis along which all vertices project to the same
+/// value gets zero. [`Plane::BestFit`] takes the normal of the vertices as one ring (Newell's
+/// method) and falls back to [`Plane::XY`] if they have no area. 2D vertices are treated as
+/// lying at `z = 0`.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `dim`: the number of coordinates per vertex
+/// - `plane`: the plane to project onto
+pub fn planar_uvs<T: Float>(vertices: &[T], dim: usize, plane: Plane) -> Vec<T> {
+    if dim < 2 {
+        return Vec::new();
+    }
+    let points: Vec<[T; 3]> = vertices
+        .chunks_exact(dim)
+        .map(|p| [p[0], p[1], if dim > 2 { p[2] } else { T::zero() }])
+        .collect();
+    let (zero, one) = (T::zero(), T::one());
+    let axes = match plane {
+        Plane::XY => [[one, zero, zero], [zero, one, zero]],
+        Plane::XZ => [[one, zero, zero], [zero, zero, one]],
+        Plane::YZ => [[zero, one, zero], [zero, zero, one]],
+        Plane::BestFit => best_fit_axes(&points),
+    };
+
+    let mut uvs: Vec<T> = points
+        .iter()
+        .flat_map(|&p| axes.map(|axis| dot(p, axis)))
+        .collect();
+    for k in 0..2 {
+        let coords = || uvs.iter().skip(k).step_by(2);
+        let min = coords().fold(T::infinity(), |min, &c| min.min(c));
+        let max = coords().fold(T::neg_infinity(), |max, &c| max.max(c));
+        let size = max - min;
+        for c in uvs.iter_mut().skip(k).step_by(2) {
+            *c = if size > zero { (*c - min) / size } else { zero };
+        }
+    }
+
+    uvs
+}
+
+/// the u and v axes of the plane through the points as one ring
+fn best_fit_axes<T: Float>(points: &[[T; 3]]) -> [[T; 3]; 2] {
+    let (zero, one) = (T::zero(), T::one());
+    let (x, z) = ([one, zero, zero], [zero, zero, one]);
+    // Newell's method: the sum of the edges' cross products, robust for concave rings
+    let mut normal = [zero; 3];
+    let mut prev = points.last().copied().unwrap_or([zero; 3]);
+    for &p in points {
+        normal = [
+            normal[0] + (prev[1] - p[1]) * (prev[2] + p[2]),
+            normal[1] + (prev[2] - p[2]) * (prev[0] + p[0]),
+            normal[2] + (prev[0] - p[0]) * (prev[1] + p[1]),
+        ];
+        prev = p;
+    }
+    let normal = normalize(normal);
+    if normal == [zero; 3] {
+        return [x, [zero, one, zero]];
+    }
+    // horizontal u, unless the plane is horizontal itself
+    let u = normalize(cross(z, normal));
+    let u = if u == [zero; 3] { x } else { u };
+    [u, cross(normal, u)]
+}
+
+/// scale to unit length, leaving zero vectors untouched
+fn normalize<T: Float>(v: [T; 3]) -> [T; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len.is_zero() {
+        v
+    } else {
+        v.map(|c| c / len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earclip;
+
+    #[test]
+    fn simple_triangle_points_up() {
+        let polygon = vec![vec![
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+        ]];
+        let (vertices, indices) = earclip::<f64, usize>(&polygon, None, None);
+
+        let smooth = compute_normals(&vertices, &indices, 3, NormalMode::Smooth);
+        assert_eq!(smooth, [0.0, 0.0, 1.0].repeat(3));
+        let flat = compute_normals(&vertices, &indices, 3, NormalMode::Flat);
+        assert_eq!(flat, vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn smooth_is_area_weighted() {
+        // a large triangle in the xy plane and a small one in the xz plane share vertex 0
+        let vertices = [
+            0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0,
+        ];
+        let indices = [0_u32, 1, 2, 0, 3, 4];
+        let normals = compute_normals(&vertices, &indices, 3, NormalMode::Smooth);
+        assert!(normals[2] > 0.99 && normals[1] > 0.0);
+    }
+
+    #[test]
+    fn interleaved_normals() {
+        // a unit square at z = 2, then the same square tilted up along x
+        let square = |z: fn(f64) -> f64| {
+            vec![vec![
+                vec![0.0, 0.0, z(0.0)],
+                vec![1.0, 0.0, z(1.0)],
+                vec![1.0, 1.0, z(1.0)],
+                vec![0.0, 1.0, z(0.0)],
+            ]]
+        };
+        let (vertices, indices) = earclip_with_normals_3d::<f64, u32>(&square(|_| 2.0));
+        assert_eq!(indices.len(), 6);
+        assert_eq!(vertices.len(), 4 * 6);
+        for vertex in vertices.chunks(6) {
+            assert_eq!(vertex[2], 2.0);
+            assert_eq!(vertex[3..], [0.0, 0.0, 1.0]);
+        }
+
+        let (vertices, _) = earclip_with_normals_3d::<f64, u32>(&square(|x| x));
+        let tilted = core::f64::consts::FRAC_1_SQRT_2;
+        for vertex in vertices.chunks(6) {
+            assert_eq!(vertex[2], vertex[0]);
+            let expected = [-tilted, 0.0, tilted];
+            assert!(vertex[3..]
+                .iter()
+                .zip(expected)
+                .all(|(a, b)| (a - b).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn uvs_of_a_square() {
+        // a unit square standing upright, facing -y
+        let vertices = [2.0, 5.0, 1.0, 4.0, 5.0, 1.0, 4.0, 5.0, 3.0, 2.0, 5.0, 3.0];
+        let expected = vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        assert_eq!(planar_uvs(&vertices, 3, Plane::XZ), expected);
+        assert_eq!(planar_uvs(&vertices, 3, Plane::BestFit), expected);
+        // seen from above it is a line
+        assert_eq!(
+            planar_uvs(&vertices, 3, Plane::XY),
+            vec![0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0]
+        );
+
+        // tilted at 45 degrees, counter-clockwise seen from above
+        let vertices = [0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 2.0, 2.0, 0.0, 2.0, 2.0];
+        let uvs = planar_uvs(&vertices, 3, Plane::BestFit);
+        let expected = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        assert!(uvs.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert!(uvs.iter().all(|&c| (0.0..=1.0).contains(&c)));
+    }
+
+    #[test]
+    fn two_dimensional_is_zero() {
+        let vertices = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        let normals = compute_normals(&vertices, &[0_usize, 1, 2], 2, NormalMode::Smooth);
+        assert_eq!(normals, vec![0.0; 9]);
+    }
+}
@@ -0,0 +1,327 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use num_traits::float::Float;
+
+use crate::{earcut, point_in_polygon, ring_area};
+
+/// How [`offset_polygon`] fills the gap where the offset edges of a corner move apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinStyle {
+    /// Extend both edges until they meet; corners whose miter would reach out more than 4 times
+    /// the distance are beveled instead
+    #[default]
+    Miter,
+    /// An arc around the corner, in steps of at most 11.25°
+    Round,
+    /// A straight cut across the corner
+    Bevel,
+}
+
+/// how far, in multiples of the distance, a miter may reach out from its corner
+const MITER_LIMIT: f64 = 4.0;
+
+/// Grow (positive `distance`) or shrink (negative) a ring by a fixed distance, a buffer, e.g. to
+/// triangulate the outline of a footprint.
+///
+/// Every edge moves `distance` outward along its normal and the corners are joined with `join`
+/// where the edges move apart; where they move together they are cut off at the point the
+/// edges meet. Parts of a shrunk ring that are narrower than twice the distance collapse and
+/// are dropped, which can split it into several rings; a grown ring can close off a gap into a
+/// hole. There is no straight skeleton, so this is meant for simple rings and distances on the
+/// order of their features.
+///
+/// ## Parameters
+/// - `ring`: the points of a simple ring in either winding, open or closed
+/// - `distance`: how far to move the edges outward
+/// - `join`: how to join the edges around the corners they move apart at
+///
+/// ## Returns
+/// Open rings ready for [`crate::earcut_points2d`]. Rings wound like `ring` come first and are
+/// polygons of their own; the rings after them are wound the other way and are holes of the
+/// polygon around them. Empty if the ring has no area or shrinks away.
+pub fn offset_polygon<T: Float>(ring: &[[T; 2]], distance: T, join: JoinStyle) -> Vec<Vec<[T; 2]>> {
+    let mut ring = ring.to_vec();
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    let area = ring_area(ring.as_flattened(), 2);
+    if ring.len() < 3 || area.is_zero() || !distance.is_finite() {
+        return Vec::new();
+    }
+    if distance.is_zero() {
+        return vec![ring];
+    }
+    // offset counter-clockwise (y-up), so outward is to the right of every edge
+    let reversed = area < T::zero();
+    if reversed {
+        ring.reverse();
+    }
+
+    let curve = offset_curve(&ring, distance, join);
+    // the offset covers what the curve winds around a positive number of times; loops that
+    // collapsed wind the other way
+    let loops: Vec<(Vec<[T; 2]>, T, [T; 2])> = split_loops(&curve)
+        .into_iter()
+        .filter_map(|part| {
+            let area = ring_area(part.as_flattened(), 2);
+            let inside = inner_point(&part)?;
+            Some((part, area, inside))
+        })
+        .collect();
+    let winding = |area: T, p: [T; 2]| -> i32 {
+        // a loop can only be inside loops larger than it
+        let around = loops.iter().filter(|(part, other, _)| {
+            other.abs() > area.abs() && point_in_polygon(part.as_flattened(), 2, p)
+        });
+        around
+            .map(|(_, other, _)| if *other > T::zero() { 1 } else { -1 })
+            .sum()
+    };
+    let mut rings: Vec<Vec<[T; 2]>> = loops
+        .iter()
+        .filter(|(_, area, inside)| {
+            let outside = winding(*area, *inside);
+            (*area > T::zero() && outside == 0) || (*area < T::zero() && outside == 1)
+        })
+        // a ring shrunk past its middle turns inside out and back, so check it is deep enough
+        .filter(|(_, _, inside)| {
+            distance > T::zero() || distance_to_ring_sq(&ring, *inside) >= distance * distance
+        })
+        .map(|(part, _, _)| part.clone())
+        .collect();
+    rings.sort_by_key(|part| ring_area(part.as_flattened(), 2) < T::zero());
+    if reversed {
+        rings.iter_mut().for_each(|ring| ring.reverse());
+    }
+    rings
+}
+
+/// the offset points of every corner of a counter-clockwise ring, in order; the curve crosses
+/// itself where parts of the ring collapse
+fn offset_curve<T: Float>(ring: &[[T; 2]], distance: T, join: JoinStyle) -> Vec<[T; 2]> {
+    let normal = |a: [T; 2], b: [T; 2]| {
+        let [dx, dy] = [b[0] - a[0], b[1] - a[1]];
+        let len = dx.hypot(dy);
+        [dy / len, -dx / len]
+    };
+    let two = T::one() + T::one();
+    let limit = T::from(MITER_LIMIT).unwrap_or(two);
+    let pi = T::from(PI).unwrap_or_else(T::zero);
+    let step = pi / T::from(16.0).unwrap_or(two);
+
+    let n = ring.len();
+    let mut curve = Vec::with_capacity(n * 2);
+    for i in 0..n {
+        let [prev, p, next] = [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]];
+        let [n0, n1] = [normal(prev, p), normal(p, next)];
+        let cross = n0[0] * n1[1] - n0[1] * n1[0];
+        let dot = n0[0] * n1[0] + n0[1] * n1[1];
+        let at = |n: [T; 2]| [p[0] + n[0] * distance, p[1] + n[1] * distance];
+        // a left turn pulls the edges apart outward, a right turn inward, a spike both ways
+        let apart = cross * distance > T::zero() || (cross.is_zero() && dot < T::zero());
+
+        if !apart || join == JoinStyle::Miter {
+            // the miter reaches 1 / cos(half the turn) times the distance out
+            if (T::one() + dot) * limit * limit >= two {
+                let scale = T::one() + dot;
+                curve.push(at([(n0[0] + n1[0]) / scale, (n0[1] + n1[1]) / scale]));
+            } else {
+                curve.extend([at(n0), at(n1)]);
+            }
+        } else if join == JoinStyle::Round {
+            let sweep = if cross.is_zero() {
+                pi * distance.signum()
+            } else {
+                cross.atan2(dot)
+            };
+            let steps = (sweep.abs() / step).ceil().to_usize().unwrap_or(1).max(1);
+            curve.push(at(n0));
+            for k in 1..steps {
+                let angle = sweep * T::from(k).unwrap_or_else(T::zero)
+                    / T::from(steps).unwrap_or_else(T::one);
+                let (sin, cos) = angle.sin_cos();
+                curve.push(at([n0[0] * cos - n0[1] * sin, n0[0] * sin + n0[1] * cos]));
+            }
+            curve.push(at(n1));
+        } else {
+            curve.extend([at(n0), at(n1)]);
+        }
+    }
+    curve.dedup();
+    while curve.len() > 1 && curve.first() == curve.last() {
+        curve.pop();
+    }
+    curve
+}
+
+/// Split a closed curve into loops that don't cross themselves or each other, by reconnecting
+/// the curve at every point where two of its edges cross, where it runs through one of its own
+/// points, or where two of its edges start to overlap.
+fn split_loops<T: Float>(curve: &[[T; 2]]) -> Vec<Vec<[T; 2]>> {
+    let n = curve.len();
+    // the crossings along each edge, as (position along the edge, crossing) pairs
+    let mut on_edge: Vec<Vec<(T, usize)>> = vec![Vec::new(); n];
+    let mut crossings: Vec<[T; 2]> = Vec::new();
+    let mut cross_at = |[i, j]: [usize; 2], [t, u]: [T; 2], p: [T; 2]| {
+        let k = crossings.len();
+        crossings.push(p);
+        // the end of an edge is the start of the next one
+        for (edge, at) in [(i, t), (j, u)] {
+            if at < T::one() {
+                on_edge[edge].push((at, k));
+            } else {
+                on_edge[(edge + 1) % n].push((T::zero(), k));
+            }
+        }
+    };
+    for i in 0..n {
+        let [a1, a2] = [curve[i], curve[(i + 1) % n]];
+        // edges next to each other only share their end point
+        for j in i + 2..n - usize::from(i == 0) {
+            let [b1, b2] = [curve[j], curve[(j + 1) % n]];
+            if let Some((t, u)) = crossing(a1, a2, b1, b2) {
+                let p = [a1[0] + (a2[0] - a1[0]) * t, a1[1] + (a2[1] - a1[1]) * t];
+                cross_at([i, j], [t, u], p);
+            } else if let Some(t) = overlap_start(a1, a2, b1, b2) {
+                // reconnecting at the start of an overlap is enough to take it apart
+                let p = [a1[0] + (a2[0] - a1[0]) * t, a1[1] + (a2[1] - a1[1]) * t];
+                cross_at([i, j], [t, position(b1, b2, p)], p);
+            }
+        }
+        // a point of the curve on the edge, where neither of its own edges runs along it
+        for j in (0..n).filter(|&j| j != i && j != (i + 1) % n) {
+            let [prev, p, next] = [curve[(j + n - 1) % n], curve[j], curve[(j + 1) % n]];
+            if let Some(t) =
+                on_segment(a1, a2, p).filter(|_| !on_line(a1, a2, prev) && !on_line(a1, a2, next))
+            {
+                cross_at([i, j], [t, T::zero()], p);
+            }
+        }
+    }
+    if crossings.is_empty() {
+        return vec![curve.to_vec()];
+    }
+
+    // the curve with the crossings inserted, each crossing twice
+    let mut points: Vec<[T; 2]> = Vec::with_capacity(n + 2 * crossings.len());
+    let mut seen: Vec<Option<usize>> = vec![None; crossings.len()];
+    let mut next: Vec<usize> = Vec::with_capacity(points.capacity());
+    let mut swaps = Vec::with_capacity(crossings.len());
+    for (i, edge) in on_edge.iter_mut().enumerate() {
+        points.push(curve[i]);
+        edge.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+        for &(_, k) in edge.iter() {
+            match seen[k] {
+                Some(other) => swaps.push((other, points.len())),
+                None => seen[k] = Some(points.len()),
+            }
+            points.push(crossings[k]);
+        }
+    }
+    next.extend((1..points.len()).chain([0]));
+    // leave each crossing along the other edge
+    for (a, b) in swaps {
+        next.swap(a, b);
+    }
+
+    let mut visited = vec![false; points.len()];
+    let mut loops = Vec::new();
+    for start in 0..points.len() {
+        let mut part = Vec::new();
+        let mut p = start;
+        while !visited[p] {
+            visited[p] = true;
+            part.push(points[p]);
+            p = next[p];
+        }
+        // a crossing at a point of the curve repeats it
+        part.dedup();
+        if part.len() > 1 && part.first() == part.last() {
+            part.pop();
+        }
+        if !part.is_empty() {
+            loops.push(part);
+        }
+    }
+    loops
+}
+
+/// where the segments a1-a2 and b1-b2 cross, as positions along each; touching doesn't count
+fn crossing<T: Float>(a1: [T; 2], a2: [T; 2], b1: [T; 2], b2: [T; 2]) -> Option<(T, T)> {
+    let [ax, ay] = [a2[0] - a1[0], a2[1] - a1[1]];
+    let [bx, by] = [b2[0] - b1[0], b2[1] - b1[1]];
+    let den = ax * by - ay * bx;
+    if den.is_zero() {
+        return None;
+    }
+    let [cx, cy] = [b1[0] - a1[0], b1[1] - a1[1]];
+    let t = (cx * by - cy * bx) / den;
+    let u = (cx * ay - cy * ax) / den;
+    let inside = |v: T| v > T::zero() && v < T::one();
+    (inside(t) && inside(u)).then_some((t, u))
+}
+
+/// whether `p` lies on the line through a and b
+fn on_line<T: Float>(a: [T; 2], b: [T; 2], p: [T; 2]) -> bool {
+    ((b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])).is_zero()
+}
+
+/// the position of `p` along a-b, projected onto it
+fn position<T: Float>(a: [T; 2], b: [T; 2], p: [T; 2]) -> T {
+    let [dx, dy] = [b[0] - a[0], b[1] - a[1]];
+    ((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / (dx * dx + dy * dy)
+}
+
+/// a point strictly inside a loop, the center of its largest triangle; `None` if it has no area
+pub(crate) fn inner_point<T: Float>(part: &[[T; 2]]) -> Option<[T; 2]> {
+    let triangles: Vec<usize> = earcut(part.as_flattened(), &[], 2);
+    let three = T::from(3.0).unwrap_or_else(T::one);
+    triangles
+        .as_chunks::<3>()
+        .0
+        .iter()
+        .map(|&[a, b, c]| [part[a], part[b], part[c]])
+        .max_by(|x, y| {
+            let area = |[a, b, c]: &[[T; 2]; 3]| {
+                ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs()
+            };
+            area(x)
+                .partial_cmp(&area(y))
+                .unwrap_or(core::cmp::Ordering::Equal)
+        })
+        .map(|[a, b, c]| [(a[0] + b[0] + c[0]) / three, (a[1] + b[1] + c[1]) / three])
+}
+
+/// where `p` lies on the segment a-b, strictly between its ends
+fn on_segment<T: Float>(a: [T; 2], b: [T; 2], p: [T; 2]) -> Option<T> {
+    let t = position(a, b, p);
+    (on_line(a, b, p) && t > T::zero() && t < T::one()).then_some(t)
+}
+
+/// where along a1-a2 the collinear segment b1-b2 starts to overlap it, if they share more than
+/// a point
+fn overlap_start<T: Float>(a1: [T; 2], a2: [T; 2], b1: [T; 2], b2: [T; 2]) -> Option<T> {
+    if !(on_line(a1, a2, b1) && on_line(a1, a2, b2)) {
+        return None;
+    }
+    let [t1, t2] = [position(a1, a2, b1), position(a1, a2, b2)];
+    let start = t1.min(t2).max(T::zero());
+    (start < t1.max(t2).min(T::one())).then_some(start)
+}
+
+/// the squared distance from a point to the nearest edge of a ring
+fn distance_to_ring_sq<T: Float>(ring: &[[T; 2]], p: [T; 2]) -> T {
+    let n = ring.len();
+    (0..n).fold(T::infinity(), |min, i| {
+        let [a, b] = [ring[i], ring[(i + 1) % n]];
+        let t = position(a, b, p).max(T::zero()).min(T::one());
+        let [dx, dy] = [
+            a[0] + (b[0] - a[0]) * t - p[0],
+            a[1] + (b[1] - a[1]) * t - p[1],
+        ];
+        min.min(dx * dx + dy * dy)
+    })
+}
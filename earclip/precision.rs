@@ -0,0 +1,33 @@
+use alloc::vec::Vec;
+
+use half::f16;
+
+/// Convert a vertex buffer to half precision, e.g. to upload it to the GPU.
+///
+/// Triangulate in `f32` (or `f64`) and convert the finished buffer, as `f16` is far too coarse
+/// to triangulate in. Values are rounded to the nearest `f16`, which keeps only 11 significant
+/// bits: integers are exact up to 2048, larger values snap to multiples of growing powers of
+/// two, and magnitudes above 65504 become infinite. Scale or translate the vertices into a small
+/// range first if that loses too much.
+pub fn to_f16(vertices: &[f32]) -> Vec<f16> {
+    vertices.iter().map(|&v| f16::from_f32(v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn round_trip() {
+        let exact = [0.0, -0.5, 1.0, 2048.0, 0.125, -3.75];
+        let halves = to_f16(&exact);
+        assert_eq!(halves.iter().map(|h| h.to_f32()).collect::<Vec<_>>(), exact);
+
+        let lossy = to_f16(&[0.1, 2049.0, 70000.0]);
+        assert!((lossy[0].to_f32() - 0.1).abs() < 1e-4);
+        assert_eq!(lossy[1].to_f32(), 2048.0);
+        assert_eq!(lossy[2], f16::INFINITY);
+        assert_eq!(to_f16(&[]), vec![]);
+    }
+}
@@ -0,0 +1,15 @@
+//! The functions and traits most callers need, importable in one go:
+//!
+//! ```
+//! use earclip::prelude::*;
+//!
+//! let polygon = vec![vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0, 1.0], vec![0.0, 1.0]]];
+//! let (vertices, hole_indices, dim) = flatten::<f64, usize>(&polygon);
+//! let indices = earcut(&vertices, &hole_indices, dim);
+//! assert_eq!(deviation(&vertices, &hole_indices, dim, &indices), 0.0);
+//! ```
+
+pub use crate::{
+    deviation, earclip, earclip_mesh, earcut, earcut_into, flatten, tesselate, try_earclip,
+    try_earcut, Earclip, EarcutError, Index, Point2D, Point3D, Store, TriangleMesh,
+};
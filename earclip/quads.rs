@@ -0,0 +1,101 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::vec3::{add, cross, dot, sub};
+use crate::Index;
+
+/// Merge pairs of adjacent triangles into quads where they form one.
+///
+/// Two triangles are merged when they share an edge, have the same winding, and together form
+/// a strictly convex quad. For 3D meshes their normals also have to be within about a degree
+/// of each other, so only roughly coplanar pairs are merged. Triangles are paired greedily in
+/// order, each at most once.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The triangles left unmerged, three indices each, and the quads, four indices each in the
+/// winding of their triangles
+pub fn merge_to_quads<T: Float, N: Index>(
+    vertices: &[T],
+    indices: &[N],
+    dim: usize,
+) -> (Vec<N>, Vec<N>) {
+    let point = |i: usize| {
+        let z = if dim > 2 {
+            vertices[i * dim + 2]
+        } else {
+            T::zero()
+        };
+        [vertices[i * dim], vertices[i * dim + 1], z]
+    };
+    let triangles = indices.as_chunks::<3>().0;
+    let mut edges: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    for (t, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (triangle[k].into_usize(), triangle[(k + 1) % 3].into_usize());
+            edges.entry((a.min(b), a.max(b))).or_default().push(t);
+        }
+    }
+    // cos(1°), how far apart the normals of a merged pair may be
+    let min_cos = T::from(0.99985).unwrap_or_else(T::one);
+
+    let mut merged = vec![false; triangles.len()];
+    let mut quads = Vec::new();
+    for (t1, triangle) in triangles.iter().enumerate() {
+        if merged[t1] {
+            continue;
+        }
+        let t = triangle.map(|i| i.into_usize());
+        for k in 0..3 {
+            let [a, b, c] = [t[k], t[(k + 1) % 3], t[(k + 2) % 3]];
+            let [x, y] = edges[&(a.min(b), a.max(b))][..] else {
+                continue;
+            };
+            let t2 = if x == t1 { y } else { x };
+            if merged[t2] {
+                continue;
+            }
+            // opposite directions along the shared edge mean both have the same winding
+            let other = triangles[t2].map(|i| i.into_usize());
+            let Some(d) = (0..3)
+                .find(|&j| other[j] == b && other[(j + 1) % 3] == a)
+                .map(|j| other[(j + 2) % 3])
+            else {
+                continue;
+            };
+            let [pa, pb, pc, pd] = [point(a), point(b), point(c), point(d)];
+            let (n1, n2) = (
+                cross(sub(pb, pa), sub(pc, pa)),
+                cross(sub(pa, pb), sub(pd, pb)),
+            );
+            let coplanar = dot(n1, n2) >= min_cos * (dot(n1, n1) * dot(n2, n2)).sqrt();
+            // going a, d, b, c every corner has to turn the same way as the triangles
+            let normal = add(n1, n2);
+            let corners = [[pc, pa, pd], [pa, pd, pb], [pd, pb, pc], [pb, pc, pa]];
+            let convex = corners
+                .iter()
+                .all(|[p, q, r]| dot(cross(sub(*q, *p), sub(*r, *q)), normal) > T::zero());
+            if coplanar && convex {
+                merged[t1] = true;
+                merged[t2] = true;
+                quads.extend([a, d, b, c].map(N::from_usize));
+                break;
+            }
+        }
+    }
+
+    let unmerged = triangles
+        .iter()
+        .zip(&merged)
+        .filter(|(_, &merged)| !merged)
+        .flat_map(|(triangle, _)| *triangle)
+        .collect();
+    (unmerged, quads)
+}
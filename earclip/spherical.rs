@@ -0,0 +1,460 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::float::Float;
+
+use crate::{push_triangle, Index};
+
+/// Tesselate a lon/lat mesh along meridians and parallels
+///
+/// The first two coordinates of every vertex are read as longitude and latitude in degrees.
+/// Triangles are split wherever they cross a multiple of `lon_modulo` or `lat_modulo`, and the
+/// new vertices are placed on the great circle between the edge's end points rather than on
+/// the straight line in lon/lat space. Any further coordinates are interpolated linearly.
+///
+/// Triangles crossing the antimeridian (an edge spanning more than 180° of longitude) are
+/// split at ±180°, with separate vertices at 180 and -180 for either side.
+///
+/// ## Parameters
+/// - `vertices`: flattened vertices to append to
+/// - `indices`: triangle indices to append to
+/// - `lon_modulo`: the meridian spacing in degrees; non-positive values skip longitude splits
+/// - `lat_modulo`: the parallel spacing in degrees; non-positive values skip latitude splits
+/// - `dim`: the number of coordinates per vertex
+///
+/// ## Returns
+/// The number of vertices appended, see [`crate::tesselate`]
+pub fn tesselate_spherical<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut Vec<N>,
+    lon_modulo: T,
+    lat_modulo: T,
+    dim: usize,
+) -> usize {
+    let original_count = vertices.len() / dim;
+    let half_turn = degrees(180.0);
+    let full_turn = degrees(360.0);
+
+    // unwrap triangles crossing the antimeridian into longitudes beyond 180
+    let mut unwrapped: BTreeMap<usize, usize> = BTreeMap::new();
+    for triangle in indices.as_chunks_mut::<3>().0 {
+        let lons = triangle.map(|i| vertices[i.into_usize() * dim]);
+        if max(lons) - min(lons) <= half_turn {
+            continue;
+        }
+        for i in triangle.iter_mut() {
+            let v = i.into_usize();
+            if vertices[v * dim] < T::zero() {
+                let copy = *unwrapped.entry(v).or_insert_with(|| {
+                    let copy = vertices.len() / dim;
+                    vertices.extend_from_within(v * dim..(v + 1) * dim);
+                    vertices[copy * dim] = vertices[copy * dim] + full_turn;
+                    copy
+                });
+                *i = N::from_usize(copy);
+            }
+        }
+    }
+
+    let mut splitter = Splitter {
+        vertices: &mut *vertices,
+        indices: &mut *indices,
+        dim,
+        edges: BTreeMap::new(),
+    };
+    if lon_modulo > T::zero() {
+        splitter.split_axis(0, |lo, hi| {
+            let line = next_multiple(lo, lon_modulo);
+            // the antimeridian always gets split
+            if lo < half_turn && half_turn < hi && half_turn < line {
+                half_turn
+            } else {
+                line
+            }
+        });
+    } else {
+        splitter.split_axis(0, |_, _| half_turn);
+    }
+    if lat_modulo > T::zero() {
+        splitter.split_axis(1, |lo, _| next_multiple(lo, lat_modulo));
+    }
+
+    // wrap the far side of the antimeridian back into -180..180
+    let mut wrapped: BTreeMap<usize, usize> = BTreeMap::new();
+    for triangle in indices.as_chunks_mut::<3>().0 {
+        let lons = triangle.map(|i| vertices[i.into_usize() * dim]);
+        if min(lons) < half_turn || max(lons) == half_turn {
+            continue;
+        }
+        for i in triangle.iter_mut() {
+            let v = i.into_usize();
+            if vertices[v * dim] == half_turn {
+                // the seam vertex is shared with the near side, so it gets a -180 twin
+                let twin = *wrapped.entry(v).or_insert_with(|| {
+                    let twin = vertices.len() / dim;
+                    vertices.extend_from_within(v * dim..(v + 1) * dim);
+                    vertices[twin * dim] = -half_turn;
+                    twin
+                });
+                *i = N::from_usize(twin);
+            }
+        }
+    }
+    // vertices beyond 180 only ever belong to the far side
+    for lon in vertices.iter_mut().step_by(dim) {
+        if *lon > half_turn {
+            *lon = *lon - full_turn;
+        }
+    }
+
+    vertices.len() / dim - original_count
+}
+
+/// Cut a lon/lat polygon crossing the antimeridian into pieces that each stay within -180..180
+///
+/// The input must be in lon/lat degrees. Consecutive points more than 180° of longitude apart
+/// are taken to wrap around the antimeridian rather than to span the globe. Every ring is
+/// unwrapped into continuous longitudes and clipped at ±180°, so each returned polygon can be
+/// triangulated on its own, e.g. by [`crate::earclip`]. Polygons that don't cross are returned
+/// as is.
+///
+/// ## Parameters
+/// - `polygon`: the outer ring followed by its holes
+pub fn split_antimeridian<T: Float>(polygon: &[Vec<Vec<T>>]) -> Vec<Vec<Vec<Vec<T>>>> {
+    let half_turn = degrees::<T>(180.0);
+    let full_turn = degrees::<T>(360.0);
+    let Some(outer) = polygon.first().filter(|ring| !ring.is_empty()) else {
+        return alloc::vec![polygon.to_vec()];
+    };
+
+    // holes are unwrapped onto the same turn as the outer ring's first point
+    let reference = outer[0][0];
+    let rings: Vec<Vec<Vec<T>>> = polygon.iter().map(|ring| unwrap(ring, reference)).collect();
+    let (lo, hi) = rings
+        .iter()
+        .flatten()
+        .fold((reference, reference), |(lo, hi), point| {
+            (lo.min(point[0]), hi.max(point[0]))
+        });
+    if lo >= -half_turn && hi <= half_turn {
+        return alloc::vec![polygon.to_vec()];
+    }
+
+    // clip against every 360° wide strip centered on a multiple of 360 the polygon touches
+    let first = ((lo + half_turn) / full_turn).floor();
+    let last = ((hi + half_turn) / full_turn).ceil();
+    let mut pieces = Vec::new();
+    let mut turn = first;
+    while turn < last {
+        let shift = turn * full_turn;
+        let (left, right) = (shift - half_turn, shift + half_turn);
+        let mut piece: Vec<Vec<Vec<T>>> = rings
+            .iter()
+            .map(|ring| clip_strip(ring, left, right))
+            .collect();
+        // the outer ring has to survive for the piece to exist
+        if piece[0].len() >= 3 {
+            piece.retain(|ring| ring.len() >= 3);
+            for point in piece.iter_mut().flatten() {
+                point[0] = point[0] - shift;
+            }
+            pieces.push(piece);
+        }
+        turn = turn + T::one();
+    }
+
+    pieces
+}
+
+/// make a ring's longitudes continuous, starting on the same turn as `reference`
+fn unwrap<T: Float>(ring: &[Vec<T>], reference: T) -> Vec<Vec<T>> {
+    let half_turn = degrees::<T>(180.0);
+    let full_turn = degrees::<T>(360.0);
+    let mut prev = reference;
+    ring.iter()
+        .map(|point| {
+            let mut point = point.clone();
+            while point[0] - prev > half_turn {
+                point[0] = point[0] - full_turn;
+            }
+            while prev - point[0] > half_turn {
+                point[0] = point[0] + full_turn;
+            }
+            prev = point[0];
+            point
+        })
+        .collect()
+}
+
+/// Sutherland-Hodgman clip of a ring to the longitudes `left..=right`
+fn clip_strip<T: Float>(ring: &[Vec<T>], left: T, right: T) -> Vec<Vec<T>> {
+    let ring = clip_side(ring, left, true);
+    clip_side(&ring, right, false)
+}
+
+/// keep the part of a ring right of (or left of, if not `keep_greater`) the meridian `lon`
+fn clip_side<T: Float>(ring: &[Vec<T>], lon: T, keep_greater: bool) -> Vec<Vec<T>> {
+    let inside = |point: &Vec<T>| (point[0] >= lon) == keep_greater || point[0] == lon;
+    let mut out = Vec::with_capacity(ring.len());
+    for (i, point) in ring.iter().enumerate() {
+        let prev = &ring[(i + ring.len() - 1) % ring.len()];
+        if inside(point) != inside(prev) {
+            let t = (lon - prev[0]) / (point[0] - prev[0]);
+            let mut cut: Vec<T> = prev
+                .iter()
+                .zip(point)
+                .map(|(&a, &b)| a + (b - a) * t)
+                .collect();
+            cut[0] = lon;
+            out.push(cut);
+        }
+        if inside(point) {
+            out.push(point.clone());
+        }
+    }
+    out
+}
+
+/// splits triangles along lines of a single axis, sharing new vertices between neighbours
+struct Splitter<'a, T: Float, N: Index> {
+    vertices: &'a mut Vec<T>,
+    indices: &'a mut Vec<N>,
+    dim: usize,
+    /// the vertex created on an edge (lower index, higher index) at an axis and line (by its
+    /// bits)
+    edges: BTreeMap<(usize, usize, usize, u64), usize>,
+}
+
+impl<T: Float, N: Index> Splitter<'_, T, N> {
+    /// split every triangle along each line `first_line(lo, hi)` returns strictly inside the
+    /// triangle's `lo..hi` range on `axis`
+    fn split_axis(&mut self, axis: usize, first_line: impl Fn(T, T) -> T) {
+        let mut i = 0;
+        while i < self.indices.len() {
+            let triangle = [0, 1, 2].map(|k| self.indices[i + k].into_usize());
+            let values = triangle.map(|v| self.vertices[v * self.dim + axis]);
+            let (lo, hi) = (min(values), max(values));
+            let line = first_line(lo, hi);
+            if !(lo < line && line < hi) {
+                i += 3;
+                continue;
+            }
+            // rotate the vertex on the line, or else the one alone on its side, to the front
+            let side = values.map(|v| v.partial_cmp(&line));
+            let a = (0..3)
+                .find(|&k| side[k] == Some(Ordering::Equal))
+                .or_else(|| (0..3).find(|&k| side[(k + 1) % 3] == side[(k + 2) % 3]))
+                .unwrap_or(0);
+            let [a, b, c] = [triangle[a], triangle[(a + 1) % 3], triangle[(a + 2) % 3]];
+            if self.vertices[a * self.dim + axis] == line {
+                let p = self.vertex_on_edge(b, c, axis, line);
+                self.replace(i, [a, b, p]);
+                push_triangle(self.indices, a, p, c);
+            } else {
+                let p = self.vertex_on_edge(a, b, axis, line);
+                let q = self.vertex_on_edge(a, c, axis, line);
+                self.replace(i, [a, p, q]);
+                push_triangle(self.indices, p, b, c);
+                push_triangle(self.indices, p, c, q);
+            }
+            // re-examine the first piece in place, the others are checked when reached
+        }
+    }
+
+    fn replace(&mut self, i: usize, triangle: [usize; 3]) {
+        for (k, v) in triangle.into_iter().enumerate() {
+            self.indices[i + k] = N::from_usize(v);
+        }
+    }
+
+    /// the (shared) vertex where the great circle from `a` to `b` crosses `line` on `axis`
+    fn vertex_on_edge(&mut self, a: usize, b: usize, axis: usize, line: T) -> usize {
+        let (a, b) = (a.min(b), a.max(b));
+        let key = (a, b, axis, line.to_f64().unwrap_or_default().to_bits());
+        if let Some(&v) = self.edges.get(&key) {
+            return v;
+        }
+        let dim = self.dim;
+        let start = [self.vertices[a * dim], self.vertices[a * dim + 1]];
+        let end = [self.vertices[b * dim], self.vertices[b * dim + 1]];
+        let arc = Arc::new(start, end);
+        // bisect for the crossing; the end points are on either side of the line
+        let ascending = start[axis] < end[axis];
+        let (mut lo, mut hi) = (T::zero(), T::one());
+        for _ in 0..64 {
+            let mid = (lo + hi) / (T::one() + T::one());
+            if (arc.at(mid)[axis] < line) == ascending {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let t = (lo + hi) / (T::one() + T::one());
+        let mut point = arc.at(t);
+        point[axis] = line;
+
+        let index = self.vertices.len() / dim;
+        self.vertices.extend_from_slice(&point);
+        for k in 2..dim {
+            let (va, vb) = (self.vertices[a * dim + k], self.vertices[b * dim + k]);
+            self.vertices.push(va + (vb - va) * t);
+        }
+        self.edges.insert(key, index);
+        index
+    }
+}
+
+/// a great circle arc between two lon/lat points
+struct Arc<T: Float> {
+    start: [T; 3],
+    end: [T; 3],
+    angle: T,
+    /// the longitudes of the end points, to unwrap interpolated longitudes next to them
+    lons: [T; 2],
+}
+
+impl<T: Float> Arc<T> {
+    fn new(start: [T; 2], end: [T; 2]) -> Self {
+        let (a, b) = (to_unit(start), to_unit(end));
+        let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let angle = dot.max(-T::one()).min(T::one()).acos();
+        Self {
+            start: a,
+            end: b,
+            angle,
+            lons: [start[0], end[0]],
+        }
+    }
+
+    /// the lon/lat point a fraction `t` along the arc
+    fn at(&self, t: T) -> [T; 2] {
+        let (wa, wb) = if self.angle > T::epsilon() {
+            let sin = self.angle.sin();
+            (
+                ((T::one() - t) * self.angle).sin() / sin,
+                (t * self.angle).sin() / sin,
+            )
+        } else {
+            (T::one() - t, t)
+        };
+        let p = [0, 1, 2].map(|k| self.start[k] * wa + self.end[k] * wb);
+        let lat = p[2].atan2((p[0] * p[0] + p[1] * p[1]).sqrt()).to_degrees();
+        let mut lon = p[1].atan2(p[0]).to_degrees();
+        // keep the longitude on the same unwrapped turn as the linear interpolation
+        let linear = self.lons[0] + (self.lons[1] - self.lons[0]) * t;
+        let full_turn = degrees::<T>(360.0);
+        while lon - linear > degrees(180.0) {
+            lon = lon - full_turn;
+        }
+        while linear - lon > degrees(180.0) {
+            lon = lon + full_turn;
+        }
+        [lon, lat]
+    }
+}
+
+/// the unit vector of a lon/lat point in degrees
+fn to_unit<T: Float>([lon, lat]: [T; 2]) -> [T; 3] {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// the smallest multiple of `modulo` strictly greater than `x`
+fn next_multiple<T: Float>(x: T, modulo: T) -> T {
+    ((x / modulo).floor() + T::one()) * modulo
+}
+
+fn degrees<T: Float>(d: f64) -> T {
+    T::from(d).unwrap_or_else(T::zero)
+}
+
+fn min<T: Float>(v: [T; 3]) -> T {
+    v[0].min(v[1]).min(v[2])
+}
+
+fn max<T: Float>(v: [T; 3]) -> T {
+    v[0].max(v[1]).max(v[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn splits_along_the_equator() {
+        let mut vertices = vec![0.0, -10.0, 10.0, -10.0, 5.0, 40.0];
+        let mut indices: Vec<usize> = vec![0, 1, 2];
+        let added = tesselate_spherical(&mut vertices, &mut indices, 90.0, 50.0, 2);
+        assert_eq!(added, 2);
+        assert_eq!(indices.len(), 9);
+
+        for triangle in indices.chunks(3) {
+            let lats = [0, 1, 2].map(|k| vertices[triangle[k] * 2 + 1]);
+            // no triangle straddles the equator
+            assert!(lats.iter().all(|&lat| lat <= 0.0) || lats.iter().all(|&lat| lat >= 0.0));
+        }
+        // the new vertices lie on the great circle through their edge's end points
+        let unit = |v: usize| to_unit([vertices[v * 2], vertices[v * 2 + 1]]);
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let cross = |a: [f64; 3], b: [f64; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        for (new, [a, b]) in [(3, [0, 2]), (4, [1, 2])] {
+            assert_eq!(vertices[new * 2 + 1], 0.0);
+            assert!(dot(cross(unit(a), unit(b)), unit(new)).abs() < 1e-12);
+        }
+        // which bulges away from the straight line in lon/lat space
+        assert!((vertices[3 * 2] - 1.0).abs() > 1e-3);
+    }
+
+    #[test]
+    fn cuts_polygons_at_the_antimeridian() {
+        let polygon = vec![vec![
+            vec![170.0, -10.0],
+            vec![-170.0, -10.0],
+            vec![-170.0, 10.0],
+            vec![170.0, 10.0],
+        ]];
+        let pieces = split_antimeridian(&polygon);
+        assert_eq!(pieces.len(), 2);
+        for piece in &pieces {
+            let lons: Vec<f64> = piece[0].iter().map(|p| p[0]).collect();
+            let lo = lons.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = lons.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            assert_eq!(hi - lo, 10.0);
+            assert!(lo >= -180.0 && hi <= 180.0);
+
+            let (_, indices) = crate::earclip::<f64, usize>(piece, None, None);
+            assert_eq!(indices.len(), 6);
+        }
+
+        // polygons away from the antimeridian are left alone
+        let polygon = vec![vec![vec![0.0, 0.0], vec![10.0, 0.0], vec![10.0, 10.0]]];
+        assert_eq!(split_antimeridian(&polygon), vec![polygon]);
+    }
+
+    #[test]
+    fn splits_at_the_antimeridian() {
+        let mut vertices = vec![170.0, -5.0, -170.0, -5.0, 175.0, 5.0];
+        let mut indices: Vec<u32> = vec![0, 1, 2];
+        tesselate_spherical(&mut vertices, &mut indices, 90.0, 0.0, 2);
+        assert!(indices.len() > 3);
+
+        let mut sides = [false; 2];
+        for triangle in indices.chunks(3) {
+            let lons = [0, 1, 2].map(|k| vertices[triangle[k] as usize * 2]);
+            assert!(lons.iter().all(|lon| (-180.0..=180.0).contains(lon)));
+            assert!(max(lons) - min(lons) < 180.0);
+            sides[usize::from(lons.iter().any(|&lon| lon < 0.0))] = true;
+        }
+        assert_eq!(sides, [true, true]);
+        assert!(vertices.chunks(2).any(|p| p[0] == 180.0));
+        assert!(vertices.chunks(2).any(|p| p[0] == -180.0));
+    }
+}
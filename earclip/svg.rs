@@ -0,0 +1,55 @@
+use alloc::string::String;
+use core::fmt::{Display, Write};
+
+use crate::Index;
+
+/// Build an SVG `<path>` `d` attribute drawing every triangle of a mesh.
+///
+/// Each triangle becomes its own `M x y L x y L x y Z` subpath using the first two coordinates
+/// of each vertex in input units; the caller is responsible for setting a fitting `viewBox`.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices
+/// - `indices`: the triangle indices
+/// - `dim`: the number of coordinates per vertex
+pub fn to_svg_path<T: Copy + Display, N: Index>(
+    vertices: &[T],
+    indices: &[N],
+    dim: usize,
+) -> String {
+    let mut path = String::new();
+    for &[a, b, c] in indices.as_chunks::<3>().0 {
+        let [a, b, c] = [a, b, c].map(|i| i.into_usize() * dim);
+        if !path.is_empty() {
+            path.push(' ');
+        }
+        // writing into a String cannot fail
+        let _ = write!(
+            path,
+            "M{} {} L{} {} L{} {} Z",
+            vertices[a],
+            vertices[a + 1],
+            vertices[b],
+            vertices[b + 1],
+            vertices[c],
+            vertices[c + 1]
+        );
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_triangles() {
+        let vertices = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let path = to_svg_path(&vertices, &[0_usize, 1, 2, 0, 2, 3], 2);
+        assert_eq!(path.matches('M').count(), 2);
+        assert_eq!(path.matches('L').count(), 4);
+        assert_eq!(path.matches('Z').count(), 2);
+        assert_eq!(path, "M0 0 L1 0 L1 1 Z M0 0 L1 1 L0 1 Z");
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+
+use crate::earclip_flat;
+
+/// The output of [`earclip_packed`], laid out so JavaScript can view it as typed arrays.
+///
+/// The pointers stay valid until the result is dropped or moved, e.g. read them into a
+/// `Float64Array` and a `Uint32Array` over the module's memory before freeing the result.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackedResult {
+    /// The flattened 2D vertices, `[x0, y0, x1, y1, ...]`
+    pub vertices: Vec<f64>,
+    /// The triangle indices, three per triangle
+    pub indices: Vec<u32>,
+}
+
+impl PackedResult {
+    /// A pointer to the first vertex coordinate
+    pub fn vertices_ptr(&self) -> *const f64 {
+        self.vertices.as_ptr()
+    }
+
+    /// The number of vertex coordinates (twice the number of vertices)
+    pub fn vertices_len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// A pointer to the first triangle index
+    pub fn indices_ptr(&self) -> *const u32 {
+        self.indices.as_ptr()
+    }
+
+    /// The number of triangle indices (three times the number of triangles)
+    pub fn indices_len(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// [`crate::earclip`] over packed buffers, to pass typed arrays across a WebAssembly boundary
+/// without marshaling nested arrays.
+///
+/// ## Parameters
+/// - `coords`: the 2D vertices of all rings flattened, `[x0, y0, x1, y1, ...]`
+/// - `ring_offsets`: the vertex index each ring starts at; the first ring is the outer ring,
+///   the rest are holes, and the leading `0` of the outer ring may be left out
+/// - `modulo`: tesselate the triangles along every multiple of `modulo`, if it is positive
+///
+/// ## Returns
+/// The vertices and triangles, or an empty result if `coords` has an odd length or the ring
+/// offsets are not strictly ascending vertex indices within `coords`, as a panic would trap the
+/// WebAssembly instance
+pub fn earclip_packed(coords: &[f64], ring_offsets: &[u32], modulo: f64) -> PackedResult {
+    let hole_indices: Vec<u32> = ring_offsets
+        .iter()
+        .copied()
+        .skip_while(|&offset| offset == 0)
+        .collect();
+    let vertex_count = coords.len() / 2;
+    if !coords.len().is_multiple_of(2)
+        || hole_indices.windows(2).any(|pair| pair[0] >= pair[1])
+        || hole_indices
+            .last()
+            .is_some_and(|&offset| offset as usize >= vertex_count)
+    {
+        return PackedResult::default();
+    }
+    let modulo = (modulo > 0.0).then_some(modulo);
+    let (vertices, indices) = earclip_flat(coords, &hole_indices, 2, modulo, None);
+    PackedResult { vertices, indices }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earclip;
+    use alloc::vec;
+
+    #[test]
+    fn packed_round_trip() {
+        let coords = [
+            0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, // outer
+            2.0, 2.0, 2.0, 8.0, 8.0, 8.0, 8.0, 2.0, // hole
+        ];
+        let result = earclip_packed(&coords, &[0, 4], 0.0);
+        assert_eq!(result.vertices, coords);
+        assert_eq!(result.indices_len(), 8 * 3);
+        // the leading offset is optional
+        assert_eq!(earclip_packed(&coords, &[4], 0.0), result);
+
+        let polygon = vec![
+            vec![
+                vec![0.0, 0.0],
+                vec![10.0, 0.0],
+                vec![10.0, 10.0],
+                vec![0.0, 10.0],
+            ],
+            vec![
+                vec![2.0, 2.0],
+                vec![2.0, 8.0],
+                vec![8.0, 8.0],
+                vec![8.0, 2.0],
+            ],
+        ];
+        let (vertices, indices) = earclip::<f64, u32>(&polygon, Some(5.0), None);
+        let result = earclip_packed(&coords, &[0, 4], 5.0);
+        assert_eq!((&result.vertices, &result.indices), (&vertices, &indices));
+
+        // read back through the raw parts, as JavaScript would
+        let raw_indices =
+            unsafe { core::slice::from_raw_parts(result.indices_ptr(), result.indices_len()) };
+        assert_eq!(raw_indices, indices.as_slice());
+        let raw_vertices =
+            unsafe { core::slice::from_raw_parts(result.vertices_ptr(), result.vertices_len()) };
+        assert_eq!(raw_vertices, vertices.as_slice());
+    }
+
+    #[test]
+    fn packed_malformed_input() {
+        let coords = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0];
+        assert_eq!(earclip_packed(&coords, &[10], 0.0), PackedResult::default());
+        assert_eq!(earclip_packed(&coords, &[3], 0.0), PackedResult::default());
+        assert_eq!(
+            earclip_packed(&coords[..5], &[], 0.0),
+            PackedResult::default()
+        );
+        assert_eq!(
+            earclip_packed(&coords, &[0, 2, 1], 0.0),
+            PackedResult::default()
+        );
+        assert_eq!(earclip_packed(&coords, &[0], 0.0).indices_len(), 3);
+    }
+}
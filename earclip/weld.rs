@@ -0,0 +1,137 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use num_traits::float::Float;
+
+use crate::Index;
+
+/// Snap every coordinate to the nearest multiple of `grid`, e.g. to make the triangulation of
+/// inputs that differ only by floating point noise identical.
+///
+/// Snap before triangulating, as the triangles depend on the exact coordinates. Nearby
+/// vertices can snap onto the same point: consecutive duplicates within a ring are skipped by
+/// the triangulation, and [`dedup_vertices`] with a zero `epsilon` merges the remaining ones in
+/// the resulting mesh. A grid that is not positive and finite leaves `data` as is.
+///
+/// ## Parameters
+/// - `data`: the flattened vertices to snap in place
+/// - `dim`: the number of coordinates per vertex
+/// - `grid`: the grid spacing, the same on every axis
+pub fn snap_to_grid<T: Float>(data: &mut [T], dim: usize, grid: T) {
+    if dim == 0 || !(grid > T::zero() && grid.is_finite()) {
+        return;
+    }
+    for v in data.iter_mut() {
+        *v = (*v / grid).round() * grid;
+    }
+}
+
+/// Weld vertices that lie within `epsilon` of each other on every axis.
+///
+/// Each vertex is merged into the first earlier vertex close enough to it, the indices are
+/// rewritten to point at the kept vertices and the coordinates of the merged ones are removed.
+/// The kept vertices stay in their original order. Vertices are bucketed in a grid of cells
+/// `epsilon` wide over their first two coordinates, so only nearby vertices are compared.
+///
+/// Welding with a non-zero `epsilon` can collapse small triangles to zero area, see
+/// [`crate::filter_slivers`] to drop them.
+///
+/// ## Parameters
+/// - `vertices`: the flattened vertices to weld
+/// - `indices`: the triangle indices to rewrite
+/// - `dim`: the number of coordinates per vertex
+/// - `epsilon`: the largest distance per axis between welded vertices, zero for exact duplicates
+///
+/// ## Returns
+/// The number of vertices removed
+pub fn dedup_vertices<T: Float, N: Index>(
+    vertices: &mut Vec<T>,
+    indices: &mut [N],
+    dim: usize,
+    epsilon: T,
+) -> usize {
+    if dim == 0 {
+        return 0;
+    }
+    let count = vertices.len() / dim;
+    let size = if epsilon > T::zero() {
+        epsilon
+    } else {
+        T::one()
+    };
+    let cell = |v: T| {
+        let c = (v / size).floor();
+        c.to_i64()
+            .unwrap_or(if c > T::zero() { i64::MAX } else { i64::MIN })
+    };
+    // exact duplicates always share a cell, close ones may be in a neighbouring cell
+    let reach = if epsilon > T::zero() { 1 } else { 0 };
+
+    let mut grid: BTreeMap<[i64; 2], Vec<usize>> = BTreeMap::new();
+    let mut remap = Vec::with_capacity(count);
+    let mut kept = 0;
+    for i in 0..count {
+        let start = i * dim;
+        let key = [
+            cell(vertices[start]),
+            if dim > 1 {
+                cell(vertices[start + 1])
+            } else {
+                0
+            },
+        ];
+        let close = |j: usize| {
+            (0..dim)
+                .all(|axis| (vertices[j * dim + axis] - vertices[start + axis]).abs() <= epsilon)
+        };
+        let found = (-reach..=reach)
+            .flat_map(|dx| (-reach..=reach).map(move |dy| (dx, dy)))
+            .filter_map(|(dx, dy)| {
+                grid.get(&[key[0].saturating_add(dx), key[1].saturating_add(dy)])
+            })
+            .flat_map(|bucket| bucket.iter().copied())
+            .filter(|&j| close(j))
+            .min();
+        match found {
+            Some(j) => remap.push(j),
+            None => {
+                vertices.copy_within(start..start + dim, kept * dim);
+                grid.entry(key).or_default().push(kept);
+                remap.push(kept);
+                kept += 1;
+            }
+        }
+    }
+    vertices.truncate(kept * dim);
+    for index in indices.iter_mut() {
+        *index = N::from_usize(remap[index.into_usize()]);
+    }
+
+    count - kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn welds_exact_duplicates() {
+        // two triangles sharing an edge, each with its own copy of the shared vertices
+        let mut vertices = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let mut indices = vec![0_u32, 1, 2, 3, 4, 5];
+        assert_eq!(dedup_vertices(&mut vertices, &mut indices, 2, 0.0), 2);
+        assert_eq!(vertices, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        assert_eq!(indices, vec![0, 1, 2, 1, 3, 2]);
+    }
+
+    #[test]
+    fn welds_within_epsilon() {
+        // the second and third vertex straddle a cell boundary
+        let mut vertices = vec![5.0, 5.0, 0.999, 2.0, 1.0005, 2.0, 1.1, 2.0];
+        let mut indices = vec![0_usize, 1, 2, 3, 2, 0];
+        assert_eq!(dedup_vertices(&mut vertices, &mut indices, 2, 0.01), 1);
+        assert_eq!(vertices, vec![5.0, 5.0, 0.999, 2.0, 1.1, 2.0]);
+        assert_eq!(indices, vec![0, 1, 1, 2, 1, 0]);
+    }
+}
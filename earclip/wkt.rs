@@ -0,0 +1,309 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Why a WKT string could not be parsed, see [`parse_wkt_polygon`]
+///
+/// Positions are byte offsets into the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WktError {
+    /// The geometry is not a `POLYGON` (or `MULTIPOLYGON` where allowed)
+    UnsupportedType,
+    /// Something other than what the grammar allows here
+    UnexpectedToken(usize),
+    /// The input ends in the middle of the geometry
+    UnexpectedEnd,
+    /// A coordinate that is not a finite number, e.g. `NaN`, `inf` or one too large for `f64`
+    InvalidNumber(usize),
+}
+
+impl fmt::Display for WktError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WktError::UnsupportedType => write!(f, "only POLYGON and MULTIPOLYGON are supported"),
+            WktError::UnexpectedToken(at) => write!(f, "unexpected token at byte {at}"),
+            WktError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            WktError::InvalidNumber(at) => write!(f, "invalid number at byte {at}"),
+        }
+    }
+}
+
+impl core::error::Error for WktError {}
+
+/// Parse a WKT `POLYGON`, e.g. `POLYGON((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 1 2, 2 2, 1 1))`, into
+/// the nested form [`crate::earclip`] takes: the exterior ring followed by the interior rings.
+///
+/// Keywords are case-insensitive. `POLYGON Z` points keep their z; the measure of `M` and `ZM`
+/// points is dropped. `POLYGON EMPTY` gives no rings.
+///
+/// ## Errors
+/// [`WktError::UnsupportedType`] for any other geometry, see [`parse_wkt_multipolygon`] for
+/// `MULTIPOLYGON`, or the first syntax error
+pub fn parse_wkt_polygon(s: &str) -> Result<Vec<Vec<Vec<f64>>>, WktError> {
+    let mut parser = Parser::new(s);
+    if !parser.keyword()?.eq_ignore_ascii_case("POLYGON") {
+        return Err(WktError::UnsupportedType);
+    }
+    let dims = parser.dims()?;
+    let polygon = parser.polygon(dims)?;
+    parser.end()?;
+    Ok(polygon)
+}
+
+/// Parse a WKT `MULTIPOLYGON`, or a `POLYGON` as a multipolygon of one, into one polygon per
+/// element, each in the form [`parse_wkt_polygon`] returns. `EMPTY` polygons are left out.
+///
+/// ## Errors
+/// [`WktError::UnsupportedType`] for any other geometry, or the first syntax error
+pub fn parse_wkt_multipolygon(s: &str) -> Result<Vec<Vec<Vec<Vec<f64>>>>, WktError> {
+    let mut parser = Parser::new(s);
+    let keyword = parser.keyword()?;
+    let dims = parser.dims()?;
+    let polygons = if keyword.eq_ignore_ascii_case("POLYGON") {
+        let polygon = parser.polygon(dims)?;
+        Vec::from_iter((!polygon.is_empty()).then_some(polygon))
+    } else if keyword.eq_ignore_ascii_case("MULTIPOLYGON") {
+        let mut polygons = parser.list(|parser| parser.polygon(dims))?;
+        polygons.retain(|polygon| !polygon.is_empty());
+        polygons
+    } else {
+        return Err(WktError::UnsupportedType);
+    };
+    parser.end()?;
+    Ok(polygons)
+}
+
+/// which coordinates the points of a geometry carry
+#[derive(Clone, Copy)]
+struct Dims {
+    z: bool,
+    m: bool,
+}
+
+/// a cursor over the WKT text
+struct Parser<'a> {
+    s: &'a str,
+    at: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { s, at: 0 }
+    }
+
+    /// the next non-whitespace byte, without consuming it
+    fn peek(&mut self) -> Option<u8> {
+        let rest = &self.s[self.at..];
+        self.at += rest.len() - rest.trim_start().len();
+        self.s.as_bytes().get(self.at).copied()
+    }
+
+    /// consume `byte` or fail
+    fn expect(&mut self, byte: u8) -> Result<(), WktError> {
+        match self.peek() {
+            Some(b) if b == byte => {
+                self.at += 1;
+                Ok(())
+            }
+            Some(_) => Err(WktError::UnexpectedToken(self.at)),
+            None => Err(WktError::UnexpectedEnd),
+        }
+    }
+
+    /// consume a run of letters
+    fn keyword(&mut self) -> Result<&'a str, WktError> {
+        self.peek().ok_or(WktError::UnexpectedEnd)?;
+        let rest = &self.s[self.at..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(WktError::UnexpectedToken(self.at));
+        }
+        self.at += len;
+        Ok(&rest[..len])
+    }
+
+    /// the optional `Z`, `M` or `ZM` after the geometry type
+    fn dims(&mut self) -> Result<Dims, WktError> {
+        let mut dims = Dims { z: false, m: false };
+        if self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            let start = self.at;
+            let keyword = self.keyword()?;
+            if keyword.eq_ignore_ascii_case("EMPTY") {
+                self.at = start;
+            } else if keyword.eq_ignore_ascii_case("Z") {
+                dims.z = true;
+            } else if keyword.eq_ignore_ascii_case("M") {
+                dims.m = true;
+            } else if keyword.eq_ignore_ascii_case("ZM") {
+                dims = Dims { z: true, m: true };
+            } else {
+                return Err(WktError::UnexpectedToken(start));
+            }
+        }
+        Ok(dims)
+    }
+
+    /// `EMPTY` or a parenthesized, comma separated list of items
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, WktError>,
+    ) -> Result<Vec<T>, WktError> {
+        if self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            let start = self.at;
+            return if self.keyword()?.eq_ignore_ascii_case("EMPTY") {
+                Ok(Vec::new())
+            } else {
+                Err(WktError::UnexpectedToken(start))
+            };
+        }
+        self.expect(b'(')?;
+        let mut items = Vec::new();
+        loop {
+            items.push(item(self)?);
+            match self.peek() {
+                Some(b',') => self.at += 1,
+                Some(b')') => {
+                    self.at += 1;
+                    return Ok(items);
+                }
+                Some(_) => return Err(WktError::UnexpectedToken(self.at)),
+                None => return Err(WktError::UnexpectedEnd),
+            }
+        }
+    }
+
+    /// the rings of a polygon
+    fn polygon(&mut self, dims: Dims) -> Result<Vec<Vec<Vec<f64>>>, WktError> {
+        self.list(|parser| parser.list(|parser| parser.point(dims)))
+    }
+
+    /// a point's coordinates separated by whitespace, without its measure
+    fn point(&mut self, dims: Dims) -> Result<Vec<f64>, WktError> {
+        let count = 2 + usize::from(dims.z) + usize::from(dims.m);
+        let mut point = Vec::with_capacity(count);
+        for _ in 0..count {
+            point.push(self.number()?);
+        }
+        point.truncate(2 + usize::from(dims.z));
+        Ok(point)
+    }
+
+    fn number(&mut self) -> Result<f64, WktError> {
+        self.peek().ok_or(WktError::UnexpectedEnd)?;
+        let rest = &self.s[self.at..];
+        let len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == ',' || c == '(' || c == ')')
+            .unwrap_or(rest.len());
+        let number = rest[..len]
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .ok_or(WktError::InvalidNumber(self.at))?;
+        self.at += len;
+        Ok(number)
+    }
+
+    /// only whitespace may follow the geometry
+    fn end(&mut self) -> Result<(), WktError> {
+        match self.peek() {
+            Some(_) => Err(WktError::UnexpectedToken(self.at)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{earclip, triangulated_area};
+    use alloc::vec;
+
+    #[test]
+    fn polygon_with_hole() {
+        let polygon =
+            parse_wkt_polygon("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0),\n  (1 1, 1 2, 2.5 2, 1 1))")
+                .unwrap();
+        assert_eq!(polygon.len(), 2);
+        assert_eq!(polygon[0].len(), 5);
+        assert_eq!(polygon[1][2], vec![2.5, 2.0]);
+        let (vertices, indices) = earclip::<f64, usize>(&polygon, None, None);
+        assert_eq!(triangulated_area(&vertices, &indices, 2), 16.0 - 0.75);
+
+        // keywords in any case, z kept and measures dropped
+        let polygon = parse_wkt_polygon("polygon zm((0 0 1 9,1 0 2 9,0 1 3 9,0 0 1 9))").unwrap();
+        assert_eq!(polygon[0][1], vec![1.0, 0.0, 2.0]);
+        assert_eq!(parse_wkt_polygon("POLYGON EMPTY"), Ok(vec![]));
+    }
+
+    #[test]
+    fn multipolygon() {
+        let polygons = parse_wkt_multipolygon(
+            "MULTIPOLYGON (((0 0, 2 0, 2 2, 0 0)), EMPTY, ((5 5, 9 5, 9 9, 5 9, 5 5), (6 6, 6 7, 7 7, 6 6)))",
+        )
+        .unwrap();
+        assert_eq!(polygons.len(), 2);
+        assert_eq!(polygons[0].len(), 1);
+        assert_eq!(polygons[1].len(), 2);
+        let area: f64 = polygons
+            .iter()
+            .map(|polygon| {
+                let (vertices, indices) = earclip::<f64, usize>(polygon, None, None);
+                triangulated_area(&vertices, &indices, 2)
+            })
+            .sum();
+        assert_eq!(area, 2.0 + 16.0 - 0.5);
+
+        // a polygon is a multipolygon of one
+        let polygon = "POLYGON ((0 0, 1 0, 0 1, 0 0))";
+        assert_eq!(
+            parse_wkt_multipolygon(polygon),
+            Ok(vec![parse_wkt_polygon(polygon).unwrap()])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_wkt_polygon("POINT (1 2)"),
+            Err(WktError::UnsupportedType)
+        );
+        assert_eq!(
+            parse_wkt_polygon("MULTIPOLYGON EMPTY"),
+            Err(WktError::UnsupportedType)
+        );
+        assert_eq!(
+            parse_wkt_polygon("POLYGON ((0 0, 1 x, 0 1))"),
+            Err(WktError::InvalidNumber(17))
+        );
+        assert_eq!(
+            parse_wkt_polygon("POLYGON ((0 0, 1 0, 0 1)"),
+            Err(WktError::UnexpectedEnd)
+        );
+        assert_eq!(
+            parse_wkt_polygon("POLYGON ((0 0, 1 0, 0 1)) x"),
+            Err(WktError::UnexpectedToken(26))
+        );
+        assert_eq!(parse_wkt_polygon(""), Err(WktError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn non_finite_numbers() {
+        for number in [
+            "NaN",
+            "nan",
+            "inf",
+            "-inf",
+            "Infinity",
+            "+infinity",
+            "1e400",
+        ] {
+            let wkt = alloc::format!("POLYGON ((0 0, 1 {number}, 0 1))");
+            assert_eq!(parse_wkt_polygon(&wkt), Err(WktError::InvalidNumber(17)));
+        }
+        assert_eq!(
+            parse_wkt_multipolygon("MULTIPOLYGON (((0 0, 1 0, NaN 1)))"),
+            Err(WktError::InvalidNumber(26))
+        );
+    }
+}
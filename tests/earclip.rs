@@ -0,0 +1,1276 @@
+use earclip::{
+    all_same_winding, append_mesh, centroid, centroid_3d, classify_rings, convert_2d, convert_3d,
+    earclip, earclip_arrays, earclip_arrays_3d, earclip_flat, earclip_mesh,
+    earclip_mesh_with_rings, earclip_with_holes, earclip_with_steiner, earcut_iter,
+    earcut_multipolygon, earcut_points2d, earcut_points3d, extrude, flatten, flatten_points2d,
+    flatten_points3d, flatten_with_rings, is_clockwise, is_counter_clockwise, offset_polygon,
+    point_in_polygon, subdivide, tesselate_chunked, tesselate_max_edge, tesselate_tracked,
+    tesselate_with_origin, triangulated_area, try_earclip, try_earcut, try_tesselate, Earclip,
+    EarcutError, IncrementalPolygon, JoinStyle, Point2D, PolygonRings, TriangleMesh, VertexOrigin,
+    Winding,
+};
+use std::f64::consts::{PI, TAU};
+
+/// the points of a regular `n`-gon around the origin
+fn regular_polygon(n: usize, radius: f64) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| {
+            let angle = i as f64 / n as f64 * TAU;
+            vec![angle.cos() * radius, angle.sin() * radius]
+        })
+        .collect()
+}
+
+fn geometry() -> Vec<Vec<Vec<f64>>> {
+    vec![
+        vec![
+            vec![3506.0, -2048.0],
+            vec![7464.0, 402.0],
+            vec![-2048.0, 2685.0],
+            vec![-2048.0, -2048.0],
+            vec![3506.0, -2048.0],
+        ],
+        vec![
+            vec![-2048.0, -37.0],
+            vec![1235.0, 747.0],
+            vec![338.0, -1464.0],
+            vec![-116.0, -1188.0],
+            vec![-2048.0, -381.0],
+            vec![-2048.0, -37.0],
+        ],
+        vec![
+            vec![-1491.0, -1981.0],
+            vec![-1300.0, -1800.0],
+            vec![-1155.0, -1981.0],
+            vec![-1491.0, -1981.0],
+        ],
+    ]
+}
+
+const FLAT_VERTICES: [f64; 30] = [
+    3506.0, -2048.0, 7464.0, 402.0, -2048.0, 2685.0, -2048.0, -2048.0, 3506.0, -2048.0, -2048.0,
+    -37.0, 1235.0, 747.0, 338.0, -1464.0, -116.0, -1188.0, -2048.0, -381.0, -2048.0, -37.0,
+    -1491.0, -1981.0, -1300.0, -1800.0, -1155.0, -1981.0, -1491.0, -1981.0,
+];
+
+const FLAT_INDICES: [usize; 36] = [
+    2, 5, 6, 7, 8, 9, 9, 3, 11, 13, 11, 3, 1, 2, 6, 9, 11, 12, 13, 3, 0, 0, 1, 6, 7, 9, 12, 12, 13,
+    0, 0, 6, 7, 7, 12, 0,
+];
+
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl Point2D<f64> for Point {
+    fn x(&self) -> f64 {
+        self.x
+    }
+    fn y(&self) -> f64 {
+        self.y
+    }
+}
+
+#[test]
+fn empty() {
+    let (vertices, indices) = earclip::<f64, usize>(&[], None, None);
+    assert!(vertices.is_empty());
+    assert!(indices.is_empty());
+}
+
+#[test]
+fn simple() {
+    let polygon = vec![vec![
+        vec![0.0, 0.0, 0.0],
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 1.0, 0.0],
+    ]];
+    let (vertices, indices) = earclip::<f64, usize>(&polygon, None, None);
+    assert_eq!(vertices, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(indices, vec![1, 2, 0]);
+}
+
+#[test]
+fn flat_points() {
+    let (vertices, indices) = earclip::<f64, usize>(&geometry(), None, None);
+    assert_eq!(vertices, FLAT_VERTICES);
+    assert_eq!(indices, FLAT_INDICES);
+}
+
+#[test]
+fn object_points() {
+    let points: Vec<Vec<Point>> = geometry()
+        .iter()
+        .map(|ring| ring.iter().map(|p| Point { x: p[0], y: p[1] }).collect())
+        .collect();
+    let (vertices, indices) = earclip::<f64, usize>(&convert_2d(&points), None, None);
+    assert_eq!(vertices, FLAT_VERTICES);
+    assert_eq!(indices, FLAT_INDICES);
+}
+
+#[test]
+fn array_and_tuple_points() {
+    let arrays: Vec<Vec<[f64; 2]>> = geometry()
+        .iter()
+        .map(|ring| ring.iter().map(|p| [p[0], p[1]]).collect())
+        .collect();
+    let tuples: Vec<Vec<(f64, f64)>> = geometry()
+        .iter()
+        .map(|ring| ring.iter().map(|p| (p[0], p[1])).collect())
+        .collect();
+    assert_eq!(convert_2d(&arrays), geometry());
+    assert_eq!(convert_2d(&tuples), geometry());
+
+    let arrays = vec![vec![[0.0, 0.0, 1.0], [1.0, 0.0, 2.0], [0.0, 1.0, 3.0]]];
+    let tuples = vec![vec![(0.0, 0.0, 1.0), (1.0, 0.0, 2.0), (0.0, 1.0, 3.0)]];
+    let expected = vec![vec![
+        vec![0.0, 0.0, 1.0],
+        vec![1.0, 0.0, 2.0],
+        vec![0.0, 1.0, 3.0],
+    ]];
+    assert_eq!(convert_3d(&arrays), expected);
+    assert_eq!(convert_3d(&tuples), expected);
+}
+
+#[test]
+fn separate_holes() {
+    let polygon = geometry();
+    let (outer, holes) = polygon.split_first().unwrap();
+    let positional = earclip::<f64, usize>(&polygon, None, None);
+    assert_eq!(
+        earclip_with_holes::<f64, usize>(outer, holes, None, None),
+        positional
+    );
+    assert_eq!(
+        earclip_with_holes::<f64, u32>(outer, holes, Some(2048.0), Some(10)),
+        earclip::<f64, u32>(&polygon, Some(2048.0), Some(10))
+    );
+    assert_eq!(
+        earclip_with_holes::<f64, usize>(outer, &[], None, None),
+        earclip(&polygon[..1], None, None)
+    );
+}
+
+#[test]
+fn u8_indices() {
+    let (vertices, indices) = earclip::<f32, u8>(
+        &[vec![
+            vec![0.0, 0.0],
+            vec![4.0, 0.0],
+            vec![4.0, 4.0],
+            vec![0.0, 4.0],
+        ]],
+        None,
+        None,
+    );
+    assert_eq!(vertices.len(), 8);
+    assert_eq!(indices, vec![2, 3, 0, 0, 1, 2]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "index type is too small for 300 vertices")]
+fn u8_indices_overflow() {
+    earclip::<f64, u8>(&[regular_polygon(300, 1.0)], None, None);
+}
+
+#[test]
+fn u16_indices_overflow() {
+    let ring = regular_polygon(70_000, 1.0);
+    let flat = ring.concat();
+    assert_eq!(
+        try_earcut::<f64, u16>(&flat, &[], 2),
+        Err(EarcutError::IndexOverflow)
+    );
+    let polygon = vec![ring];
+    assert_eq!(
+        try_earclip::<f64, u16>(&polygon, None, None),
+        Err(EarcutError::IndexOverflow)
+    );
+    // the offset counts too
+    let small = [vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0]]];
+    assert_eq!(
+        try_earclip::<f64, u16>(&small, None, Some(65_534)),
+        Err(EarcutError::IndexOverflow)
+    );
+    assert!(try_earclip::<f64, u16>(&small, None, Some(65_533)).is_ok());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "index type is too small for 70000 vertices")]
+fn u16_earcut_overflow() {
+    let flat = regular_polygon(70_000, 1.0).concat();
+    earclip::earcut::<f64, u16>(&flat, &[], 2);
+}
+
+#[test]
+fn offset() {
+    let (_, indices) = earclip::<f64, usize>(&geometry(), None, Some(10));
+    assert_eq!(indices, FLAT_INDICES.map(|i| i + 10));
+}
+
+#[test]
+fn tesselate() {
+    let (vertices, indices) = earclip::<f64, usize>(&geometry(), Some(2048.0), None);
+    assert_eq!(
+        indices,
+        vec![
+            15, 51, 50, 18, 8, 9, 9, 3, 11, 13, 11, 3, 24, 53, 6, 9, 11, 12, 29, 13, 3, 56, 34, 36,
+            12, 37, 9, 13, 42, 12, 58, 7, 44, 48, 7, 45, 6, 15, 16, 60, 16, 15, 7, 17, 18, 18, 17,
+            8, 1, 19, 20, 20, 19, 21, 21, 19, 22, 21, 22, 23, 23, 22, 24, 23, 24, 6, 62, 26, 2, 64,
+            25, 6, 0, 27, 28, 28, 27, 29, 29, 27, 30, 29, 30, 13, 66, 1, 31, 32, 67, 33, 70, 31,
+            34, 33, 71, 0, 74, 36, 6, 76, 35, 0, 7, 37, 38, 38, 37, 12, 0, 39, 40, 40, 39, 41, 41,
+            39, 42, 41, 42, 13, 0, 43, 44, 43, 77, 44, 12, 45, 46, 46, 45, 7, 0, 47, 48, 48, 47, 7,
+            5, 49, 50, 50, 49, 15, 2, 51, 52, 52, 51, 15, 26, 53, 54, 54, 53, 24, 0, 55, 56, 55,
+            79, 56, 6, 57, 58, 58, 57, 7, 5, 59, 60, 60, 59, 16, 25, 61, 62, 62, 61, 26, 26, 63,
+            64, 64, 63, 25, 32, 65, 66, 66, 65, 1, 31, 67, 68, 68, 67, 32, 33, 69, 70, 70, 69, 31,
+            34, 71, 72, 71, 81, 72, 35, 73, 74, 74, 73, 36, 36, 75, 76, 76, 75, 35, 6, 77, 78, 78,
+            77, 43, 34, 79, 80, 80, 79, 55, 33, 81, 82, 82, 81, 71
+        ]
+    );
+    assert_eq!(
+        vertices,
+        vec![
+            3506.0,
+            -2048.0,
+            7464.0,
+            402.0,
+            -2048.0,
+            2685.0,
+            -2048.0,
+            -2048.0,
+            3506.0,
+            -2048.0,
+            -2048.0,
+            -37.0,
+            1235.0,
+            747.0,
+            338.0,
+            -1464.0,
+            -116.0,
+            -1188.0,
+            -2048.0,
+            -381.0,
+            -2048.0,
+            -37.0,
+            -1491.0,
+            -1981.0,
+            -1300.0,
+            -1800.0,
+            -1155.0,
+            -1981.0,
+            -1491.0,
+            -1981.0,
+            0.0,
+            1476.0374657325617,
+            0.0,
+            452.07462686567163,
+            0.0,
+            -1258.5198237885463,
+            0.0,
+            -1310.58256496228,
+            6144.0,
+            718.8166526492851,
+            6144.0,
+            475.1096484186868,
+            4096.0,
+            588.5403756622251,
+            4096.0,
+            1210.362489486964,
+            2048.0,
+            701.9711029057634,
+            2048.0,
+            1701.9083263246425,
+            0.0,
+            1476.0374657325617,
+            0.0,
+            2193.4541631623215,
+            2048.0,
+            -2027.041836515769,
+            2048.0,
+            -2048.0,
+            0.0,
+            -2048.0,
+            0.0,
+            -1997.6026603733105,
+            6144.0,
+            475.1096484186868,
+            6144.0,
+            -415.0793329964629,
+            4096.0,
+            -1682.7902981303687,
+            4096.0,
+            588.5403756622251,
+            2048.0,
+            -253.58784676354026,
+            2048.0,
+            701.9711029057634,
+            0.0,
+            -1310.58256496228,
+            0.0,
+            -1533.3333333333333,
+            2048.0,
+            -1972.7640449438202,
+            2048.0,
+            -2027.041836515769,
+            0.0,
+            -1997.6026603733105,
+            0.0,
+            -1867.082813150229,
+            2048.0,
+            -253.58784676354026,
+            2048.0,
+            -1779.2272727272727,
+            0.0,
+            -1867.082813150229,
+            0.0,
+            -1533.3333333333333,
+            2048.0,
+            -1779.2272727272727,
+            2048.0,
+            -1972.7640449438202,
+            -1997.9179619036652,
+            0.0,
+            -2048.0,
+            0.0,
+            -2048.0,
+            2048.0,
+            -968.9127966976264,
+            2048.0,
+            124.19051780578839,
+            2048.0,
+            606.027157249234,
+            2048.0,
+            3964.2975520321793,
+            0.0,
+            2420.1762264902154,
+            0.0,
+            931.9430122116689,
+            0.0,
+            1475.402360688774,
+            0.0,
+            -1893.0625,
+            0.0,
+            -1997.9179619036652,
+            0.0,
+            0.0,
+            2048.0,
+            -968.9127966976264,
+            2048.0,
+            0.0,
+            2048.0,
+            124.19051780578839,
+            2048.0,
+            6814.564897959183,
+            0.0,
+            6144.0,
+            0.0,
+            5693.087268148115,
+            -1.1368683772161603e-13,
+            6144.0,
+            -1.1368683772161603e-13,
+            5693.087268148115,
+            0.0,
+            4096.0,
+            0.0,
+            3964.2975520321793,
+            1.1368683772161603e-13,
+            4096.0,
+            1.1368683772161603e-13,
+            2048.0,
+            0.0,
+            1841.9542039355993,
+            0.0,
+            2048.0,
+            -1.1368683772161603e-13,
+            2420.1762264902154,
+            -1.1368683772161603e-13,
+            1475.402360688774,
+            0.0,
+            1841.9542039355993,
+            0.0,
+            2420.176226490216,
+            1.1368683772161603e-13,
+            3964.2975520321793,
+            1.1368683772161603e-13,
+            4096.0,
+            0.0,
+            3964.2975520321793,
+            0.0
+        ]
+    );
+}
+
+#[test]
+fn tesselate_added_vertex_count() {
+    let (mut vertices, mut indices) = earclip::<f64, usize>(&geometry(), None, None);
+    let original_count = vertices.len() / 2;
+    let added = earclip::tesselate(&mut vertices, &mut indices, 2048.0, 2);
+    assert_eq!(added, vertices.len() / 2 - original_count);
+    assert!(added > 0);
+    // the originals are kept up front
+    assert_eq!(vertices[..original_count * 2], FLAT_VERTICES);
+
+    let before = vertices.len() / 2;
+    let added = tesselate_max_edge(&mut vertices, &mut indices, 1000.0, 2);
+    assert_eq!(added, vertices.len() / 2 - before);
+}
+
+#[test]
+fn tesselate_first_triangle_split() {
+    // the very first triangle crosses the x = 0 and x = 1 grid lines
+    let polygon = vec![vec![vec![-1.0, 0.0], vec![2.0, 0.0], vec![0.5, 1.0]]];
+    let (vertices, indices) = earclip::<f64, usize>(&polygon, Some(1.0), None);
+    assert_eq!(indices.len() % 3, 0);
+    assert!(indices.len() > 3);
+    for triangle in indices.chunks(3) {
+        let xs = triangle.iter().map(|&i| vertices[i * 2]);
+        let min = xs.clone().fold(f64::INFINITY, f64::min);
+        let max = xs.fold(f64::NEG_INFINITY, f64::max);
+        // no triangle may straddle a grid line
+        assert!(max - min <= 1.0 && (min.floor() == max.floor() || max == max.floor()));
+    }
+}
+
+#[test]
+fn tesselate_max_edge_thin_triangle() {
+    let mut vertices: Vec<f64> = vec![0.0, 0.0, 100.0, 0.0, 0.0, 1.0];
+    let mut indices: Vec<usize> = vec![1, 2, 0];
+    tesselate_max_edge(&mut vertices, &mut indices, 10.0, 2);
+    assert!(indices.len() > 3);
+
+    let mut area = 0.0_f64;
+    for triangle in indices.chunks(3) {
+        let p = |i: usize| [vertices[triangle[i] * 2], vertices[triangle[i] * 2 + 1]];
+        for e in 0..3 {
+            let (a, b) = (p(e), p((e + 1) % 3));
+            assert!(((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt() <= 10.0);
+        }
+        let [a, b, c] = [p(0), p(1), p(2)];
+        let double_area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        // the winding of the input triangle is kept
+        assert!(double_area > 0.0);
+        area += double_area / 2.0;
+    }
+    assert!((area - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn subdivide_levels() {
+    let mut vertices: Vec<f64> = vec![0.0, 0.0, 4.0, 0.0, 0.0, 3.0];
+    let mut indices: Vec<usize> = vec![0, 1, 2];
+    assert_eq!(subdivide(&mut vertices, &mut indices, 1, 2), 3);
+    assert_eq!(indices.len(), 4 * 3);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 6.0);
+    assert!(all_same_winding(&vertices, &indices, 2));
+
+    // the two triangles of a square share the midpoints of their common edge
+    let (mut vertices, mut indices) = earclip::<f64, usize>(
+        &[vec![
+            vec![0.0, 0.0],
+            vec![4.0, 0.0],
+            vec![4.0, 4.0],
+            vec![0.0, 4.0],
+        ]],
+        None,
+        None,
+    );
+    subdivide(&mut vertices, &mut indices, 2, 2);
+    assert_eq!(indices.len(), 2 * 16 * 3);
+    assert_eq!(vertices.len(), 5 * 5 * 2);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 16.0);
+}
+
+#[test]
+fn tesselate_max_edge_degenerate() {
+    // a collinear triangle has no area to split, so it's left alone
+    let mut vertices = vec![0.0, 0.0, 50.0, 0.0, 100.0, 0.0];
+    let mut indices: Vec<usize> = vec![0, 1, 2];
+    tesselate_max_edge(&mut vertices, &mut indices, 1.0, 2);
+    assert_eq!(indices, vec![0, 1, 2]);
+    assert_eq!(vertices.len(), 6);
+}
+
+#[test]
+fn tesselate_z_axis() {
+    // x and y stay within one grid cell, only z crosses the 0.5 grid line
+    let polygon = vec![vec![
+        vec![0.1, 0.1, 0.0],
+        vec![0.4, 0.1, 1.0],
+        vec![0.1, 0.4, 0.2],
+    ]];
+    let (vertices, indices) = earclip::<f64, usize>(&polygon, Some(0.5), None);
+    assert_eq!(vertices.len() % 3, 0);
+    assert!(vertices.len() > 9);
+    assert!(indices.len() > 3);
+
+    let close =
+        |v: &[f64], expected: [f64; 3]| v.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-12);
+    let created: Vec<&[f64]> = vertices[9..].chunks(3).collect();
+    assert!(created.iter().all(|v| (v[2] - 0.5).abs() < 1e-12));
+    assert!(created.iter().any(|v| close(v, [0.25, 0.1, 0.5])));
+    assert!(created.iter().any(|v| close(v, [0.2125, 0.2875, 0.5])));
+}
+
+#[test]
+fn points_arrays() {
+    let rings: Vec<Vec<[f64; 2]>> = geometry()
+        .iter()
+        .map(|ring| ring.iter().map(|p| [p[0], p[1]]).collect())
+        .collect();
+    let slices: Vec<&[[f64; 2]]> = rings.iter().map(|ring| ring.as_slice()).collect();
+    let (vertices, indices) = earcut_points2d::<f64, usize>(&slices);
+    assert_eq!(vertices, FLAT_VERTICES);
+    assert_eq!(indices, FLAT_INDICES);
+
+    let rings_3d: Vec<[f64; 3]> = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    let (vertices, indices) = earcut_points3d::<f64, usize>(&[&rings_3d]);
+    assert_eq!(vertices, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(indices, vec![1, 2, 0]);
+}
+
+#[test]
+fn preserves_input_vertices() {
+    let polygon: Vec<Vec<Vec<f64>>> = vec![vec![
+        vec![0.0, 0.0],
+        vec![4.0, 0.0],
+        vec![4.0, 4.0],
+        vec![2.0, 1.0],
+        vec![0.0, 4.0],
+    ]];
+    let (vertices, indices) = earclip::<f64, usize>(&polygon, None, None);
+    let input: Vec<f64> = polygon[0].iter().flatten().copied().collect();
+    assert_eq!(vertices, input);
+    assert!(indices.iter().all(|&i| i < polygon[0].len()));
+}
+
+#[test]
+fn builder() {
+    let builder = Earclip::<f64, usize>::new().modulo(2048.0).offset(3);
+    assert_eq!(
+        builder.triangulate(&geometry()),
+        earclip(&geometry(), Some(2048.0), Some(3))
+    );
+
+    let (vertices, indices) = Earclip::<f64, usize>::new()
+        .winding(Winding::CounterClockwise)
+        .triangulate(&geometry());
+    assert!(all_same_winding(&vertices, &indices, 2));
+    let (_, clockwise) = Earclip::<f64, usize>::new()
+        .winding(Winding::Clockwise)
+        .triangulate(&geometry());
+    let flipped: Vec<usize> = indices.chunks(3).flat_map(|t| [t[0], t[2], t[1]]).collect();
+    assert_eq!(clockwise, flipped);
+
+    // every triangle of the geometry is far bigger than this
+    let (_, indices) = Earclip::<f64, usize>::new()
+        .min_area(1.0)
+        .triangulate(&geometry());
+    assert_eq!(indices, FLAT_INDICES);
+    let (_, indices) = Earclip::<f64, usize>::new()
+        .min_area(1e12)
+        .triangulate(&geometry());
+    assert!(indices.is_empty());
+
+    // a hole with a spike into the middle of the square
+    let polygon = vec![
+        vec![
+            vec![0.0, 0.0],
+            vec![4.0, 0.0],
+            vec![4.0, 4.0],
+            vec![0.0, 4.0],
+        ],
+        vec![
+            vec![1.0, 1.0],
+            vec![1.0, 3.0],
+            vec![2.0, 3.0],
+            vec![2.0, 3.5],
+            vec![2.0, 3.0],
+            vec![3.0, 3.0],
+            vec![3.0, 1.0],
+        ],
+    ];
+    let (vertices, indices) = Earclip::<f64, usize>::new()
+        .remove_spikes()
+        .triangulate(&polygon);
+    assert_eq!(vertices.len(), 2 * 9);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 12.0);
+}
+
+#[test]
+fn tracked_tesselation() {
+    let mut vertices: Vec<f64> = vec![0.0, 0.0, 2.0, 0.0, 0.0, 2.0];
+    let mut indices: Vec<usize> = vec![0, 1, 2];
+    let origins = tesselate_tracked(&mut vertices, &mut indices, 1.0, 2);
+    assert_eq!(origins.len(), vertices.len() / 2);
+    assert_eq!(
+        origins[..3],
+        (0..3).map(VertexOrigin::Original).collect::<Vec<_>>()
+    );
+
+    // the split of the bottom edge at x = 1 is its midpoint
+    let midpoint = (0..origins.len())
+        .find(|&i| vertices[i * 2..i * 2 + 2] == [1.0, 0.0])
+        .unwrap();
+    match origins[midpoint] {
+        VertexOrigin::Interpolated { a, b, t } => {
+            assert_eq!((a.min(b), a.max(b)), (0, 1));
+            assert_eq!(t, 0.5);
+        }
+        origin => panic!("unexpected origin {origin:?}"),
+    }
+    // every new vertex lies where its origin says
+    for (i, origin) in origins.iter().enumerate() {
+        if let VertexOrigin::Interpolated { a, b, t } = *origin {
+            assert!(a < i && b < i);
+            for axis in 0..2 {
+                let (va, vb) = (vertices[a * 2 + axis], vertices[b * 2 + axis]);
+                assert!((va + (vb - va) * t - vertices[i * 2 + axis]).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn iterator_rings() {
+    let polygon = geometry();
+    // a streaming producer handing out one coordinate pair at a time
+    let rings = polygon
+        .iter()
+        .map(|ring| ring.iter().map(|point| [point[0], point[1]]));
+    let (vertices, indices): (Vec<f64>, Vec<usize>) = earcut_iter(rings);
+    assert_eq!((vertices, indices), earclip(&polygon, None, None));
+
+    let (vertices, indices): (Vec<f64>, Vec<usize>) =
+        earcut_iter(core::iter::empty::<Vec<[f64; 2]>>());
+    assert!(vertices.is_empty() && indices.is_empty());
+}
+
+#[test]
+fn consistent_winding() {
+    let counter_clockwise = vec![vec![
+        vec![0.0, 0.0],
+        vec![0.0, 4.0],
+        vec![4.0, 4.0],
+        vec![4.0, 0.0],
+    ]];
+    let clockwise = vec![vec![
+        vec![0.0, 0.0],
+        vec![4.0, 0.0],
+        vec![4.0, 4.0],
+        vec![0.0, 4.0],
+    ]];
+    let flat = |polygon: &Vec<Vec<Vec<f64>>>| -> Vec<f64> {
+        polygon[0].iter().flatten().copied().collect()
+    };
+    assert!(is_counter_clockwise(&flat(&counter_clockwise), 2));
+    assert!(is_clockwise(&flat(&clockwise), 2));
+    // signed area of every triangle in y-up coordinates, positive when counter-clockwise
+    let areas = |vertices: &[f64], indices: &[usize]| -> Vec<f64> {
+        indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [t[0], t[1], t[2]].map(|i| [vertices[i * 2], vertices[i * 2 + 1]]);
+                (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+            })
+            .collect()
+    };
+    let triangles = |vertices: &[f64], indices: &[usize]| -> Vec<Vec<f64>> {
+        indices
+            .chunks(3)
+            .map(|t| {
+                t.iter()
+                    .flat_map(|&i| [vertices[i * 2], vertices[i * 2 + 1]])
+                    .collect()
+            })
+            .collect()
+    };
+
+    for polygon in [&counter_clockwise, &clockwise] {
+        for (winding, sign) in [(Winding::CounterClockwise, 1.0), (Winding::Clockwise, -1.0)] {
+            let (vertices, indices) = Earclip::<f64, usize>::new()
+                .winding(winding)
+                .triangulate(polygon);
+            assert_eq!(indices.len(), 6);
+            assert!(areas(&vertices, &indices)
+                .iter()
+                .all(|&area| area * sign > 0.0));
+        }
+        // by default, triangles are counter-clockwise in y-up whatever the input
+        let (vertices, indices) = Earclip::<f64, usize>::new().triangulate(polygon);
+        assert!(areas(&vertices, &indices).iter().all(|&area| area > 0.0));
+        let (vertices, indices) = earclip::<f64, usize>(polygon, None, None);
+        assert!(areas(&vertices, &indices).iter().all(|&area| area > 0.0));
+
+        let (vertices, indices) = Earclip::<f64, usize>::new()
+            .winding(Winding::AsInput)
+            .triangulate(polygon);
+        let expected = is_clockwise(&flat(polygon), 2);
+        for triangle in triangles(&vertices, &indices) {
+            assert_eq!(is_clockwise(&triangle, 2), expected);
+        }
+    }
+}
+
+#[test]
+fn flatten_point_objects() {
+    let polygon: Vec<Vec<Point>> = geometry()
+        .iter()
+        .map(|ring| ring.iter().map(|p| Point { x: p[0], y: p[1] }).collect())
+        .collect();
+    let flat = flatten_points2d::<f64, usize, Point>(&polygon);
+    assert_eq!(flat, flatten(&convert_2d(&polygon)));
+    assert_eq!(flat.0, FLAT_VERTICES);
+
+    let polygon = vec![
+        vec![(0.0, 0.0, 1.0), (4.0, 0.0, 2.0), (4.0, 4.0, 3.0)],
+        vec![(1.0, 1.0, 4.0), (2.0, 1.0, 5.0), (2.0, 2.0, 6.0)],
+    ];
+    let flat = flatten_points3d::<f64, u32, _>(&polygon);
+    assert_eq!(flat, flatten(&convert_3d(&polygon)));
+    assert_eq!(flat.1, [3]);
+}
+
+#[test]
+fn ragged_points() {
+    // the first point decides the dimension, the rest are padded or truncated to match
+    let polygon = vec![vec![
+        vec![0.0, 0.0],
+        vec![4.0, 0.0, 9.0],
+        vec![4.0, 4.0],
+        vec![0.0],
+    ]];
+    let (vertices, _, dim) = flatten::<f64, usize>(&polygon);
+    assert_eq!(dim, 2);
+    assert_eq!(vertices, [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 0.0]);
+    let (_, indices) = earclip::<f64, usize>(&polygon, None, None);
+    assert_eq!(indices.len(), 3);
+
+    let polygon = vec![vec![
+        vec![0.0, 0.0, 1.0],
+        vec![4.0, 0.0],
+        vec![4.0, 4.0, 3.0, 7.0],
+    ]];
+    let (vertices, _, dim) = flatten::<f64, usize>(&polygon);
+    assert_eq!(dim, 3);
+    assert_eq!(vertices, [0.0, 0.0, 1.0, 4.0, 0.0, 0.0, 4.0, 4.0, 3.0]);
+}
+
+#[test]
+fn tesselate_vertex_limit() {
+    // a unit triangle off the grid lines
+    let triangle = vec![0.31415, 0.27182, 1.31415, 0.27182, 0.31415, 1.27182];
+    let mut vertices: Vec<f64> = triangle.clone();
+    let mut indices: Vec<usize> = vec![0, 1, 2];
+    // uncapped, this would add millions of vertices per axis
+    assert_eq!(
+        try_tesselate(&mut vertices, &mut indices, 1e-6, 2, 10_000),
+        Err(EarcutError::VertexLimit(10_000))
+    );
+    assert!(vertices.len() / 2 <= 10_000);
+    // the mesh is left whole, just coarser
+    assert!((triangulated_area(&vertices, &indices, 2) - 0.5).abs() < 1e-9);
+
+    // a sane modulo stays under the cap and matches plain tesselation
+    let mut capped: (Vec<f64>, Vec<usize>) = (triangle.clone(), vec![0, 1, 2]);
+    let added = try_tesselate(&mut capped.0, &mut capped.1, 0.25, 2, 10_000).unwrap();
+    let mut plain: (Vec<f64>, Vec<usize>) = (triangle.clone(), vec![0, 1, 2]);
+    assert_eq!(
+        earclip::tesselate(&mut plain.0, &mut plain.1, 0.25, 2),
+        added
+    );
+    assert_eq!(capped, plain);
+}
+
+#[test]
+fn source_rings() {
+    let polygon = geometry();
+    let (vertices, hole_indices, dim, rings) = flatten_with_rings::<f64, usize>(&polygon);
+    assert_eq!(vertices, FLAT_VERTICES);
+    assert_eq!(rings, [0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+    // each ring starts where the hole indices say
+    for (r, &start) in hole_indices.iter().enumerate() {
+        assert_eq!(rings[start - 1], r);
+        assert_eq!(rings[start], r + 1);
+    }
+    for (i, &r) in rings.iter().enumerate() {
+        assert!(polygon[r]
+            .iter()
+            .any(|p| p[..] == vertices[i * dim..i * dim + 2]));
+    }
+
+    let (mesh, mesh_rings) = earclip_mesh_with_rings::<f64, usize>(&polygon, Some(2048.0));
+    assert_eq!(mesh_rings, rings);
+    // the tesselated vertices come after those of the rings
+    assert_eq!(mesh.vertices[..rings.len() * dim], FLAT_VERTICES);
+    assert!(mesh.vertex_count() > rings.len());
+}
+
+#[test]
+fn tesselate_in_batches() {
+    let (mut vertices, indices) = earclip::<f64, usize>(&geometry(), None, None);
+    let stream = |vertices: &mut Vec<f64>, batch_size| {
+        let (mut all, mut all_indices) = (vertices.clone(), Vec::new());
+        let added = tesselate_chunked(vertices, &indices, 512.0, 2, batch_size, |new, batch| {
+            all.extend_from_slice(new);
+            all_indices.extend_from_slice(batch);
+        });
+        assert_eq!(all.len() / 2, vertices.len() / 2 + added);
+        (all, all_indices)
+    };
+    let mut in_place = (vertices.clone(), indices.clone());
+    earclip::tesselate(&mut in_place.0, &mut in_place.1, 512.0, 2);
+
+    // a single batch is plain tesselation
+    assert_eq!(stream(&mut vertices, 1000), in_place);
+    assert_eq!(vertices, FLAT_VERTICES);
+
+    // smaller batches number the vertices differently but cut the same triangles
+    let corners = |(vertices, indices): &(Vec<f64>, Vec<usize>)| {
+        let mut triangles: Vec<Vec<[u64; 2]>> = indices
+            .chunks(3)
+            .map(|triangle| {
+                let mut corners: Vec<[u64; 2]> = triangle
+                    .iter()
+                    .map(|&i| [vertices[i * 2].to_bits(), vertices[i * 2 + 1].to_bits()])
+                    .collect();
+                corners.sort();
+                corners
+            })
+            .collect();
+        triangles.sort();
+        triangles
+    };
+    for batch_size in [1, 5] {
+        let streamed = stream(&mut vertices, batch_size);
+        assert_eq!(streamed.0.len(), in_place.0.len());
+        assert_eq!(corners(&streamed), corners(&in_place));
+    }
+}
+
+#[test]
+fn triangle_mesh() {
+    let mesh = earclip_mesh::<f64, usize>(&geometry(), None);
+    assert_eq!(mesh.dim, 2);
+    assert_eq!(mesh.vertices, FLAT_VERTICES);
+    assert_eq!(mesh.indices, FLAT_INDICES);
+    assert_eq!(mesh.vertex_count(), 15);
+    assert_eq!(mesh.triangle_count(), 12);
+    let first: Vec<&[f64]> = mesh.iter_triangles().next().unwrap().to_vec();
+    assert_eq!(
+        first,
+        [&[-2048.0, 2685.0][..], &[-2048.0, -37.0], &[1235.0, 747.0]]
+    );
+    assert_eq!(mesh.iter_triangles().count(), 12);
+    assert_eq!(
+        mesh.area(),
+        triangulated_area(&mesh.vertices, &mesh.indices, 2)
+    );
+
+    // tesselation adds vertices and triangles, but not area
+    let tesselated = earclip_mesh::<f64, usize>(&geometry(), Some(2048.0));
+    assert_eq!(
+        (tesselated.vertices.clone(), tesselated.indices.clone()),
+        earclip(&geometry(), Some(2048.0), None)
+    );
+    assert!(tesselated.vertex_count() > mesh.vertex_count());
+    assert!(tesselated.triangle_count() > mesh.triangle_count());
+    assert!((tesselated.area() - mesh.area()).abs() < 1e-6 * mesh.area());
+
+    let square = vec![vec![
+        vec![0.0, 0.0, 1.0],
+        vec![2.0, 0.0, 1.0],
+        vec![2.0, 2.0, 1.0],
+        vec![0.0, 2.0, 1.0],
+    ]];
+    let mesh = earclip_mesh::<f64, u16>(&square, None);
+    assert_eq!((mesh.dim, mesh.vertex_count(), mesh.area()), (3, 4, 4.0));
+    assert!(mesh
+        .iter_triangles()
+        .flatten()
+        .all(|point| point.len() == 3));
+    assert_eq!(TriangleMesh::<f64, u16>::default().vertex_count(), 0);
+}
+
+#[test]
+fn flat_input() {
+    let polygon = geometry();
+    let (data, hole_indices, dim) = flatten::<f64, u32>(&polygon);
+    assert_eq!(
+        earclip_flat(&data, &hole_indices, dim, Some(1000.0), Some(5)),
+        earclip(&polygon, Some(1000.0), Some(5))
+    );
+    assert_eq!(
+        earclip_flat::<f64, u32>(&data, &hole_indices, dim, None, None),
+        earclip(&polygon, None, None)
+    );
+}
+
+#[test]
+fn incremental_polygon() {
+    let polygon = vec![
+        vec![
+            vec![0.0, 0.0],
+            vec![10.0, 0.0],
+            vec![10.0, 10.0],
+            vec![0.0, 10.0],
+        ],
+        vec![
+            vec![2.0, 2.0],
+            vec![2.0, 4.0],
+            vec![4.0, 4.0],
+            vec![4.0, 2.0],
+        ],
+    ];
+    let mut incremental = IncrementalPolygon::<f64, u32>::new();
+    for (i, point) in polygon[0].iter().enumerate() {
+        incremental.push_vertex([point[0], point[1]]);
+        // a live preview after every vertex
+        assert_eq!(
+            incremental.triangulate().triangle_count(),
+            i.saturating_sub(1)
+        );
+    }
+    let (vertices, indices) = earclip(&polygon[..1], None, None);
+    let mesh = incremental.triangulate();
+    assert_eq!((mesh.vertices, mesh.indices), (vertices, indices));
+
+    // clicking the first point again closes the ring without a duplicate vertex
+    incremental.push_vertex([0.0, 0.0]);
+    incremental.close_ring();
+    incremental.begin_hole();
+    for point in &polygon[1] {
+        incremental.push_vertex([point[0], point[1]]);
+    }
+    assert_eq!(incremental.hole_indices(), &[4]);
+    let (vertices, indices) = earclip(&polygon, None, None);
+    let mesh = incremental.triangulate();
+    assert_eq!((mesh.vertices, mesh.indices), (vertices, indices));
+
+    incremental.clear();
+    assert!(incremental.vertices().is_empty());
+    assert!(incremental.triangulate().indices.is_empty());
+}
+
+#[test]
+fn multipolygon() {
+    let square = |x: f64| {
+        vec![
+            vec![x, 0.0],
+            vec![x + 1.0, 0.0],
+            vec![x + 1.0, 1.0],
+            vec![x, 1.0],
+        ]
+    };
+    let polygons = vec![(square(0.0), vec![]), (square(5.0), vec![])];
+    let (mesh, ranges) = earcut_multipolygon::<f64, u32>(&polygons);
+    assert_eq!(mesh.triangle_count(), 4);
+    assert_eq!(mesh.vertex_count(), 8);
+    assert_eq!(ranges, vec![0..6, 6..12]);
+    assert!(mesh.indices[ranges[0].clone()].iter().all(|&i| i < 4));
+    assert!(mesh.indices[ranges[1].clone()]
+        .iter()
+        .all(|&i| (4..8).contains(&i)));
+    assert_eq!(mesh.area(), 2.0);
+
+    // holes stay with their own polygon
+    let hole = vec![vec![
+        vec![5.25, 0.25],
+        vec![5.25, 0.75],
+        vec![5.75, 0.75],
+        vec![5.75, 0.25],
+    ]];
+    let (mesh, ranges) =
+        earcut_multipolygon::<f64, u32>(&[(square(0.0), vec![]), (square(5.0), hole)]);
+    assert_eq!(ranges[0], 0..6);
+    assert_eq!(mesh.vertex_count(), 12);
+    assert_eq!(mesh.area(), 1.75);
+}
+
+#[test]
+fn steiner_points_and_holes() {
+    let square = |min: f64, max: f64| {
+        vec![
+            vec![min, min],
+            vec![max, min],
+            vec![max, max],
+            vec![min, max],
+        ]
+    };
+    // a hole cuts a region out
+    let (vertices, indices) =
+        earclip::<f64, u32>(&[square(0.0, 4.0), square(1.0, 3.0)], None, None);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 12.0);
+
+    // a steiner point only joins the triangles that meet at it
+    let (vertices, indices) =
+        earclip_with_steiner::<f64, u32>(&[square(0.0, 4.0)], &[vec![2.0, 2.0]], None, None);
+    assert_eq!(vertices[8..], [2.0, 2.0]);
+    assert_eq!(indices.len(), 4 * 3);
+    assert!(indices.chunks(3).all(|triangle| triangle.contains(&4)));
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 16.0);
+    // the same as a hole ring of a single point
+    let point_hole = earclip(&[square(0.0, 4.0), vec![vec![2.0, 2.0]]], None, None);
+    assert_eq!((vertices, indices), point_hole);
+}
+
+#[test]
+fn area_weighted_centroid() {
+    let l_shape = vec![vec![
+        vec![0.0, 0.0, 2.0],
+        vec![10.0, 0.0, 2.0],
+        vec![10.0, 4.0, 2.0],
+        vec![4.0, 4.0, 2.0],
+        vec![4.0, 10.0, 2.0],
+        vec![0.0, 10.0, 2.0],
+    ]];
+    let (vertices, indices) = earclip::<f64, u32>(&l_shape, None, None);
+    let inside = |[x, y]: [f64; 2]| point_in_polygon(&vertices, 3, [x, y]);
+    // the vertices average out to the empty corner of the L
+    let average = [28.0 / 6.0, 28.0 / 6.0];
+    assert!(!inside(average));
+
+    // the 10 x 4 and 4 x 6 rectangles, centered at (5, 2) and (2, 7)
+    let [x, y] = centroid(&vertices, &indices, 3);
+    assert!((x - 248.0 / 64.0).abs() < 1e-12 && (y - 248.0 / 64.0).abs() < 1e-12);
+    assert!(inside([x, y]));
+    let [x3, y3, z3] = centroid_3d(&vertices, &indices, 3);
+    assert!((x3 - x).abs() < 1e-12 && (y3 - y).abs() < 1e-12 && z3 == 2.0);
+
+    // without area, the vertex average
+    let line: [f64; 6] = [0.0, 0.0, 1.0, 1.0, 5.0, 5.0];
+    assert_eq!(centroid::<f64, u32>(&line, &[0, 1, 2], 2), [2.0, 2.0]);
+}
+
+#[test]
+fn classified_rings() {
+    let square = |min: f64, max: f64| vec![[min, min], [max, min], [max, max], [min, max]];
+    let mut hole = square(2.0, 8.0);
+    hole.reverse();
+    // an island in the hole, a separate square, the hole and its outer ring, in any winding
+    let rings = vec![
+        square(4.0, 6.0),
+        square(20.0, 22.0),
+        hole,
+        square(0.0, 10.0),
+    ];
+    let classified = classify_rings(&rings);
+    assert_eq!(classified, vec![(0, vec![]), (1, vec![]), (3, vec![2])]);
+
+    let to_vecs = |ring: &Vec<[f64; 2]>| ring.iter().map(|p| p.to_vec()).collect::<Vec<_>>();
+    let polygons: Vec<PolygonRings<f64>> = classified
+        .iter()
+        .map(|(outer, holes)| {
+            let holes = holes.iter().map(|&h| to_vecs(&rings[h])).collect();
+            (to_vecs(&rings[*outer]), holes)
+        })
+        .collect();
+    let (mesh, _) = earcut_multipolygon::<f64, u32>(&polygons);
+    assert_eq!(mesh.area(), 100.0 - 36.0 + 4.0 + 4.0);
+}
+
+#[test]
+fn tesselate_phase() {
+    let triangle = vec![0.0, 0.0, 300.0, 0.0, 0.0, 300.0];
+    let (mut vertices, mut indices) = (triangle.clone(), vec![0_usize, 1, 2]);
+    let added = tesselate_with_origin(&mut vertices, &mut indices, 256.0, 128.0, 2);
+    assert!(added > 0);
+    // every new vertex sits on a grid line at 128 + k * 256
+    for point in vertices[triangle.len()..].chunks(2) {
+        assert!(point.contains(&128.0), "{point:?}");
+    }
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 45000.0);
+
+    // a zero origin is plain tesselation
+    let (mut shifted, mut shifted_indices) = (triangle.clone(), vec![0_usize, 1, 2]);
+    tesselate_with_origin(&mut shifted, &mut shifted_indices, 256.0, 0.0, 2);
+    let (mut plain, mut plain_indices) = (triangle, vec![0_usize, 1, 2]);
+    earclip::tesselate(&mut plain, &mut plain_indices, 256.0, 2);
+    assert_eq!((shifted, shifted_indices), (plain, plain_indices));
+}
+
+#[test]
+fn invalid_modulo() {
+    let polygon = geometry();
+    let untesselated = earclip::<f64, u32>(&polygon, None, None);
+    for modulo in [
+        0.0,
+        -0.0,
+        -2048.0,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ] {
+        assert_eq!(earclip(&polygon, Some(modulo), None), untesselated);
+        let (mut vertices, mut indices) = untesselated.clone();
+        assert_eq!(
+            earclip::tesselate(&mut vertices, &mut indices, modulo, 2),
+            0
+        );
+        assert_eq!((vertices, indices), untesselated);
+    }
+}
+
+#[test]
+fn append_meshes() {
+    let triangle = |x: f64| vec![vec![vec![x, 0.0], vec![x + 1.0, 0.0], vec![x, 1.0]]];
+    let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+    let first = earclip_mesh::<f64, u16>(&triangle(0.0), None);
+    let second = earclip_mesh::<f64, u16>(&triangle(5.0), None);
+    assert_eq!(append_mesh(&mut vertices, &mut indices, &first), 0);
+    assert_eq!(append_mesh(&mut vertices, &mut indices, &second), 3);
+
+    let mut expected = first.indices.clone();
+    expected.extend(second.indices.iter().map(|i| i + 3));
+    assert_eq!(indices, expected);
+    assert_eq!(vertices[6..], second.vertices[..]);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 1.0);
+}
+
+#[test]
+fn array_vertices() {
+    let polygon = geometry();
+    let (flat, indices) = earclip::<f64, u32>(&polygon, Some(1000.0), None);
+    let (arrays, array_indices) = earclip_arrays::<f64, u32>(&polygon, Some(1000.0), None);
+    assert_eq!(array_indices, indices);
+    assert_eq!(arrays.as_flattened(), flat.as_slice());
+
+    let (arrays, _) = earclip_arrays_3d::<f64, u32>(&polygon, None, None);
+    let (flat, _) = earclip::<f64, u32>(&polygon, None, None);
+    assert_eq!(arrays.len(), flat.len() / 2);
+    assert!(arrays
+        .iter()
+        .zip(flat.chunks(2))
+        .all(|(a, f)| a[..2] == *f && a[2] == 0.0));
+
+    let polygon = vec![vec![
+        vec![0.0, 0.0, 1.0],
+        vec![4.0, 0.0, 2.0],
+        vec![4.0, 4.0, 3.0],
+        vec![0.0, 4.0, 4.0],
+    ]];
+    let (flat, indices) = earclip::<f64, usize>(&polygon, None, None);
+    let (arrays, array_indices) = earclip_arrays_3d::<f64, usize>(&polygon, None, None);
+    assert_eq!(array_indices, indices);
+    assert_eq!(arrays.as_flattened(), flat.as_slice());
+    let (arrays, _) = earclip_arrays::<f64, usize>(&polygon, None, None);
+    assert_eq!(arrays, vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+}
+
+#[test]
+fn extruded_prism() {
+    // the volume from the divergence theorem is only right if every triangle faces outward
+    let volume = |mesh: &TriangleMesh<f64, u32>| -> f64 {
+        mesh.iter_triangles()
+            .map(|[a, b, c]| {
+                let cross = [
+                    b[1] * c[2] - b[2] * c[1],
+                    b[2] * c[0] - b[0] * c[2],
+                    b[0] * c[1] - b[1] * c[0],
+                ];
+                (a[0] * cross[0] + a[1] * cross[1] + a[2] * cross[2]) / 6.0
+            })
+            .sum()
+    };
+
+    let square = vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]];
+    let cube: TriangleMesh<f64, u32> = extrude(&square, 1.0);
+    assert_eq!(cube.dim, 3);
+    assert_eq!(cube.vertex_count(), 8);
+    assert_eq!(cube.triangle_count(), 2 + 2 + 4 * 2);
+    assert!((volume(&cube) - 1.0).abs() < 1e-12);
+
+    // either winding, downward, and with a hole and a closing point
+    let mut reversed = square.clone();
+    reversed[0].reverse();
+    assert!((volume(&extrude(&reversed, 1.0)) - 1.0).abs() < 1e-12);
+    let below: TriangleMesh<f64, u32> = extrude(&square, -2.0);
+    assert!((volume(&below) - 2.0).abs() < 1e-12);
+
+    let footprint = vec![
+        vec![[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0], [0.0, 0.0]],
+        vec![[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 2.0]],
+    ];
+    let prism: TriangleMesh<f64, u32> = extrude(&footprint, 2.0);
+    assert_eq!(prism.triangle_count(), 8 + 8 + 8 * 2);
+    assert!((volume(&prism) - 16.0).abs() < 1e-12);
+}
+
+#[test]
+fn offset_square() {
+    let area = |rings: &[Vec<[f64; 2]>]| -> f64 {
+        rings
+            .iter()
+            .map(|ring| {
+                let (vertices, indices) = earcut_points2d::<f64, usize>(&[ring]);
+                triangulated_area(&vertices, &indices, 2)
+            })
+            .sum()
+    };
+    let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+    // the sides grow by 2, plus a quarter square, disc or triangle in each corner
+    let grown = offset_polygon(&square, 1.0, JoinStyle::Miter);
+    assert_eq!(grown.len(), 1);
+    assert_eq!(area(&grown), 4.0 + 8.0 + 4.0);
+    assert_eq!(
+        area(&offset_polygon(&square, 1.0, JoinStyle::Bevel)),
+        4.0 + 8.0 + 2.0
+    );
+    let round = area(&offset_polygon(&square, 1.0, JoinStyle::Round));
+    assert!(round < 12.0 + PI && round > 12.0 + PI - 0.05);
+
+    // the other winding and closed in, the same winding out
+    let mut reversed = square.to_vec();
+    reversed.push(square[0]);
+    reversed.reverse();
+    let shrunk = offset_polygon(&reversed, -0.5, JoinStyle::Round);
+    assert_eq!(shrunk.len(), 1);
+    assert!(!is_clockwise(shrunk[0].as_flattened(), 2));
+    assert_eq!(area(&shrunk), 1.0);
+    assert!(offset_polygon(&square, -1.5, JoinStyle::Miter).is_empty());
+
+    // shrinking a dumbbell cuts through its narrow middle
+    let dumbbell = [
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.0, 1.5],
+        [6.0, 1.5],
+        [6.0, 0.0],
+        [10.0, 0.0],
+        [10.0, 4.0],
+        [6.0, 4.0],
+        [6.0, 2.5],
+        [4.0, 2.5],
+        [4.0, 4.0],
+        [0.0, 4.0],
+    ];
+    let parts = offset_polygon(&dumbbell, -1.0, JoinStyle::Miter);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(area(&parts), 2.0 * 2.0 * 2.0);
+
+    // growing a room shuts its door, leaving a hole wound the other way
+    let room = [
+        [0.0, 0.0],
+        [10.0, 0.0],
+        [10.0, 10.0],
+        [6.0, 10.0],
+        [6.0, 9.0],
+        [9.0, 9.0],
+        [9.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 9.0],
+        [4.0, 9.0],
+        [4.0, 10.0],
+        [0.0, 10.0],
+    ];
+    let grown = offset_polygon(&room, 1.25, JoinStyle::Miter);
+    assert_eq!(grown.len(), 2);
+    assert!(!is_clockwise(grown[1].as_flattened(), 2));
+    let (vertices, indices) = earcut_points2d::<f64, usize>(&[&grown[0], &grown[1]]);
+    assert_eq!(
+        triangulated_area(&vertices, &indices, 2),
+        12.5 * 12.5 - 5.5 * 5.5
+    );
+}
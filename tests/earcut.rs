@@ -0,0 +1,1094 @@
+use earclip::{
+    all_same_winding, delaunay_flip, deviation, earclip_checked, earcut, earcut_impl, earcut_into,
+    earcut_into_slice, earcut_observed, earcut_range, earcut_ranges, earcut_triangles,
+    earcut_with_bbox, earcut_with_steiner, filter_slivers, flatten, flatten_dim, flatten_float,
+    flatten_into, flatten_with_areas, has_self_intersections, is_clockwise, is_counter_clockwise,
+    merge_to_quads, normalize_winding, orient2d, point_in_polygon, point_in_triangle,
+    remove_spikes, ring_area, segments_intersect, simplify_ring, snap_to_grid, suggest_tolerance,
+    triangulate_iter, triangulated_area, triangulation_report, try_earcut_strict,
+    validate_triangulation, EarcutError, Node, PassStats, Store,
+};
+
+use std::collections::BTreeMap;
+use std::fs;
+
+fn fixture(name: &str) -> Vec<Vec<Vec<f64>>> {
+    let path = format!("{}/test/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// the flattened vertices of a regular `n`-gon around the origin
+fn regular_polygon(n: usize, radius: f64) -> Vec<f64> {
+    (0..n)
+        .flat_map(|i| {
+            let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+            [angle.cos() * radius, angle.sin() * radius]
+        })
+        .collect()
+}
+
+/// the expectations shared with the TypeScript suite, overridden and extended by those only the
+/// Rust port meets
+fn expected() -> (BTreeMap<String, usize>, BTreeMap<String, f64>) {
+    let mut triangles = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    for name in ["expected", "expected-rust"] {
+        let path = format!("{}/test/{name}.json", env!("CARGO_MANIFEST_DIR"));
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let file_triangles: BTreeMap<String, usize> =
+            serde_json::from_value(json["triangles"].clone()).unwrap();
+        let file_errors: BTreeMap<String, f64> =
+            serde_json::from_value(json["errors"].clone()).unwrap();
+        triangles.extend(file_triangles);
+        errors.extend(file_errors);
+    }
+    (triangles, errors)
+}
+
+#[test]
+fn indices_2d() {
+    let indices: Vec<usize> = earcut(&[10.0, 0.0, 0.0, 50.0, 60.0, 60.0, 70.0, 10.0], &[], 2);
+    assert_eq!(indices, vec![1, 0, 3, 3, 2, 1]);
+}
+
+#[test]
+fn indices_3d() {
+    let data = [
+        10.0, 0.0, 0.0, 0.0, 50.0, 0.0, 60.0, 60.0, 0.0, 70.0, 10.0, 0.0,
+    ];
+    let indices: Vec<usize> = earcut(&data, &[], 3);
+    assert_eq!(indices, vec![1, 0, 3, 3, 2, 1]);
+}
+
+#[test]
+fn empty() {
+    let indices: Vec<usize> = earcut::<f64, usize>(&[], &[], 2);
+    assert!(indices.is_empty());
+}
+
+#[test]
+fn fixtures() {
+    let (triangles, errors) = expected();
+    for (id, expected_triangles) in triangles {
+        let (vertices, hole_indices, dim) = flatten_float(&fixture(&id));
+        let indices = earcut(&vertices, &hole_indices, dim);
+        let actual_deviation = deviation(&vertices, &hole_indices, dim, &indices);
+        let expected_deviation = errors.get(&id).copied().unwrap_or(0.0);
+
+        assert_eq!(indices.len() / 3, expected_triangles, "{id} triangle count");
+        if expected_triangles > 0 {
+            assert!(
+                actual_deviation <= expected_deviation,
+                "{id} deviation {actual_deviation} > {expected_deviation}"
+            );
+        }
+    }
+}
+
+#[test]
+fn fixtures_f32_u32() {
+    let (triangles, _) = expected();
+    for id in ["building", "dude", "water3b", "issue16", "hilbert"] {
+        let polygon: Vec<Vec<Vec<f32>>> = fixture(id)
+            .iter()
+            .map(|ring| {
+                ring.iter()
+                    .map(|p| p.iter().map(|&v| v as f32).collect())
+                    .collect()
+            })
+            .collect();
+        let (vertices, hole_indices, dim) = flatten::<f32, u32>(&polygon);
+        let indices = earcut(&vertices, &hole_indices, dim);
+        assert_eq!(indices.len() / 3, triangles[id], "{id} triangle count");
+    }
+}
+
+#[test]
+fn infinite_loop() {
+    let data = [
+        1.0, 2.0, 2.0, 2.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0, 4.0, 1.0, 5.0, 1.0, 3.0, 2.0, 4.0, 2.0,
+        4.0, 1.0,
+    ];
+    let indices: Vec<usize> = earcut(&data, &[5], 2);
+    assert_eq!(indices, vec![8, 5, 6]);
+}
+
+#[test]
+fn infinite_loop_ranges() {
+    let data = [
+        1.0, 2.0, 2.0, 2.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0, 4.0, 1.0, 5.0, 1.0, 3.0, 2.0, 4.0, 2.0,
+        4.0, 1.0,
+    ];
+    let expected: Vec<usize> = earcut(&data, &[5], 2);
+    assert_eq!(
+        earcut_ranges::<f64, usize>(&data, &[0..5, 5..10], 2),
+        expected
+    );
+
+    // the same rings stored hole first with a spare vertex in between
+    let mut shuffled = data[10..].to_vec();
+    shuffled.extend([9.0, 9.0]);
+    shuffled.extend(&data[..10]);
+    let indices: Vec<usize> = earcut_ranges(&shuffled, &[6..11, 0..5], 2);
+    let remapped: Vec<usize> = expected
+        .iter()
+        .map(|&i| if i < 5 { i + 6 } else { i - 5 })
+        .collect();
+    assert_eq!(indices, remapped);
+}
+
+#[test]
+fn reuse_output_buffer() {
+    let square = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    let triangle = [0.0, 0.0, 2.0, 0.0, 0.0, 2.0];
+    let mut store = Store::new();
+    let mut triangles: Vec<usize> = Vec::new();
+
+    // appending keeps the previous polygon's triangles
+    earcut_impl(&square, &[], 2, &mut triangles, &mut store);
+    earcut_impl(&triangle, &[], 2, &mut triangles, &mut store);
+    let mut appended = earcut(&square, &[], 2);
+    appended.extend(earcut::<f64, usize>(&triangle, &[], 2));
+    assert_eq!(triangles, appended);
+
+    // clearing only keeps the capacity
+    let capacity = triangles.capacity();
+    earcut_into(&triangle, &[], 2, &mut triangles, &mut store);
+    assert_eq!(triangles, earcut::<f64, usize>(&triangle, &[], 2));
+    assert!(triangles.capacity() >= capacity);
+}
+
+#[test]
+fn same_winding() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let mut indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    assert!(all_same_winding(&vertices, &indices, dim));
+
+    // flip a single triangle
+    indices.swap(0, 1);
+    assert!(!all_same_winding(&vertices, &indices, dim));
+}
+
+#[test]
+#[allow(clippy::chunks_exact_to_as_chunks)]
+fn triangles_as_arrays() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("water3b"));
+    let flat: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    let triangles = earcut_triangles(&vertices, &hole_indices, dim);
+    let chunked: Vec<[usize; 3]> = flat.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+    assert_eq!(triangles, chunked);
+}
+
+#[test]
+fn checked_deviation() {
+    // bad-hole is known to triangulate with a deviation of almost 0.019
+    let polygon = fixture("bad-hole");
+    let (mesh, warning) = earclip_checked::<f64, usize>(&polygon, None, 0.01);
+    assert_eq!(mesh.indices.len() / 3, 43);
+    let warning = warning.expect("deviation above tolerance");
+    assert_eq!(warning.tolerance, 0.01);
+    assert!(warning.deviation > 0.01 && warning.deviation <= 0.019);
+
+    let (_, warning) = earclip_checked::<f64, usize>(&fixture("dude"), None, 0.01);
+    assert_eq!(warning, None);
+}
+
+#[test]
+fn ring_winding() {
+    let clockwise = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    assert!(is_clockwise(&clockwise, 2));
+    assert!(!is_counter_clockwise(&clockwise, 2));
+
+    let counter_clockwise = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0];
+    assert!(!is_clockwise(&counter_clockwise, 2));
+    assert!(is_counter_clockwise(&counter_clockwise, 2));
+
+    let degenerate = [0.0, 0.0, 1.0, 1.0, 2.0, 2.0];
+    assert!(!is_clockwise(&degenerate, 2));
+    assert!(!is_counter_clockwise(&degenerate, 2));
+}
+
+#[test]
+fn ring_areas() {
+    let clockwise = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    assert_eq!(ring_area(&clockwise, 2), 1.0);
+    let counter_clockwise = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0];
+    assert_eq!(ring_area(&counter_clockwise, 2), -1.0);
+    // extra coordinates are ignored
+    let raised = [0.0, 0.0, 5.0, 2.0, 0.0, 5.0, 2.0, 2.0, 5.0, 0.0, 2.0, 5.0];
+    assert_eq!(ring_area(&raised, 3), 4.0);
+    assert_eq!(ring_area::<f64>(&[], 2), 0.0);
+}
+
+#[test]
+fn strict() {
+    // two edges of the notch at the top cross each other around (5, 11)
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("self-intersecting"));
+    let result = try_earcut_strict::<f64, usize>(&vertices, &hole_indices, dim);
+    assert_eq!(result, Err(EarcutError::NotSimple(4)));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "polygon is not simple near vertex 4"
+    );
+
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("building"));
+    let indices = try_earcut_strict::<f64, usize>(&vertices, &hole_indices, dim);
+    assert_eq!(indices, Ok(earcut(&vertices, &hole_indices, dim)));
+}
+
+#[test]
+fn strict_winding() {
+    let outer = [0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0];
+    let hole = [2.0, 2.0, 2.0, 8.0, 8.0, 8.0, 8.0, 2.0];
+    let reversed = |ring: &[f64]| -> Vec<f64> { ring.chunks(2).rev().flatten().copied().collect() };
+
+    let valid = [&outer[..], &hole[..]].concat();
+    assert!(try_earcut_strict::<f64, usize>(&valid, &[4], 2).is_ok());
+    let data = [reversed(&outer), hole.to_vec()].concat();
+    assert_eq!(
+        try_earcut_strict::<f64, usize>(&data, &[4], 2),
+        Err(EarcutError::WrongWinding(0))
+    );
+    let data = [outer.to_vec(), reversed(&hole)].concat();
+    assert_eq!(
+        try_earcut_strict::<f64, usize>(&data, &[4], 2),
+        Err(EarcutError::WrongWinding(1))
+    );
+}
+
+#[test]
+fn report() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("bad-hole"));
+    let indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    let report = triangulation_report(&vertices, &hole_indices, dim, &indices);
+    assert_eq!(report.triangle_count, 43);
+    assert_eq!(
+        report.difference,
+        report.triangles_area - report.polygon_area
+    );
+    assert_eq!(
+        report.deviation(),
+        deviation(&vertices, &hole_indices, dim, &indices)
+    );
+
+    // a unit square with a quarter sized hole
+    let data = [
+        0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0, 0.5, 0.5, 0.5, 1.5, 1.5, 1.5, 1.5, 0.5,
+    ];
+    let indices: Vec<usize> = earcut(&data, &[4], 2);
+    let report = triangulation_report(&data, &[4], 2, &indices);
+    assert_eq!(report.polygon_area, 3.0);
+    assert_eq!(report.triangles_area, 3.0);
+    assert_eq!(report.deviation(), 0.0);
+}
+
+#[test]
+fn self_intersections() {
+    let bowtie = [0.0, 0.0, 2.0, 2.0, 2.0, 0.0, 0.0, 2.0];
+    assert!(has_self_intersections(&bowtie, 2));
+    let quad = [0.0, 0.0, 2.0, 0.0, 3.0, 2.0, 0.0, 1.0];
+    assert!(!has_self_intersections(&quad, 2));
+    // a closing point and duplicates don't count as touching
+    let closed = [0.0, 0.0, 2.0, 0.0, 2.0, 0.0, 3.0, 2.0, 0.0, 1.0, 0.0, 0.0];
+    assert!(!has_self_intersections(&closed, 2));
+
+    // large rings take the sweep line path
+    let mut circle = regular_polygon(200, 1.0);
+    assert!(!has_self_intersections(&circle, 2));
+    // pull the top point down through the bottom of the circle
+    circle[100] = 0.0;
+    circle[101] = -2.0;
+    assert!(has_self_intersections(&circle, 2));
+    // or onto the first point, so the ring only touches itself there
+    circle[100] = 1.0;
+    circle[101] = 0.0;
+    assert!(has_self_intersections(&circle, 2));
+}
+
+#[test]
+fn slivers() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("degenerate"));
+    let mut indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    assert_eq!(filter_slivers(&vertices, &mut indices, 1e-9, dim), 0);
+    assert!(indices.is_empty());
+
+    // a unit square next to a sliver that is only 0.001 high
+    let vertices = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 2.0, 0.0, 2.0, 0.001];
+    let mut indices: Vec<u32> = vec![0, 1, 2, 1, 4, 5, 2, 3, 0];
+    assert_eq!(filter_slivers(&vertices, &mut indices, 0.01, 2), 1);
+    assert_eq!(indices, vec![0, 1, 2, 2, 3, 0]);
+    assert_eq!(filter_slivers(&vertices, &mut indices, 0.5, 2), 0);
+}
+
+/// the smallest angle of any triangle in radians
+fn min_angle(vertices: &[f64], indices: &[usize]) -> f64 {
+    let mut min = f64::INFINITY;
+    for triangle in indices.chunks(3) {
+        for k in 0..3 {
+            let p = |i: usize| [vertices[triangle[i] * 2], vertices[triangle[i] * 2 + 1]];
+            let (a, b, c) = (p(k), p((k + 1) % 3), p((k + 2) % 3));
+            let (u, v) = ([b[0] - a[0], b[1] - a[1]], [c[0] - a[0], c[1] - a[1]]);
+            let angle = (u[0] * v[1] - u[1] * v[0])
+                .abs()
+                .atan2(u[0] * v[0] + u[1] * v[1]);
+            min = min.min(angle);
+        }
+    }
+    min
+}
+
+#[test]
+fn delaunay() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let mut indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    let before = min_angle(&vertices, &indices);
+    let deviation_before = deviation(&vertices, &hole_indices, dim, &indices);
+
+    assert!(delaunay_flip(&vertices, &mut indices, dim) > 0);
+    assert!(min_angle(&vertices, &indices) > before);
+    let deviation_after = deviation(&vertices, &hole_indices, dim, &indices);
+    assert!((deviation_after - deviation_before).abs() < 1e-12);
+    assert!(all_same_winding(&vertices, &indices, dim));
+    // already Delaunay, so nothing is left to flip
+    assert_eq!(delaunay_flip(&vertices, &mut indices, dim), 0);
+}
+
+#[test]
+fn annulus() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("annulus"));
+    let mut indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    assert_eq!(indices.len() / 3, 128);
+    assert_eq!(deviation(&vertices, &hole_indices, dim, &indices), 0.0);
+
+    // the bridge is the horizontal diagonal from the hole's leftmost vertex (95) straight out to
+    // the outer ring's (32), flanked by the two quads of the concentric rings on either side
+    let bridge: Vec<&[usize]> = indices
+        .chunks(3)
+        .filter(|t| t.contains(&32) && t.contains(&95))
+        .collect();
+    assert_eq!(bridge, [&[31, 32, 95][..], &[94, 95, 32]]);
+    assert!(bridge.iter().all(|t| min_angle(&vertices, t) > 0.09));
+
+    // the raw output is unchanged from the generic path: its ear order fans slivers out of the
+    // bridge vertex, which `with_best_ears` doesn't avoid either, and flipping cleans them up
+    assert!(min_angle(&vertices, &indices) < 0.01);
+    delaunay_flip(&vertices, &mut indices, dim);
+    assert!(min_angle(&vertices, &indices) > 0.09);
+}
+
+#[test]
+fn best_ears() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let default: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    let mut best = Vec::new();
+    let mut store = Store::new().with_best_ears(8);
+    earcut_impl(&vertices, &hole_indices, dim, &mut best, &mut store);
+    assert_eq!(best.len(), default.len());
+    assert!(min_angle(&vertices, &best) > min_angle(&vertices, &default));
+    assert!(deviation(&vertices, &hole_indices, dim, &best) < 1e-12);
+}
+
+#[test]
+fn observed_ears() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let ring_len = |nodes: &[Node<f64>], start_i: usize| {
+        let (mut len, mut p_i) = (1, nodes[start_i].next());
+        while p_i != start_i {
+            assert!(nodes[p_i].index() < vertices.len() / dim);
+            assert_eq!(nodes[nodes[p_i].prev()].next(), p_i);
+            len += 1;
+            p_i = nodes[p_i].next();
+        }
+        len
+    };
+    let mut lens = Vec::new();
+    let mut triangles: Vec<usize> = Vec::new();
+    let mut store = Store::new();
+    let stats = earcut_observed(
+        &vertices,
+        &hole_indices,
+        dim,
+        &mut triangles,
+        &mut store,
+        |nodes, ring_i| lens.push(ring_len(nodes, ring_i)),
+    );
+    assert_eq!(stats.split_count, 0);
+    assert_eq!(
+        triangles,
+        earcut::<f64, usize>(&vertices, &hole_indices, dim)
+    );
+    assert_eq!(lens.len(), triangles.len() / 3);
+    // every ear takes a node off the ring, down to the last triangle's
+    assert!(lens.windows(2).all(|w| w[1] < w[0]));
+    assert_eq!(lens.last(), Some(&2));
+    assert!(!store.nodes().is_empty());
+}
+
+#[test]
+fn flatten_reuses_buffers() {
+    let mut vertices: Vec<f64> = vec![1.0; 1 << 16];
+    let mut hole_indices: Vec<usize> = vec![7; 64];
+    for id in ["water", "dude", "building"] {
+        let polygon = fixture(id);
+        let capacity = vertices.capacity();
+        let dim = flatten_into(&polygon, &mut vertices, &mut hole_indices);
+        assert_eq!(
+            (vertices.clone(), hole_indices.clone(), dim),
+            flatten(&polygon)
+        );
+        assert_eq!(vertices.capacity(), capacity);
+    }
+}
+
+#[test]
+fn flatten_fixed_dim() {
+    let polygon = vec![
+        vec![
+            vec![0.0, 0.0, 1.0],
+            vec![4.0, 0.0],
+            vec![4.0, 4.0, 2.0],
+            vec![0.0, 4.0],
+        ],
+        vec![vec![1.0, 2.0], vec![2.0, 3.0, 3.0, 9.0], vec![3.0, 2.0]],
+    ];
+    let (vertices, hole_indices): (Vec<f64>, Vec<usize>) = flatten_dim(&polygon, 3);
+    assert_eq!(
+        vertices,
+        [
+            0.0, 0.0, 1.0, 4.0, 0.0, 0.0, 4.0, 4.0, 2.0, 0.0, 4.0, 0.0, 1.0, 2.0, 0.0, 2.0, 3.0,
+            3.0, 3.0, 2.0, 0.0
+        ]
+    );
+    assert_eq!(hole_indices, [4]);
+
+    let (vertices, _): (Vec<f64>, Vec<usize>) = flatten_dim(&polygon, 2);
+    assert_eq!(vertices.len(), 14);
+    assert_eq!(vertices[..4], [0.0, 0.0, 4.0, 0.0]);
+    let indices: Vec<usize> = earcut(&vertices, &hole_indices, 2);
+    assert_eq!(indices.len(), 7 * 3);
+}
+
+#[test]
+fn point_in_shapes() {
+    let (a, b, c) = ([0.0, 0.0], [4.0, 0.0], [0.0, 4.0]);
+    for (a, b, c) in [(a, b, c), (a, c, b)] {
+        assert!(point_in_triangle(a, b, c, [1.0, 1.0]));
+        assert!(!point_in_triangle(a, b, c, [3.0, 3.0]));
+        assert!(!point_in_triangle(a, b, c, [-1.0, 1.0]));
+        // on an edge and on a corner
+        assert!(point_in_triangle(a, b, c, [2.0, 2.0]));
+        assert!(point_in_triangle(a, b, c, [4.0, 0.0]));
+    }
+
+    // a U shape, so the ray crosses the boundary more than once
+    let ring = [
+        0.0, 0.0, 6.0, 0.0, 6.0, 6.0, 4.0, 6.0, 4.0, 2.0, 2.0, 2.0, 2.0, 6.0, 0.0, 6.0,
+    ];
+    let reversed: Vec<f64> = ring.chunks(2).rev().flatten().copied().collect();
+    for ring in [&ring[..], &reversed] {
+        assert!(point_in_polygon(ring, 2, [1.0, 4.0]));
+        assert!(point_in_polygon(ring, 2, [5.0, 1.0]));
+        assert!(!point_in_polygon(ring, 2, [3.0, 4.0]));
+        assert!(!point_in_polygon(ring, 2, [7.0, 1.0]));
+        // on edges and vertices
+        assert!(point_in_polygon(ring, 2, [3.0, 2.0]));
+        assert!(point_in_polygon(ring, 2, [6.0, 3.0]));
+        assert!(point_in_polygon(ring, 2, [4.0, 6.0]));
+    }
+    assert!(!point_in_polygon(&[] as &[f64], 2, [0.0, 0.0]));
+}
+
+#[test]
+fn snapped_noise() {
+    let (mut vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    snap_to_grid(&mut vertices, dim, 0.5);
+    assert!(vertices.iter().all(|v| (v * 2.0).fract() == 0.0));
+    let expected: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+
+    // jitter every coordinate by less than half a cell
+    let mut noisy = vertices.clone();
+    for (i, v) in noisy.iter_mut().enumerate() {
+        *v += ((i * 7919) % 13) as f64 / 13.0 * 0.4 - 0.2;
+    }
+    assert_ne!(noisy, vertices);
+    snap_to_grid(&mut noisy, dim, 0.5);
+    assert_eq!(noisy, vertices);
+    assert_eq!(earcut::<f64, usize>(&noisy, &hole_indices, dim), expected);
+
+    // a zero grid does nothing
+    snap_to_grid(&mut noisy, dim, 0.0);
+    assert_eq!(noisy, vertices);
+}
+
+#[test]
+fn segment_intersections() {
+    let hit = |a1, a2, b1, b2| {
+        // the answer doesn't depend on the order of the segments or their ends
+        let all = [
+            segments_intersect(a1, a2, b1, b2),
+            segments_intersect(a2, a1, b2, b1),
+            segments_intersect(b1, b2, a1, a2),
+        ];
+        assert!(all.iter().all(|&v| v == all[0]));
+        all[0]
+    };
+    // crossing
+    assert!(hit([0.0, 0.0], [2.0, 2.0], [0.0, 2.0], [2.0, 0.0]));
+    // touching at an endpoint, or with an endpoint on the other segment
+    assert!(hit([0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [2.0, 0.0]));
+    assert!(hit([0.0, 0.0], [2.0, 0.0], [1.0, 0.0], [1.0, 1.0]));
+    // collinear and overlapping, or only in line
+    assert!(hit([0.0, 0.0], [2.0, 0.0], [1.0, 0.0], [3.0, 0.0]));
+    assert!(!hit([0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0]));
+    // disjoint
+    assert!(!hit([0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]));
+    assert!(!hit([0.0, 0.0], [2.0, 2.0], [3.0, 0.0], [2.0, 1.0]));
+}
+
+#[test]
+fn mesh_area() {
+    let vertices = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    assert_eq!(triangulated_area(&vertices, &[0_usize, 1, 2], 2), 0.5);
+    // the winding doesn't matter
+    assert_eq!(triangulated_area(&vertices, &[0_usize, 2, 1], 2), 0.5);
+    assert_eq!(triangulated_area::<f64, usize>(&vertices, &[], 2), 0.0);
+
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let indices = earcut(&vertices, &hole_indices, dim);
+    let report = triangulation_report(&vertices, &hole_indices, dim, &indices);
+    assert_eq!(
+        triangulated_area(&vertices, &indices, dim),
+        report.triangles_area
+    );
+}
+
+#[test]
+fn convex() {
+    for n in [3, 12, 100] {
+        let ring = regular_polygon(n, 10.0);
+        let indices = earcut::<f64, usize>(&ring, &[], 2);
+        assert_eq!(indices.len(), (n - 2) * 3);
+        assert!(deviation(&ring, &[], 2, &indices) < 1e-13);
+
+        // a collinear midpoint makes the ring non-convex, so it takes the general path
+        let mut general = ring.clone();
+        general.extend([
+            (ring[0] + ring[2 * n - 2]) / 2.0,
+            (ring[1] + ring[2 * n - 1]) / 2.0,
+        ]);
+        let general_indices = earcut::<f64, usize>(&general, &[], 2);
+        let area = triangulated_area(&ring, &indices, 2);
+        assert!((area - triangulated_area(&general, &general_indices, 2)).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn collinear() {
+    let line = [0.0, 0.0, 1.0, 1.0, 2.0, 2.0];
+    assert!(earcut::<f64, usize>(&line, &[], 2).is_empty());
+
+    let line: Vec<f64> = (0..50).flat_map(|i| [i as f64 * 3.0, 7.0]).collect();
+    assert!(earcut::<f64, usize>(&line, &[], 2).is_empty());
+    // going back and forth along the line
+    let back: Vec<f64> = line.chunks(2).rev().flatten().copied().collect();
+    let there_and_back: Vec<f64> = line.iter().chain(&back).copied().collect();
+    assert!(earcut::<f64, usize>(&there_and_back, &[], 2).is_empty());
+}
+
+#[test]
+fn steiner_points() {
+    let square = [0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0];
+    let indices = earcut_with_steiner::<f64, usize>(&square, &[], &[[4.0, 6.0]], 2);
+    assert_eq!(indices.len(), 4 * 3);
+    assert_eq!(indices.iter().filter(|&&i| i == 4).count(), 4);
+    let mut vertices = square.to_vec();
+    vertices.extend([4.0, 6.0]);
+    assert_eq!(triangulated_area(&vertices, &indices, 2), 100.0);
+
+    // 3D vertices take 2D steiner points too
+    let cube_face = [
+        0.0, 0.0, 1.0, 10.0, 0.0, 1.0, 10.0, 10.0, 1.0, 0.0, 10.0, 1.0,
+    ];
+    let indices = earcut_with_steiner::<f64, usize>(&cube_face, &[], &[[4.0, 6.0]], 3);
+    assert_eq!(indices.iter().filter(|&&i| i == 4).count(), 4);
+}
+
+#[test]
+fn simplify() {
+    // a redundant midpoint on the bottom edge and a repeated corner
+    let ring = [
+        0.0, 0.0, 5.0, 0.0, 10.0, 0.0, 10.0, 10.0, 10.0, 10.0, 0.0, 10.0,
+    ];
+    let simplified = simplify_ring(&ring, 2, None);
+    assert_eq!(simplified, [0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0]);
+    assert_eq!(ring_area(&simplified, 2), ring_area(&ring, 2));
+
+    // nearly collinear points only go with an epsilon, and keep their other coordinates
+    let ring = [
+        0.0, 0.0, 1.0, 5.0, 0.01, 2.0, 10.0, 0.0, 3.0, 10.0, 10.0, 4.0,
+    ];
+    assert_eq!(simplify_ring(&ring, 3, None), ring);
+    assert_eq!(
+        simplify_ring(&ring, 3, Some(0.1)),
+        [0.0, 0.0, 1.0, 10.0, 0.0, 3.0, 10.0, 10.0, 4.0]
+    );
+
+    assert!(simplify_ring::<f64>(&[], 2, None).is_empty());
+    assert!(simplify_ring(&[0.0, 0.0, 1.0, 1.0, 2.0, 2.0], 2, None).len() < 6);
+}
+
+#[test]
+fn spikes() {
+    // a unit square with a spike out of its top right corner
+    let spiky = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 2.0, 2.0, 1.0, 1.0, 0.0, 1.0];
+    let square = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    let cleaned = remove_spikes(&spiky, 2);
+    assert_eq!(cleaned, square);
+    let indices: Vec<usize> = earcut(&cleaned, &[], 2);
+    assert_eq!(indices.len(), 6);
+    assert_eq!(deviation(&cleaned, &[], 2, &indices), 0.0);
+
+    // a spike of several points along a line, next to a collinear point that stays
+    let spiky = [
+        0.0, 0.0, 0.5, 0.0, 1.0, 0.0, 1.5, -1.0, 2.0, -2.0, 1.5, -1.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+    ];
+    assert_eq!(
+        remove_spikes(&spiky, 2),
+        [0.0, 0.0, 0.5, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0]
+    );
+
+    // a spike across the start of the ring, with extra coordinates kept
+    let spiky = [
+        0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 3.0, 1.0, 2.0, 3.0, 3.0, 3.0, 1.0, 3.0, 4.0, 1.0, 1.0, 5.0,
+    ];
+    assert_eq!(
+        remove_spikes(&spiky, 3),
+        [1.0, 1.0, 1.0, 3.0, 1.0, 2.0, 3.0, 3.0, 3.0, 1.0, 3.0, 4.0]
+    );
+    assert!(remove_spikes::<f64>(&[], 2).is_empty());
+}
+
+#[test]
+fn validate() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let mut indices = earcut(&vertices, &hole_indices, dim);
+    assert!(validate_triangulation(
+        &vertices,
+        &hole_indices,
+        &indices,
+        dim
+    ));
+
+    // pointing a triangle at a vertex across the polygon makes it spill outside
+    let far = (0..vertices.len() / dim)
+        .max_by(|&a, &b| vertices[a * dim].total_cmp(&vertices[b * dim]))
+        .unwrap();
+    let near = (0..vertices.len() / dim)
+        .min_by(|&a, &b| vertices[a * dim].total_cmp(&vertices[b * dim]))
+        .unwrap();
+    let t = indices.chunks(3).position(|t| t.contains(&near)).unwrap() * 3;
+    let k = indices[t..t + 3].iter().position(|&i| i != near).unwrap();
+    indices[t + k] = far;
+    assert!(!validate_triangulation(
+        &vertices,
+        &hole_indices,
+        &indices,
+        dim
+    ));
+
+    assert!(validate_triangulation::<f64, usize>(&[], &[], &[], 2));
+}
+
+#[test]
+fn quads() {
+    let square = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    let indices = earcut::<f64, usize>(&square, &[], 2);
+    let (triangles, quads) = merge_to_quads(&square, &indices, 2);
+    assert!(triangles.is_empty());
+    assert_eq!(quads.len(), 4);
+    let mut corners = quads.clone();
+    corners.sort();
+    assert_eq!(corners, [0, 1, 2, 3]);
+    // the quad keeps the winding of its triangles
+    let quad: Vec<f64> = quads
+        .iter()
+        .flat_map(|&i| [square[i * 2], square[i * 2 + 1]])
+        .collect();
+    assert!(is_clockwise(&quad, 2));
+
+    // a dart's two triangles form a concave quad
+    let dart = [0.0, 0.0, 2.0, 1.0, 0.0, 2.0, 0.5, 1.0];
+    let indices = earcut::<f64, usize>(&dart, &[], 2);
+    let (triangles, quads) = merge_to_quads(&dart, &indices, 2);
+    assert_eq!((triangles.len(), quads.len()), (6, 0));
+
+    // folded along the diagonal, the triangles aren't coplanar
+    let folded = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0];
+    let (triangles, quads) = merge_to_quads(&folded, &[0_usize, 1, 2, 0, 2, 3], 3);
+    assert_eq!((triangles.len(), quads.len()), (6, 0));
+    let flat = [0.0, 0.0, 5.0, 1.0, 0.0, 5.0, 1.0, 1.0, 5.0, 0.0, 1.0, 5.0];
+    let (triangles, quads) = merge_to_quads(&flat, &[0_usize, 1, 2, 0, 2, 3], 3);
+    assert_eq!((triangles.len(), quads.len()), (0, 4));
+}
+
+#[test]
+fn morton_bits() {
+    // the precision of the z-order index only changes how fast ears are found
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("water"));
+    let expected = earcut::<f64, usize>(&vertices, &hole_indices, dim);
+    for bits in [1, 21, 64] {
+        let mut store = Store::default().with_morton_bits(bits);
+        let mut indices = Vec::new();
+        earcut_into(&vertices, &hole_indices, dim, &mut indices, &mut store);
+        assert_eq!(indices, expected);
+    }
+}
+
+#[test]
+fn triangle_iterator() {
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("dude"));
+    let triangles = triangulate_iter::<f64, usize>(&vertices, &hole_indices, dim);
+    assert_eq!(triangles.len(), 106);
+    let area: f64 = triangles
+        .map(|triangle| triangulated_area(&vertices, &triangle, dim))
+        .sum();
+    let indices = earcut(&vertices, &hole_indices, dim);
+    assert!((area - triangulated_area(&vertices, &indices, dim)).abs() < 1e-6);
+}
+
+#[test]
+fn ring_areas_from_flatten() {
+    let polygon = fixture("dude");
+    let (vertices, hole_indices, dim, areas) = flatten_with_areas::<f64, usize>(&polygon);
+    assert_eq!((vertices, hole_indices, dim), flatten(&polygon));
+    assert_eq!(areas.len(), polygon.len());
+    // the outer ring winds the other way from its holes
+    assert!(areas[0] < 0.0);
+    assert!(areas[1..].iter().all(|&area| area > 0.0));
+    let ring: Vec<f64> = polygon[1].iter().flatten().copied().collect();
+    assert_eq!(areas[1], ring_area(&ring, 2));
+}
+
+#[test]
+fn prefix_range() {
+    // a square followed by scratch data that isn't part of it
+    let data = [
+        0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 99.0, -5.0, 42.0, 7.0,
+    ];
+    let indices = earcut_range::<f64, usize>(&data, &[], 2, 4);
+    assert_eq!(indices, earcut::<f64, usize>(&data[..8], &[], 2));
+    assert!(indices.iter().all(|&i| i < 4));
+    assert_eq!(triangulated_area(&data, &indices, 2), 100.0);
+
+    // with a hole, the hole ends at `end` instead
+    let data = [
+        0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 2.0, 2.0, 2.0, 4.0, 4.0, 4.0, 4.0, 2.0, 50.0,
+        50.0,
+    ];
+    let indices = earcut_range::<f64, usize>(&data, &[4], 2, 8);
+    assert_eq!(triangulated_area(&data, &indices, 2), 96.0);
+}
+
+#[test]
+fn known_bbox() {
+    let (data, hole_indices, dim) = flatten_float(&fixture("water"));
+    let [mut min_x, mut min_y] = [f64::INFINITY; 2];
+    let [mut max_x, mut max_y] = [f64::NEG_INFINITY; 2];
+    for xy in data.chunks_exact(dim) {
+        (min_x, min_y) = (min_x.min(xy[0]), min_y.min(xy[1]));
+        (max_x, max_y) = (max_x.max(xy[0]), max_y.max(xy[1]));
+    }
+    let indices: Vec<usize> = earcut(&data, &hole_indices, dim);
+    let bbox = [min_x, min_y, max_x, max_y];
+    assert_eq!(earcut_with_bbox(&data, &hole_indices, dim, bbox), indices);
+
+    // a looser box, e.g. a tile with its buffer, only changes the z-order hash
+    let loose: Vec<usize> =
+        earcut_with_bbox(&data, &hole_indices, dim, [-256.0, -256.0, 4352.0, 4352.0]);
+    assert_eq!(loose.len(), indices.len());
+    assert!(deviation(&data, &hole_indices, dim, &loose) < 0.001);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "does not contain the outer ring")]
+fn bbox_too_small() {
+    let (data, hole_indices, dim) = flatten_float(&fixture("water"));
+    earcut_with_bbox::<f64, usize>(&data, &hole_indices, dim, [0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn pass_stats() {
+    // a convex polygon is cut in the first pass, with or without the z-order index
+    for n in [12, 100] {
+        let ring = regular_polygon(n, 10.0);
+        let mut triangles: Vec<usize> = Vec::new();
+        let stats = earcut_impl(&ring, &[], 2, &mut triangles, &mut Store::default());
+        let expected = PassStats {
+            p0: n - 2,
+            points_tested: stats.points_tested,
+            ..PassStats::default()
+        };
+        assert_eq!(stats, expected);
+    }
+
+    // water needs every pass but splitting, and the counts cover all triangles
+    let (data, hole_indices, dim) = flatten_float(&fixture("water"));
+    let mut triangles: Vec<usize> = Vec::new();
+    let mut store = Store::default();
+    let stats = earcut_impl(&data, &hole_indices, dim, &mut triangles, &mut store);
+    assert!(stats.p1 > 0 && stats.p2 > 0 && stats.split_count == 0);
+    assert_eq!(stats.p0 + stats.p1 + stats.p2, triangles.len() / 3);
+    // an hourglass can only be split
+    let (data, hole_indices, dim) = flatten_float(&fixture("hourglass"));
+    let stats = earcut_impl(&data, &hole_indices, dim, &mut triangles, &mut store);
+    assert!(stats.split_count > 0);
+}
+
+#[test]
+fn split_depth() {
+    // a ring through random points crosses itself all over and needs many splits
+    let mut seed = 12345_u64;
+    let ring: Vec<f64> = (0..600)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) as f64 / (1_u64 << 31) as f64 * 100.0).round()
+        })
+        .collect();
+    let mut full: Vec<usize> = Vec::new();
+    let stats = earcut_impl(&ring, &[], 2, &mut full, &mut Store::default());
+    assert!(stats.split_count > 2);
+    assert!(!stats.split_limit_hit);
+
+    // with only two halves allowed to wait, the rest are left out
+    let mut triangles: Vec<usize> = Vec::new();
+    let mut store = Store::new().with_split_depth(2);
+    let stats = earcut_impl(&ring, &[], 2, &mut triangles, &mut store);
+    assert!(stats.split_limit_hit);
+    assert!(triangles.len() < full.len());
+    assert!(triangles.chunks(3).all(|t| full.chunks(3).any(|f| f == t)));
+
+    // a cap that is never reached changes nothing
+    let mut store = Store::new().with_split_depth(100);
+    earcut_into(&ring, &[], 2, &mut triangles, &mut store);
+    assert_eq!(triangles, full);
+}
+
+#[test]
+fn preserved_boundary_vertices() {
+    // (0, 2) sits on the straight left edge of the square, which the hole gets bridged to
+    let vertices = [
+        0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0, 0.0, 2.0, 1.0, 1.0, 1.0, 3.0, 3.0, 3.0, 3.0, 1.0,
+    ];
+    let default: Vec<usize> = earcut(&vertices, &[5], 2);
+    assert!(!default.contains(&4));
+
+    let mut preserved: Vec<usize> = Vec::new();
+    let mut store = Store::new().with_preserve_boundary_vertices(true);
+    earcut_into(&vertices, &[5], 2, &mut preserved, &mut store);
+    assert!(preserved.contains(&4));
+    assert_eq!(deviation(&vertices, &[5], 2, &preserved), 0.0);
+}
+
+#[test]
+fn square_hole_sharing_edge() {
+    // the hole's bottom edge is the middle of the outer ring's bottom edge
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("hole-sharing-edge-square"));
+    let indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+    assert_eq!(deviation(&vertices, &hole_indices, dim, &indices), 0.0);
+    assert_eq!(triangulated_area(&vertices, &indices, dim), 100.0 - 16.0);
+}
+
+#[test]
+fn holes_sharing_leftmost_x() {
+    let outer = [0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0];
+    let low = [2.0, 2.0, 2.0, 4.0, 4.0, 4.0, 4.0, 2.0];
+    let high = [2.0, 6.0, 2.0, 8.0, 4.0, 8.0, 4.0, 6.0];
+    let triangulate = |holes: [&[f64]; 2]| {
+        let vertices = [&outer[..], holes[0], holes[1]].concat();
+        let indices: Vec<usize> = earcut(&vertices, &[4, 8], 2);
+        let triangles: Vec<[f64; 2]> = indices
+            .iter()
+            .map(|&i| [vertices[i * 2], vertices[i * 2 + 1]])
+            .collect();
+        (indices, triangles)
+    };
+
+    let (indices, triangles) = triangulate([&low, &high]);
+    for _ in 0..8 {
+        assert_eq!(triangulate([&low, &high]).0, indices);
+    }
+    // the holes are bridged bottom up whichever comes first in the input
+    assert_eq!(triangulate([&high, &low]).1, triangles);
+}
+
+#[test]
+fn duplicate_vertices() {
+    // an L with every corner but one repeated, and the closing point repeated twice
+    let data = [
+        0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 4.0, 0.0, 4.0, 0.0, 4.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 4.0,
+        2.0, 4.0, 0.0, 4.0, 0.0, 0.0, 0.0, 0.0,
+    ];
+    let mut triangles: Vec<usize> = Vec::new();
+    let stats = earcut_impl(&data, &[], 2, &mut triangles, &mut Store::default());
+    // the six distinct corners make four triangles, all in the first pass
+    assert_eq!(stats.p0, 4);
+    assert_eq!(
+        stats,
+        PassStats {
+            p0: 4,
+            points_tested: stats.points_tested,
+            ..PassStats::default()
+        }
+    );
+    assert_eq!(triangulated_area(&data, &triangles, 2), 12.0);
+    assert!(validate_triangulation(&data, &[], &triangles, 2));
+
+    // a hole made of one repeated point isn't a steiner point
+    let mut with_hole = data.to_vec();
+    with_hole.extend([1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+    let indices: Vec<usize> = earcut(&with_hole, &[13], 2);
+    assert_eq!(triangulated_area(&with_hole, &indices, 2), 12.0);
+    assert!(indices.iter().all(|&i| i < 13));
+}
+
+#[test]
+fn suggested_tolerance() {
+    for id in [
+        "water",
+        "water-huge",
+        "water-huge2",
+        "bad-hole",
+        "dude",
+        "hilbert",
+    ] {
+        let (vertices, hole_indices, dim) = flatten_float(&fixture(id));
+        let indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+        let actual = deviation(&vertices, &hole_indices, dim, &indices);
+        let suggested = suggest_tolerance::<f64, usize>(&vertices, &hole_indices, dim);
+        assert!(suggested >= actual, "{id}: {suggested} < {actual}");
+        assert!(
+            suggested <= actual * 1.1,
+            "{id}: {suggested} is too loose for {actual}"
+        );
+    }
+    let (vertices, hole_indices, dim) = flatten_float(&fixture("water"));
+    assert_eq!(
+        suggest_tolerance::<f64, usize>(&vertices, &hole_indices, dim),
+        0.00079
+    );
+    let square = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+    assert_eq!(suggest_tolerance::<f64, usize>(&square, &[], 2), 0.0);
+}
+
+#[test]
+fn same_winding_holes() {
+    // the hole is wound the same way as the outer ring
+    let mut data = vec![
+        0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0, 2.0, 2.0, 8.0, 2.0, 8.0, 8.0, 2.0, 8.0,
+    ];
+    assert!(is_clockwise(&data[..8], 2) && is_clockwise(&data[8..], 2));
+    let indices: Vec<usize> = earcut(&data, &[4], 2);
+    assert_eq!(indices.len(), 8 * 3);
+    assert_eq!(triangulated_area(&data, &indices, 2), 64.0);
+    assert_eq!(
+        try_earcut_strict::<f64, usize>(&data, &[4], 2),
+        Err(EarcutError::WrongWinding(1))
+    );
+
+    normalize_winding(&mut data, &[4_usize], 2);
+    assert!(is_clockwise(&data[..8], 2) && is_counter_clockwise(&data[8..], 2));
+    assert_eq!(&data[8..10], &[2.0, 8.0]);
+    let strict = try_earcut_strict::<f64, usize>(&data, &[4], 2).unwrap();
+    assert_eq!(triangulated_area(&data, &strict, 2), 64.0);
+}
+
+#[test]
+fn slice_buffers() {
+    let (triangles, _) = expected();
+    let mut nodes = vec![Node::default(); 1 << 16];
+    let mut out = vec![0_usize; 1 << 16];
+    for id in triangles.keys() {
+        let (vertices, hole_indices, dim) = flatten_float(&fixture(id));
+        let indices: Vec<usize> = earcut(&vertices, &hole_indices, dim);
+        let len = earcut_into_slice(&vertices, &hole_indices, dim, &mut nodes, &mut out);
+        assert_eq!(len, Ok(indices.len()), "{id}");
+        assert_eq!(&out[..indices.len()], indices.as_slice(), "{id}");
+    }
+}
+
+#[test]
+fn exact_orientation() {
+    let naive = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+        (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+    };
+    // a few ulps off the line through (12, 12) and (24, 24), to its right
+    let ulp = f64::EPSILON / 2.0;
+    let a = [0.5 + 41.0 * ulp, 0.5 + 48.0 * ulp];
+    let (b, c) = ([12.0, 12.0], [24.0, 24.0]);
+    assert!(naive(a, b, c) < 0.0);
+    assert!(orient2d(a, b, c) > 0.0);
+    assert!(orient2d(b, a, c) < 0.0);
+    // exactly on the line
+    assert_eq!(orient2d([0.5, 0.5], b, c), 0.0);
+    // well away from degenerate, the plain formula is used as is
+    assert_eq!(orient2d([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]), 1.0);
+    assert!(
+        orient2d(
+            [0.5f32, 0.5],
+            [12.0, 12.0],
+            [24.0, 24.0 + f32::EPSILON * 16.0]
+        ) > 0.0
+    );
+}
+
+/// a thin, simple ring along the diagonal with its vertices a few ulps off it, where the plain
+/// area formula rounds one of the reflex vertices to convex
+fn near_degenerate_ring() -> Vec<f64> {
+    vec![
+        1.4999999999999956,
+        1.5,
+        6.5,
+        6.499999999999997,
+        11.499999999999998,
+        11.5,
+        14.5,
+        14.5,
+        17.5,
+        17.5,
+        0.0,
+        32.0,
+    ]
+}
+
+/// the exact orientation of each triangle
+fn orientations(data: &[f64], triangles: &[usize]) -> Vec<f64> {
+    let point = |i: usize| [data[i * 2], data[i * 2 + 1]];
+    triangles
+        .chunks(3)
+        .map(|t| orient2d(point(t[0]), point(t[1]), point(t[2])))
+        .collect()
+}
+
+#[test]
+#[cfg(not(feature = "robust"))]
+fn near_degenerate_naive() {
+    let data = near_degenerate_ring();
+    let triangles: Vec<usize> = earcut(&data, &[], 2);
+    // one ear is clipped at a reflex vertex, giving an inverted triangle
+    assert!(orientations(&data, &triangles).iter().any(|&o| o < 0.0));
+    assert!(deviation(&data, &[] as &[usize], 2, &triangles) > 0.0);
+}
+
+#[test]
+#[cfg(feature = "robust")]
+fn near_degenerate_robust() {
+    let data = near_degenerate_ring();
+    let triangles: Vec<usize> = earcut(&data, &[], 2);
+    assert_eq!(triangles.len(), 12);
+    assert!(orientations(&data, &triangles).iter().all(|&o| o > 0.0));
+    assert_eq!(deviation(&data, &[] as &[usize], 2, &triangles), 0.0);
+}
@@ -0,0 +1,94 @@
+//! Runs the library through `core` + `alloc` only, the way a bare-metal target would use it.
+//! Run with `cargo test --no-default-features` to also leave out the `std` feature. The test
+//! harness still links `std`, so CI also builds the library for `thumbv7em-none-eabi`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::fmt::{self, Write};
+
+#[cfg(feature = "std")]
+use earclip::to_svg_path;
+use earclip::{earclip, earcut, earcut_into_slice, tesselate, try_earcut, EarcutError, Node};
+
+#[test]
+fn triangulates_without_std() {
+    let indices: Vec<u16> = earcut(&[0.0_f32, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0], &[], 2);
+    assert_eq!(indices.len(), 6);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        to_svg_path(&[0.0_f32, 0.0, 1.0, 0.0, 1.0, 1.0], &[0_u16, 1, 2], 2),
+        "M0 0 L1 0 L1 1 Z"
+    );
+
+    let polygon = vec![vec![
+        vec![0.5_f32, 0.5],
+        vec![3.5, 0.5],
+        vec![3.5, 3.5],
+        vec![0.5, 3.5],
+    ]];
+    let (mut vertices, mut indices) = earclip::<f32, u32>(&polygon, None, None);
+    // tesselation goes through libm for its float math
+    tesselate(&mut vertices, &mut indices, 1.0, 2);
+    assert!(indices.len() > 6);
+}
+
+/// a fixed size text buffer, so formatting needs no allocation
+struct Buffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn reports_errors_without_std() {
+    let result = try_earcut::<f32, u16>(&[0.0, 0.0, 1.0, f32::NAN, 1.0, 1.0], &[], 2);
+    let error = result.unwrap_err();
+    assert_eq!(error, EarcutError::NonFiniteCoordinate);
+
+    let error: &dyn core::error::Error = &error;
+    let mut buffer = Buffer {
+        bytes: [0; 64],
+        len: 0,
+    };
+    write!(buffer, "{error}").unwrap();
+    assert_eq!(
+        &buffer.bytes[..buffer.len],
+        b"coordinate is NaN or infinite"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn triangulates_into_stack_buffers() {
+    // a square with a square hole
+    let data = [
+        0.0_f32, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0, 1.0, 1.0, 1.0, 3.0, 3.0, 3.0, 3.0, 1.0,
+    ];
+    let mut nodes = [Node::default(); 10];
+    let mut out = [0_u8; 24];
+    let len = earcut_into_slice(&data, &[4], 2, &mut nodes, &mut out).unwrap();
+    assert_eq!(len, 24);
+    assert_eq!(&out[..len], earcut::<f32, u8>(&data, &[4], 2).as_slice());
+
+    // no room for the hole bridge, or for all the triangles
+    let result = earcut_into_slice(&data, &[4_u8], 2, &mut nodes[..9], &mut out);
+    assert_eq!(result, Err(EarcutError::OutOfSpace));
+    let result = earcut_into_slice(&data, &[4_u8], 2, &mut nodes, &mut out[..21]);
+    assert_eq!(result, Err(EarcutError::OutOfSpace));
+    assert_eq!(&out[..21], &earcut::<f32, u8>(&data, &[4], 2)[..21]);
+}
@@ -0,0 +1,56 @@
+//! Everything here comes from the prelude alone, without `std`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use earclip::prelude::*;
+
+struct Point([f32; 2]);
+
+impl Point2D<f32> for Point {
+    fn x(&self) -> f32 {
+        self.0[0]
+    }
+    fn y(&self) -> f32 {
+        self.0[1]
+    }
+}
+
+/// a generic caller only needs the prelude's traits in its bounds
+fn triangle_count<N: Index>(indices: &[N]) -> usize {
+    indices.len() / 3
+}
+
+#[test]
+fn triangulates_from_the_prelude() {
+    let polygon = vec![
+        vec![
+            vec![0.0, 0.0],
+            vec![4.0, 0.0],
+            vec![4.0, 4.0],
+            vec![0.0, 4.0],
+        ],
+        vec![
+            vec![1.0, 1.0],
+            vec![1.0, 3.0],
+            vec![3.0, 3.0],
+            vec![3.0, 1.0],
+        ],
+    ];
+    let (vertices, hole_indices, dim) = flatten::<f64, u32>(&polygon);
+    let indices = earcut(&vertices, &hole_indices, dim);
+    assert_eq!(triangle_count(&indices), 8);
+    assert_eq!(deviation(&vertices, &hole_indices, dim, &indices), 0.0);
+
+    let (_, checked) = try_earclip::<f64, u32>(&polygon, None, None).unwrap();
+    assert_eq!(checked, indices);
+    let mesh: TriangleMesh<f64, u32> = earclip_mesh(&polygon, None);
+    assert_eq!(mesh.indices, indices);
+
+    let points = [Point([0.0, 0.0]), Point([1.0, 0.0]), Point([0.0, 1.0])];
+    let flat: Vec<f32> = points.iter().flat_map(|p| [p.x(), p.y()]).collect();
+    assert_eq!(earcut::<f32, u16>(&flat, &[], 2).len(), 3);
+}